@@ -1,6 +1,7 @@
 use std::{path::PathBuf, str::FromStr};
 
 use clap::{Parser, Subcommand, ValueEnum};
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use tracing::level_filters::LevelFilter;
 
@@ -57,7 +58,20 @@ pub struct Cli {
     #[clap(help_heading = "Global Options", global = true)]
     pub features: Vec<String>,
 
+    /// Only consider dependencies used when building for the given target
+    /// triple (may be passed multiple times)
+    ///
+    /// This is passed to `cargo metadata` as `--filter-platform`, pruning
+    /// platform-specific dependencies which will never be built for the
+    /// selected targets. Overrides the `targets` list in config.toml.
+    #[clap(long, action, value_name = "TRIPLE")]
+    #[clap(help_heading = "Global Options", global = true)]
+    pub target: Vec<String>,
+
     /// Do not fetch new imported audits.
+    ///
+    /// Unless --frozen is also passed, `check` still re-downloads imports to
+    /// verify that they match the hashes recorded in imports.lock.
     #[clap(long, action)]
     #[clap(help_heading = "Global Options", global = true)]
     pub locked: bool,
@@ -69,6 +83,61 @@ pub struct Cli {
     #[clap(help_heading = "Global Options", global = true)]
     pub frozen: bool,
 
+    /// The HTTP(S) proxy to use for all requests, in `[protocol://]host[:port]`
+    /// format.
+    ///
+    /// Defaults to cargo's `http.proxy` setting (or `CARGO_HTTP_PROXY`), and
+    /// then the `HTTPS_PROXY` and `HTTP_PROXY` environment variables.
+    #[clap(long, action, value_name = "PROXY")]
+    #[clap(help_heading = "Global Options", global = true)]
+    pub http_proxy: Option<String>,
+
+    /// The timeout for each HTTP request, in seconds.
+    ///
+    /// Defaults to cargo's `http.timeout` setting (or `CARGO_HTTP_TIMEOUT`),
+    /// and then 60 seconds.
+    #[clap(long, action, value_name = "SECS")]
+    #[clap(help_heading = "Global Options", global = true)]
+    pub http_timeout: Option<u64>,
+
+    /// How many times to retry HTTP requests which fail with possibly spurious
+    /// errors, such as timeouts, server errors and dropped connections.
+    ///
+    /// Defaults to cargo's `net.retry` setting (or `CARGO_NET_RETRY`), and then
+    /// 3. Retries are made with exponential backoff.
+    #[clap(long, action, value_name = "N")]
+    #[clap(help_heading = "Global Options", global = true)]
+    pub net_retry: Option<u32>,
+
+    /// The maximum number of concurrent HTTP connections. Defaults to 40.
+    #[clap(long, action, value_name = "N")]
+    #[clap(value_parser = clap::value_parser!(u32).range(1..))]
+    #[clap(help_heading = "Global Options", global = true)]
+    pub max_connections: Option<u32>,
+
+    /// The sparse index to use for crates.io, such as a mirror.
+    ///
+    /// Defaults to the registry crates.io is replaced with by cargo's source
+    /// replacement, if it's a sparse registry. Unless `--crates-io-api` and
+    /// `--crates-io-download` are also passed, they're read from the index's
+    /// `config.json`.
+    #[clap(long, action, value_name = "URL")]
+    #[clap(value_parser = crate::network::parse_sparse_index)]
+    #[clap(help_heading = "Global Options", global = true)]
+    pub crates_io_index: Option<Url>,
+
+    /// The root URL of the crates.io web API, which is used to look up crate
+    /// publishers.
+    #[clap(long, action, value_name = "URL")]
+    #[clap(help_heading = "Global Options", global = true)]
+    pub crates_io_api: Option<Url>,
+
+    /// The URL template to download crates.io crates from, in the same format
+    /// as the `dl` key of a registry's `config.json`.
+    #[clap(long, action, value_name = "TEMPLATE")]
+    #[clap(help_heading = "Global Options", global = true)]
+    pub crates_io_download: Option<String>,
+
     /// Prevent commands such as `check` and `certify` from automatically
     /// cleaning up unused exemptions.
     #[clap(long, action)]
@@ -375,6 +444,74 @@ pub enum Commands {
     #[clap(disable_version_flag = true)]
     ExplainAudit(ExplainAuditArgs),
 
+    /// Print why a package is required to satisfy a given criteria.
+    ///
+    /// Prints the dependency path from the package which introduced the
+    /// requirement (such as a top-level crate, or a crate with a `criteria`,
+    /// `dev-criteria` or `dependency-criteria` policy) to the given package,
+    /// along which the requirement was inherited.
+    ///
+    /// With `--output-format=json` the explanation is printed as JSON.
+    #[clap(disable_version_flag = true)]
+    Why(WhyArgs),
+
+    /// Generate a browsable report of the audit status of every third-party
+    /// crate
+    ///
+    /// With `--html <DIR>` this writes a static site to the directory, with a
+    /// page for each crate showing the criteria it's required to satisfy, the
+    /// audit path used to vet it, its audits and exemptions, and the crates
+    /// which depend on it.
+    #[clap(disable_version_flag = true)]
+    Report(ReportArgs),
+
+    /// Print a software bill of materials annotated with audit status
+    ///
+    /// Every package in the dependency graph is listed with its version,
+    /// source and checksum. Third-party packages are annotated with the
+    /// criteria they satisfy, the auditors, imports and trusted publishers
+    /// which vouched for them, and whether they rely on an exemption.
+    #[clap(disable_version_flag = true)]
+    Sbom(SbomArgs),
+
+    /// Summarize the changes to the supply-chain store between git revisions
+    ///
+    /// Rather than the raw TOML, this lists the audits, exemptions, policies,
+    /// criteria, imports and trusted publishers which were added, removed or
+    /// changed, including imported audits which newly come into effect through
+    /// imports.lock. If only one revision is given, it's compared against the
+    /// store in the working tree.
+    #[clap(disable_version_flag = true)]
+    StoreDiff(StoreDiffArgs),
+
+    /// Report how much of the dependency graph is covered by audits
+    ///
+    /// The third-party packages are counted as vetted fully by audits, vetted
+    /// partially (relying on exemptions for some versions), vetted only with
+    /// exemptions, or unvetted. These are broken down by criteria and by the
+    /// sources used to vet them (local audits, each import, wildcard audits,
+    /// trusted publishers and exemptions), both by number of packages and by
+    /// lines of code. Measuring lines of code fetches any crates which aren't
+    /// already in the cache.
+    ///
+    /// With `--output-format=json` the statistics can be recorded to chart
+    /// progress over time.
+    #[clap(disable_version_flag = true)]
+    Stats(StatsArgs),
+
+    /// List every known audit of a crate
+    ///
+    /// This searches the local audits.toml, the imports in imports.lock, and
+    /// the other sets of audits listed in the registry for audits, violations,
+    /// wildcard audits and trusted publishers of the crate, and lists each
+    /// with where it came from. The crate doesn't need to be a dependency, so
+    /// this can be used to find existing reviews before adopting a new crate.
+    ///
+    /// If a version is given, only audits and violations which cover it are
+    /// listed. Wildcard audits and trusted publishers are always listed.
+    #[clap(disable_version_flag = true)]
+    Query(QueryArgs),
+
     /// Print the cargo build graph as understood by `cargo vet`
     ///
     /// This is a debugging command, the output's format is not guaranteed.
@@ -411,12 +548,25 @@ pub enum Commands {
     #[clap(disable_version_flag = true)]
     Gc(GcArgs),
 
-    /// Renew wildcard audit expirations
+    /// Renew wildcard audit and audit/exemption expirations
     ///
-    /// This will set a wildcard audit expiration to be one year in the future from when it is run.
-    /// It can optionally do this for all audits which are expiring soon.
+    /// This will set a wildcard audit expiration, or the `expires` date of an audit or exemption,
+    /// to be one year in the future from when it is run. It can optionally do this for all audits
+    /// which are expiring soon.
     #[clap(disable_version_flag = true)]
     Renew(RenewArgs),
+
+    /// Move network data to and from machines without internet access
+    ///
+    /// `bundle export` collects everything `check` and `suggest` need from the
+    /// network into a single archive, which `bundle import` uses to seed the
+    /// cache and store on an offline machine. Commands on the offline machine
+    /// should then be run with `--frozen`.
+    ///
+    /// See the subcommands for specifics.
+    #[clap(disable_version_flag = true)]
+    #[clap(subcommand)]
+    Bundle(BundleSubcommands),
 }
 
 #[derive(Subcommand)]
@@ -456,8 +606,35 @@ pub enum RegenerateSubcommands {
     Unpublished(RegenerateUnpublishedArgs),
 }
 
+#[derive(Subcommand)]
+pub enum BundleSubcommands {
+    /// Collect imported audits and crates.io data into a bundle
+    ///
+    /// The bundle contains the current state of all imports, along with the
+    /// crates.io publisher information and diffstats used by `check` and
+    /// `suggest`. This requires network access.
+    #[clap(disable_version_flag = true)]
+    Export(BundleExportArgs),
+
+    /// Seed the cache and store from a bundle
+    ///
+    /// Imports in the bundle are locked into `imports.lock` as `cargo vet`
+    /// would when run online, and the crates.io data and any crates are added
+    /// to the cache.
+    #[clap(disable_version_flag = true)]
+    Import(BundleImportArgs),
+}
+
 #[derive(clap::Args)]
-pub struct CheckArgs {}
+pub struct CheckArgs {
+    /// Only fail for problems which are new relative to a git revision
+    ///
+    /// The Cargo.lock and supply-chain store are loaded from the given
+    /// revision and vetted as well. Failures which were already present there
+    /// are reported separately as pre-existing, and don't fail the check.
+    #[clap(long, action, value_name = "REV")]
+    pub base: Option<String>,
+}
 
 #[derive(clap::Args)]
 pub struct InitArgs {}
@@ -735,6 +912,39 @@ pub struct AggregateArgs {
     /// Path to a file containing a list of URLs to aggregate the audits from.
     #[clap(action)]
     pub sources: PathBuf,
+
+    /// Sign the aggregated audits with this unencrypted OpenSSH private key.
+    ///
+    /// The detached signature is written to the path given by `--signature`,
+    /// and can be verified by imports which specify the matching `public-key`.
+    #[clap(long, action, requires = "signature")]
+    pub sign_with: Option<PathBuf>,
+
+    /// Where to write the detached signature when using `--sign-with`.
+    ///
+    /// This should be published next to the aggregated audits, with the same
+    /// name plus a `.sig` extension.
+    #[clap(long, action, requires = "sign_with")]
+    pub signature: Option<PathBuf>,
+}
+
+#[derive(clap::Args)]
+pub struct BundleExportArgs {
+    /// Where to write the bundle.
+    #[clap(action)]
+    pub path: PathBuf,
+
+    /// Also include the crates needed to review the diffs suggested by
+    /// `check` and `suggest`, so they can be inspected offline.
+    #[clap(long, action)]
+    pub include_crates: bool,
+}
+
+#[derive(clap::Args)]
+pub struct BundleImportArgs {
+    /// The bundle to import, as written by `bundle export`.
+    #[clap(action)]
+    pub path: PathBuf,
 }
 
 #[derive(clap::Args)]
@@ -757,7 +967,8 @@ pub struct GcArgs {
 #[derive(clap::Args)]
 pub struct RenewArgs {
     // Change this doc string if the WILDCARD_AUDIT_EXPIRATION_STRING changes.
-    /// Renew all wildcard audits which will have expired six weeks from now.
+    /// Renew all wildcard audits, audits, and exemptions which will have expired six weeks from
+    /// now.
     #[clap(long, action, conflicts_with("crate_name"))]
     pub expiring: bool,
 
@@ -802,6 +1013,66 @@ pub struct ExplainAuditArgs {
     pub criteria: CriteriaName,
 }
 
+#[derive(clap::Args)]
+pub struct ReportArgs {
+    /// The directory to write an HTML report to
+    #[clap(long, action, value_name = "DIR")]
+    pub html: PathBuf,
+}
+
+#[derive(clap::Args)]
+pub struct SbomArgs {
+    /// The SBOM format to print
+    #[clap(long, value_enum, action)]
+    #[clap(default_value_t = SbomFormat::Cyclonedx)]
+    pub format: SbomFormat,
+}
+
+#[derive(clap::Args)]
+pub struct StoreDiffArgs {
+    /// The git revision to compare from
+    #[clap(action)]
+    pub from: String,
+    /// The git revision to compare to, defaulting to the working tree
+    #[clap(action)]
+    pub to: Option<String>,
+}
+
+#[derive(clap::Args)]
+pub struct StatsArgs {}
+
+#[derive(clap::Args)]
+pub struct QueryArgs {
+    /// The package to search for
+    #[clap(action)]
+    pub package: PackageName,
+    /// The version of the package to search for
+    #[clap(action)]
+    pub version: Option<VetVersion>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum SbomFormat {
+    /// CycloneDX 1.5 JSON
+    Cyclonedx,
+    /// SPDX 2.3 JSON
+    Spdx,
+}
+
+#[derive(clap::Args)]
+pub struct WhyArgs {
+    /// The package to explain the requirements of
+    #[clap(action)]
+    pub package: PackageName,
+    /// The version of the package, if there are multiple in the graph
+    #[clap(action)]
+    pub version: Option<VetVersion>,
+    /// The criteria to explain the requirement for
+    #[clap(action)]
+    #[clap(default_value = "safe-to-deploy")]
+    pub criteria: CriteriaName,
+}
+
 /// Logging verbosity levels
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum Verbose {
@@ -827,6 +1098,12 @@ pub enum OutputFormat {
     Human,
     /// Print output in a machine-readable form with minimal extra context.
     Json,
+    /// Print `check` results as a SARIF log, for code scanning tools.
+    Sarif,
+    /// Print `check` results as a JUnit XML report, for CI dashboards.
+    Junit,
+    /// Print `check` and `suggest` results as Markdown, for pull request comments.
+    Markdown,
 }
 
 #[derive(Clone, Debug)]
@@ -123,7 +123,7 @@ impl CriteriaMapper {
     }
 
     /// Get the total number of criteria.
-    pub fn len(&self) -> usize {
+    pub(crate) fn len(&self) -> usize {
         self.names.len()
     }
 
@@ -207,13 +207,16 @@ impl CriteriaSet {
     pub fn set_criteria(&mut self, idx: usize) {
         self.0 |= 1 << idx;
     }
+    pub fn unset_criteria(&mut self, idx: usize) {
+        self.0 &= !(1 << idx);
+    }
     pub fn clear_criteria(&mut self, other: &CriteriaSet) {
         self.0 &= !other.0;
     }
     pub fn has_criteria(&self, idx: usize) -> bool {
         (self.0 & (1 << idx)) != 0
     }
-    pub fn _intersected_with(&mut self, other: &CriteriaSet) {
+    pub fn intersected_with(&mut self, other: &CriteriaSet) {
         self.0 &= other.0;
     }
     pub fn unioned_with(&mut self, other: &CriteriaSet) {
@@ -0,0 +1,192 @@
+//! Descriptions of the resolver's results which are shared between several
+//! commands and output formats.
+
+use crate::format::{AuditEntry, AuditKind, ImportName, PackageStr};
+use crate::resolver::{DeltaEdgeOrigin, ExpiredEntry, PackageNode, ViolationConflict};
+use crate::serialization::spanned::Spanned;
+use crate::storage::Store;
+use crate::string_format;
+
+/// Describe a violation conflict, returning where the violation came from, the
+/// violation itself, and a message explaining the conflict.
+pub fn describe_violation_conflict<'a>(
+    package: &PackageNode,
+    violation: &'a ViolationConflict,
+) -> (&'a Option<ImportName>, &'a AuditEntry, String) {
+    let (violation_source, entry, conflict) = match violation {
+        ViolationConflict::UnauditedConflict {
+            violation_source,
+            violation,
+            ..
+        } => (violation_source, violation, "an exemption".to_owned()),
+        ViolationConflict::AuditConflict {
+            violation_source,
+            violation,
+            audit_source,
+            ..
+        } => (
+            violation_source,
+            violation,
+            match audit_source {
+                Some(source) => format!("an audit imported from {source}"),
+                None => "an audit".to_owned(),
+            },
+        ),
+        ViolationConflict::AdvisoryConflict {
+            violation_source,
+            violation,
+        } => (violation_source, violation, "its use".to_owned()),
+    };
+    let from = match violation_source {
+        Some(source) => format!(" imported from {source}"),
+        None => String::new(),
+    };
+    let mut text = format!(
+        "{}:{} has a violation{from} which conflicts with {conflict}",
+        package.name, package.version
+    );
+    if let Some(notes) = &entry.notes {
+        text += &format!(": {notes}");
+    }
+    (violation_source, entry, text)
+}
+
+/// Describe an audit or exemption which was ignored because it expired.
+pub fn describe_expired_entry(expired: &ExpiredEntry) -> String {
+    let kind = if expired.is_exemption() {
+        "exemption"
+    } else {
+        "audit"
+    };
+    let version = match &expired.from {
+        Some(from) => format!("{from} -> {}", expired.to),
+        None => expired.to.to_string(),
+    };
+    let source = match &expired.source {
+        Some(source) => format!(" from {source}"),
+        None => String::new(),
+    };
+    format!(
+        "ignored expired {kind} for {version}{source} (expired {})",
+        expired.expires
+    )
+}
+
+/// Describe an edge of an audit path, as printed by `explain-audit`.
+pub fn describe_edge(store: &Store, package: PackageStr<'_>, edge: &DeltaEdgeOrigin) -> String {
+    fn format_who(who: &[Spanned<String>]) -> String {
+        if who.is_empty() {
+            "<unspecified>".to_owned()
+        } else {
+            string_format::FormatShortList::new(who.to_owned()).to_string()
+        }
+    }
+
+    fn format_freshness(is_fresh_import: bool) -> &'static str {
+        if is_fresh_import {
+            " (uncached)"
+        } else {
+            ""
+        }
+    }
+
+    use DeltaEdgeOrigin::*;
+    match *edge {
+        StoredLocalAudit { audit_index, .. } => {
+            let audit = &store.audits.audits[package][audit_index];
+            let who = format_who(&audit.who);
+            match &audit.kind {
+                AuditKind::Full { version } => {
+                    format!("[local] full audit for {version} by {who}")
+                }
+                AuditKind::Delta { from, to } => {
+                    format!("[local] delta audit for {from}->{to} by {who}")
+                }
+                _ => unreachable!(),
+            }
+        }
+        ImportedAudit {
+            import_index,
+            audit_index,
+        } => {
+            let (import_name, audits_file) =
+                store.imported_audits().iter().nth(import_index).unwrap();
+            let audit = &audits_file.audits[package][audit_index];
+            let freshness = format_freshness(audit.is_fresh_import);
+            let who = format_who(&audit.who);
+            match &audit.kind {
+                AuditKind::Full { version } => {
+                    format!("[{import_name}{freshness}] full audit for {version} by {who}")
+                }
+                AuditKind::Delta { from, to } => {
+                    format!("[{import_name}{freshness}] delta audit for {from}->{to} by {who}")
+                }
+                _ => unreachable!(),
+            }
+        }
+        WildcardAudit {
+            import_index,
+            audit_index,
+            publisher_index,
+        } => {
+            let (import_name, audits_file) = match import_index {
+                Some(import_index) => {
+                    let (import_name, audits_file) =
+                        store.imported_audits().iter().nth(import_index).unwrap();
+                    (&import_name[..], audits_file)
+                }
+                None => ("local", &store.audits),
+            };
+
+            let audit = &audits_file.wildcard_audits[package][audit_index];
+            let who = format_who(&audit.who);
+
+            let publisher = &store.publishers()[package][publisher_index];
+            let version = &publisher.version;
+            let identifier = publisher.source.as_identifier();
+            let freshness = format_freshness(audit.is_fresh_import || publisher.is_fresh_import);
+
+            format!("[{import_name}{freshness}] wildcard audit for {version} (published by: {identifier}) by {who}")
+        }
+        Trusted { publisher_index } => {
+            let publisher = &store.publishers()[package][publisher_index];
+            let version = &publisher.version;
+            let identifier = publisher.source.as_identifier();
+            let freshness = format_freshness(publisher.is_fresh_import);
+
+            format!("[local{freshness}] trusted entry for {version} (published by: {identifier})")
+        }
+        Exemption { exemption_index } => {
+            let exemption = &store.config.exemptions[package][exemption_index];
+            let version = &exemption.version;
+            format!("[local] exemption for {version}")
+        }
+        Unpublished { unpublished_index } => {
+            let unpublished = &store.unpublished()[package][unpublished_index];
+            let version = &unpublished.version;
+            let audited_as = &unpublished.audited_as;
+            let freshness = format_freshness(unpublished.is_fresh_import);
+
+            format!("[local{freshness}] auditing unpublished version {version} as {audited_as}")
+        }
+        FreshExemption { .. } => {
+            unreachable!("Should not observe FreshExemption edge with PreferExemptions mode")
+        }
+    }
+}
+
+/// Escape a string for use in XML text or attribute values.
+pub fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
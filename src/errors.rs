@@ -18,10 +18,12 @@ use thiserror::Error;
 
 use crate::{
     format::{
-        CriteriaName, ForeignCriteriaName, ImportName, PackageName, StoreVersion, VetVersion,
+        CriteriaName, ForeignCriteriaName, ImportName, PackageName, RegistryName, StoreVersion,
+        VetVersion,
     },
     network::PayloadEncoding,
     serialization::spanned::Spanned,
+    storage::ImportChanges,
 };
 
 #[derive(Eq, PartialEq)]
@@ -227,6 +229,31 @@ pub struct VersionedPackageError {
     pub version: VetVersion,
 }
 
+///////////////////////////////////////////////////////////
+// AuditChecksumErrors
+///////////////////////////////////////////////////////////
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+#[error("Some audited crates don't match the checksums in Cargo.lock")]
+#[diagnostic(help("The registry may be serving a different crate than the one which was audited"))]
+pub struct AuditChecksumErrors {
+    #[related]
+    pub errors: Vec<AuditChecksumError>,
+}
+
+#[derive(Debug, Error, Diagnostic, PartialEq, Eq)]
+#[error(
+    "{package}:{version} was audited{} with checksum {audited}, but Cargo.lock has {locked}",
+    import.as_ref().map(|import| format!(" by {import}")).unwrap_or_default()
+)]
+pub struct AuditChecksumError {
+    pub package: PackageName,
+    pub version: VetVersion,
+    pub import: Option<ImportName>,
+    pub audited: String,
+    pub locked: String,
+}
+
 ///////////////////////////////////////////////////////////
 // CratePolicyErrors
 ///////////////////////////////////////////////////////////
@@ -429,6 +456,32 @@ pub enum StoreAcquireError {
     #[diagnostic(transparent)]
     #[error(transparent)]
     CacheAcquire(#[from] Box<CacheAcquireError>),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    AdvisoryDb(#[from] AdvisoryDbError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ImportsChanged(#[from] Box<ImportsChangedError>),
+}
+
+#[derive(Debug, Error, Diagnostic)]
+#[non_exhaustive]
+pub enum AdvisoryDbError {
+    #[error("Couldn't read the advisory database at {}", path.display())]
+    #[help("Check the `path` of the [advisory-db] table in config.toml")]
+    Read {
+        path: PathBuf,
+        #[source]
+        error: std::io::Error,
+    },
+    #[error("Advisory {} has no TOML front matter", path.display())]
+    MissingFrontMatter { path: PathBuf },
+    #[error("Couldn't parse advisory {}", path.display())]
+    Parse {
+        path: PathBuf,
+        #[source]
+        error: toml::de::Error,
+    },
 }
 
 #[derive(Debug, Error, Diagnostic)]
@@ -489,6 +542,12 @@ pub enum StoreValidateError {
     #[error("imports.lock is out-of-date with respect to configuration")]
     #[diagnostic(help("run `cargo vet` without --locked to update imports"))]
     ImportsLockOutdated,
+    #[error("audit of '{package}' is for unknown registry '{registry}'")]
+    #[diagnostic(help("declare the registry in the [registries] table of config.toml"))]
+    UnknownRegistry {
+        package: PackageName,
+        registry: RegistryName,
+    },
 }
 
 #[derive(Debug, Error, Diagnostic)]
@@ -603,6 +662,19 @@ pub enum CommandError {
     BadOutput(#[source] FromUtf8Error),
 }
 
+//////////////////////////////////////////////////////////
+// GitCommandError
+//////////////////////////////////////////////////////////
+
+#[derive(Debug, Error, Diagnostic)]
+#[non_exhaustive]
+pub enum GitCommandError {
+    #[error(transparent)]
+    Command(#[from] CommandError),
+    #[error("`git {command}` failed:\n{stderr}")]
+    Git { command: String, stderr: String },
+}
+
 //////////////////////////////////////////////////////////
 // FetchAndDiffError
 //////////////////////////////////////////////////////////
@@ -695,6 +767,9 @@ pub enum FetchError {
     #[error(transparent)]
     #[diagnostic(transparent)]
     Download(#[from] DownloadError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Vendor(#[from] VendorError),
 }
 
 #[derive(Debug, Error, Diagnostic)]
@@ -778,8 +853,62 @@ pub enum FetchAuditError {
         #[source]
         error: url::ParseError,
     },
+    #[error("invalid git source for foreign import {import_name} @ {import_url}: {reason}")]
+    #[diagnostic(help(
+        "git imports are written as `git+<repository>?rev=<commit>`, optionally with `&path=<path to audits.toml>`"
+    ))]
+    InvalidGitSource {
+        import_name: ImportName,
+        import_url: String,
+        reason: &'static str,
+    },
+    #[error("failed to read foreign import {import_name} from {}", path.display())]
+    ReadLocal {
+        import_name: ImportName,
+        path: PathBuf,
+        #[source]
+        error: std::io::Error,
+    },
+    #[error("failed to fetch foreign import {import_name} @ {import_url}")]
+    Git {
+        import_name: ImportName,
+        import_url: String,
+        #[source]
+        error: GitImportError,
+    },
+    #[error("cannot fetch foreign import {import_name} @ {import_url} while frozen")]
+    Frozen {
+        import_name: ImportName,
+        import_url: String,
+    },
+    #[error("invalid public-key for foreign import {import_name}")]
+    InvalidPublicKey {
+        import_name: ImportName,
+        #[source]
+        error: SignatureError,
+    },
+    #[error("failed to fetch the signature for foreign import {import_name} @ {signature_url}")]
+    #[diagnostic(help("{import_name} has a public-key, so its audits must be signed"))]
+    MissingSignature {
+        import_name: ImportName,
+        signature_url: String,
+        #[source]
+        error: Box<FetchAuditError>,
+    },
+    #[error("failed to verify the signature for foreign import {import_name} @ {import_url}")]
+    #[diagnostic(help(
+        "the audits may have been tampered with, contact the maintainers of {import_name} before changing its public-key"
+    ))]
+    BadSignature {
+        import_name: ImportName,
+        import_url: String,
+        #[source]
+        error: SignatureError,
+    },
     #[error("error when aggregating multiple sources for {import_name}")]
-    #[diagnostic(help("all sources for mapped custom criteria must have identical descriptions"))]
+    #[diagnostic(help(
+        "all sources for mapped custom criteria must have identical descriptions and implied criteria"
+    ))]
     Aggregate {
         import_name: ImportName,
         #[related]
@@ -797,13 +926,286 @@ pub enum FetchAuditError {
 }
 
 #[derive(Debug, Error, Diagnostic)]
-#[error("criteria description mismatch for {criteria_name}\n{first}\n{second}")]
-#[diagnostic(help("{criteria_name} is mapped to the local criteria {mapped_to:?}"))]
-pub struct FetchAuditAggregateError {
-    pub criteria_name: CriteriaName,
-    pub mapped_to: Vec<Spanned<CriteriaName>>,
-    pub first: AggregateCriteriaDescription,
-    pub second: AggregateCriteriaDescription,
+pub enum FetchAuditAggregateError {
+    #[error("criteria description mismatch for {criteria_name}\n{first}\n{second}")]
+    #[diagnostic(help("{criteria_name} is mapped to the local criteria {mapped_to:?}"))]
+    Description {
+        criteria_name: CriteriaName,
+        mapped_to: Vec<Spanned<CriteriaName>>,
+        first: AggregateCriteriaDescription,
+        second: AggregateCriteriaDescription,
+    },
+    #[error("implied criteria mismatch for {criteria_name}\n{first}\n{second}")]
+    #[diagnostic(help("{criteria_name} is mapped to the local criteria {mapped_to:?}"))]
+    Implies {
+        criteria_name: CriteriaName,
+        mapped_to: Vec<Spanned<CriteriaName>>,
+        first: AggregateCriteriaImplies,
+        second: AggregateCriteriaImplies,
+    },
+}
+
+//////////////////////////////////////////////////////////
+// GitImportError
+//////////////////////////////////////////////////////////
+
+#[derive(Debug, Error, Diagnostic)]
+#[non_exhaustive]
+pub enum GitImportError {
+    #[error("commit {rev} hasn't been fetched yet, and can't be fetched while frozen")]
+    NotCached { rev: String },
+    #[error("git imports require a cache directory")]
+    NoCache,
+    #[error("failed to create {}", target.display())]
+    CreateDir {
+        target: PathBuf,
+        #[source]
+        error: std::io::Error,
+    },
+    #[error(transparent)]
+    Command(#[from] CommandError),
+    #[error(transparent)]
+    Git(#[from] GitCommandError),
+}
+
+//////////////////////////////////////////////////////////
+// BundleError
+//////////////////////////////////////////////////////////
+
+#[derive(Debug, Error, Diagnostic)]
+#[non_exhaustive]
+pub enum BundleError {
+    #[error("failed to write bundle to {}", path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        error: std::io::Error,
+    },
+    #[error("failed to read bundle from {}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        error: std::io::Error,
+    },
+    #[error("unexpected entry {} in bundle", entry_path.display())]
+    #[diagnostic(help("the bundle may have been created by a different version of cargo-vet"))]
+    UnexpectedEntry { entry_path: PathBuf },
+    #[error("bundle is missing {name}")]
+    MissingEntry { name: &'static str },
+    #[error("{file_name} in bundle has checksum {found}, but Cargo.lock expects {expected}")]
+    CrateChecksum {
+        file_name: String,
+        expected: String,
+        found: String,
+    },
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    LoadToml(#[from] LoadTomlError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    LoadJson(#[from] LoadJsonError),
+    #[error(transparent)]
+    StoreToml(#[from] StoreTomlError),
+    #[error(transparent)]
+    StoreJson(#[from] StoreJsonError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Fetch(#[from] FetchError),
+}
+
+//////////////////////////////////////////////////////////
+// VendorErrors
+//////////////////////////////////////////////////////////
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("vendored sources don't match the crates they were vendored from")]
+#[diagnostic(help("vendored crates must not be modified, run `cargo vendor` to restore them"))]
+pub struct VendorErrors {
+    #[related]
+    pub errors: Vec<VendorError>,
+}
+
+#[derive(Debug, Error, Diagnostic)]
+#[non_exhaustive]
+pub enum VendorError {
+    #[error("failed to read vendored sources for {package}:{version} at {}", path.display())]
+    Read {
+        package: PackageName,
+        version: semver::Version,
+        path: PathBuf,
+        #[source]
+        error: std::io::Error,
+    },
+    #[error("invalid .cargo-checksum.json for vendored {package}:{version}")]
+    InvalidChecksums {
+        package: PackageName,
+        version: semver::Version,
+        #[source]
+        error: serde_json::Error,
+    },
+    #[error(
+        "vendored {package}:{version} has checksum {found}, but Cargo.lock expects {expected}"
+    )]
+    PackageChecksum {
+        package: PackageName,
+        version: semver::Version,
+        expected: String,
+        found: String,
+    },
+    #[error(
+        "vendored {package}:{version} doesn't record the checksum of the .crate file it was vendored from, but Cargo.lock expects {expected}"
+    )]
+    MissingPackageChecksum {
+        package: PackageName,
+        version: semver::Version,
+        expected: String,
+    },
+    #[error(
+        "the .crate file for {package}:{version} has checksum {found}, but Cargo.lock expects {expected}"
+    )]
+    CrateChecksum {
+        package: PackageName,
+        version: semver::Version,
+        expected: String,
+        found: String,
+    },
+    #[error("failed to fetch the .crate file for {package}:{version}")]
+    FetchCrate {
+        package: PackageName,
+        version: semver::Version,
+        #[source]
+        error: Box<FetchError>,
+    },
+    #[error("failed to read the .crate file for {package}:{version}")]
+    InvalidCrate {
+        package: PackageName,
+        version: semver::Version,
+        #[source]
+        error: std::io::Error,
+    },
+    #[error("{path} has been modified in vendored {package}:{version}")]
+    ModifiedFile {
+        package: PackageName,
+        version: semver::Version,
+        path: String,
+    },
+    #[error("{path} is missing from vendored {package}:{version}")]
+    MissingFile {
+        package: PackageName,
+        version: semver::Version,
+        path: String,
+    },
+    #[error("{path} has been added to vendored {package}:{version}")]
+    AddedFile {
+        package: PackageName,
+        version: semver::Version,
+        path: String,
+    },
+    #[error("{package}:{version} is missing from the vendored sources at {}", dir.display())]
+    MissingPackage {
+        package: PackageName,
+        version: semver::Version,
+        dir: PathBuf,
+    },
+}
+
+//////////////////////////////////////////////////////////
+// ImportsChangedError
+//////////////////////////////////////////////////////////
+
+#[derive(Debug, Error, Diagnostic)]
+#[non_exhaustive]
+pub enum ImportsChangedError {
+    #[error("imported audits have changed upstream since imports.lock was generated")]
+    #[diagnostic(help("run `cargo vet regenerate imports` to review and accept the changes"))]
+    Changed {
+        #[related]
+        changed: Vec<ImportChangedError>,
+    },
+    #[diagnostic(transparent)]
+    #[error(transparent)]
+    Fetch(FetchAuditError),
+}
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("{} has changed\n{changes}", urls.join(", "))]
+pub struct ImportChangedError {
+    pub urls: Vec<String>,
+    pub changes: ImportChanges,
+}
+
+//////////////////////////////////////////////////////////
+// SignatureError
+//////////////////////////////////////////////////////////
+
+#[derive(Debug, Error, Diagnostic)]
+#[non_exhaustive]
+pub enum SignatureError {
+    #[error("invalid SSH key or signature")]
+    Ssh(#[source] ssh_key::Error),
+    #[error("invalid minisign key or signature")]
+    Minisign(#[source] minisign_verify::Error),
+    #[error("the signature was made with a different key")]
+    WrongKey,
+    #[error("the signature does not match the signed data")]
+    Mismatch,
+    #[error("failed to read signing key {}", path.display())]
+    ReadKey {
+        path: PathBuf,
+        #[source]
+        error: ssh_key::Error,
+    },
+    #[error("the signing key {} is encrypted", path.display())]
+    #[diagnostic(help(
+        "sign the output yourself with `ssh-keygen -Y sign -n cargo-vet -f <key> <file>`"
+    ))]
+    EncryptedKey { path: PathBuf },
+}
+
+//////////////////////////////////////////////////////////
+// HtmlReportError
+//////////////////////////////////////////////////////////
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to write report to {}", path.display())]
+pub struct HtmlReportError {
+    pub path: PathBuf,
+    #[source]
+    pub error: std::io::Error,
+}
+
+//////////////////////////////////////////////////////////
+// GitRevisionError
+//////////////////////////////////////////////////////////
+
+#[derive(Debug, Error, Diagnostic)]
+#[non_exhaustive]
+pub enum GitRevisionError {
+    #[error(transparent)]
+    Command(#[from] CommandError),
+    #[error(transparent)]
+    Git(#[from] GitCommandError),
+    #[error("{} is not inside the git repository at {}", path.display(), repo.display())]
+    OutsideRepository { path: PathBuf, repo: PathBuf },
+    #[error("failed to unpack revision {rev}")]
+    Unpack {
+        rev: String,
+        #[source]
+        error: std::io::Error,
+    },
+    #[error("failed to run `cargo metadata` on revision {rev}")]
+    Metadata {
+        rev: String,
+        #[source]
+        #[diagnostic_source]
+        error: MetadataAcquireError,
+    },
+    #[error("failed to load the supply-chain store from revision {rev}")]
+    Store {
+        rev: String,
+        #[source]
+        error: Box<StoreAcquireError>,
+    },
 }
 
 //////////////////////////////////////////////////////////
@@ -821,6 +1223,16 @@ pub enum CrateInfoError {
     Json(#[from] LoadJsonError),
     #[error("Cannot fetch crate information, '{name}' does not exist.")]
     DoesNotExist { name: PackageName },
+    #[error("Cannot fetch crate information from the '{registry}' registry")]
+    #[diagnostic(help("only sparse registry indexes (with a 'sparse+' prefix) can be queried"))]
+    UnsupportedIndex { registry: RegistryName },
+    #[error("Invalid index URL for the '{registry}' registry: {url}")]
+    InvalidIndexUrl {
+        registry: RegistryName,
+        url: String,
+        #[source]
+        error: url::ParseError,
+    },
 }
 
 //////////////////////////////////////////////////////////
@@ -887,6 +1299,19 @@ pub enum DownloadError {
         #[source]
         error: std::io::Error,
     },
+    #[error("invalid registry configuration at {url}")]
+    InvalidRegistryConfig {
+        url: Box<reqwest::Url>,
+        #[source]
+        error: serde_json::Error,
+    },
+    #[error("invalid api URL `{api}` in registry configuration at {url}")]
+    InvalidRegistryApi {
+        url: Box<reqwest::Url>,
+        api: String,
+        #[source]
+        error: url::ParseError,
+    },
 }
 
 //////////////////////////////////////////////////////////
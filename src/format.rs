@@ -27,6 +27,9 @@ pub type PackageName = String;
 pub type PackageStr<'a> = &'a str;
 pub type ImportName = String;
 pub type ImportStr<'a> = &'a str;
+pub type RegistryName = String;
+pub type RegistryStr<'a> = &'a str;
+pub type AdvisoryId = String;
 pub type CratesUserId = u64;
 pub type CratesTrustpubSignature = String;
 
@@ -302,6 +305,14 @@ pub struct AuditEntry {
     pub who: Vec<Spanned<String>>,
     pub criteria: Vec<Spanned<CriteriaName>>,
     pub kind: AuditKind,
+    /// The date on which this audit stops being considered valid, if any.
+    pub expires: Option<chrono::NaiveDate>,
+    /// The alternative registry which the audited crate is published to, or
+    /// `None` for crates.io.
+    pub registry: Option<RegistryName>,
+    /// The sha256 checksum of the audited `.crate` file (the `version`, or the
+    /// `to` version of a delta), in the same format as `Cargo.lock`.
+    pub checksum: Option<String>,
     pub importable: bool,
     pub notes: Option<String>,
     /// Chain of sources this audit was aggregated from, most recent last.
@@ -320,9 +331,20 @@ impl AuditEntry {
     /// Should `self` be considered to be the same audit as `other`, e.g. for
     /// the purposes of `is_fresh_import` checks?
     pub fn same_audit_as(&self, other: &AuditEntry) -> bool {
-        // Ignore `who` and `notes` for comparison, as they are not relevant
-        // semantically and might have been updated uneventfully.
-        self.kind == other.kind && self.criteria == other.criteria
+        // Ignore `notes` for comparison, as it isn't relevant semantically and
+        // might have been updated uneventfully. `who` is compared, as it's
+        // counted towards `min-auditors`.
+        self.kind == other.kind
+            && self.criteria == other.criteria
+            && self.who == other.who
+            && self.expires == other.expires
+            && self.registry == other.registry
+            && self.checksum == other.checksum
+    }
+
+    /// Whether this audit has expired as of `today`, and should be ignored.
+    pub fn is_expired(&self, today: chrono::NaiveDate) -> bool {
+        self.expires.is_some_and(|expires| expires <= today)
     }
 
     /// Try to collapse this (delta) entry with the given entry, which must be just prior to it
@@ -354,6 +376,16 @@ impl AuditEntry {
             return None;
         }
 
+        // Audits which expire at different times can't be merged.
+        if other.expires != self.expires {
+            return None;
+        }
+
+        // Audits of crates from different registries are for different crates.
+        if other.registry != self.registry {
+            return None;
+        }
+
         // Consume the existing audit's `from`, `who`, and `notes`.
         let mut new_entry = self.clone();
         new_entry.kind = match other_from {
@@ -537,9 +569,11 @@ impl WildcardEntry {
     /// Should `self` be considered to be the same audit as `other`, e.g. for
     /// the purposes of `is_fresh_import` checks?
     pub fn same_audit_as(&self, other: &WildcardEntry) -> bool {
-        // Ignore `who` and `notes` for comparison, as they are not relevant
-        // semantically and might have been updated uneventfully.
-        self.source == other.source
+        // Ignore `notes` for comparison, as it isn't relevant semantically and
+        // might have been updated uneventfully. `who` is compared, as it's
+        // counted towards `min-auditors`.
+        self.who == other.who
+            && self.source == other.source
             && self.start == other.start
             && self.end == other.end
             && self.criteria == other.criteria
@@ -598,11 +632,30 @@ pub struct ConfigFile {
     #[serde(skip_serializing_if = "is_default_criteria")]
     pub default_criteria: CriteriaName,
 
+    /// Target triples which the dependency graph should be filtered to. When
+    /// empty, dependencies for all platforms are considered.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub targets: Vec<String>,
+
     /// Remote audits.toml's that we trust and want to import.
     #[serde(skip_serializing_if = "SortedMap::is_empty")]
     #[serde(default)]
     pub imports: SortedMap<ImportName, RemoteImport>,
 
+    /// Alternative registries which third-party crates may be published to, in
+    /// addition to crates.io.
+    #[serde(skip_serializing_if = "SortedMap::is_empty")]
+    #[serde(default)]
+    pub registries: SortedMap<RegistryName, CargoRegistryEntry>,
+
+    /// A local clone of the RustSec advisory database, whose advisories are
+    /// treated as violations against affected crates.
+    #[serde(rename = "advisory-db")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub advisory_db: Option<AdvisoryDbConfig>,
+
     /// A table of policies for crates.
     #[serde(skip_serializing_if = "Policy::is_empty")]
     #[serde(default)]
@@ -617,6 +670,30 @@ pub struct ConfigFile {
     pub exemptions: SortedMap<PackageName, Vec<ExemptedDependency>>,
 }
 
+impl ConfigFile {
+    /// Look up an alternative registry by name.
+    pub fn cargo_registry(&self, name: RegistryStr<'_>) -> Option<CargoRegistry<'_>> {
+        self.registries
+            .get_key_value(name)
+            .map(|(name, entry)| CargoRegistry { name, entry })
+    }
+}
+
+impl Default for ConfigFile {
+    fn default() -> Self {
+        ConfigFile {
+            cargo_vet: Default::default(),
+            default_criteria: get_default_criteria(),
+            targets: Vec::new(),
+            imports: SortedMap::new(),
+            registries: SortedMap::new(),
+            advisory_db: None,
+            policy: Default::default(),
+            exemptions: SortedMap::new(),
+        }
+    }
+}
+
 impl Tidyable for ConfigFile {
     fn tidy(&mut self) {
         self.exemptions.tidy();
@@ -824,7 +901,7 @@ impl Default for PackagePolicyEntry {
 /// If this sounds overwhelming, don't worry, everything defaults to "nothing special"
 /// and an empty PolicyTable basically just means "everything should satisfy the
 /// default criteria in audits.toml".
-#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default, PartialEq)]
 pub struct PolicyEntry {
     /// Whether this nominally-first-party crate should actually be subject to audits
     /// as-if it was third-party, based on matches to crates.io packages with the same
@@ -863,6 +940,17 @@ pub struct PolicyEntry {
     #[serde(with = "serialization::string_or_vec_or_none")]
     pub dev_criteria: Option<Vec<Spanned<CriteriaName>>>,
 
+    /// Same as `criteria`, but for crates that are only used as build-dependencies
+    /// or proc-macros, and therefore only run at build time.
+    ///
+    /// Unlike `criteria`, this is inherited by the transitive dependencies of the
+    /// crate, so that build-time dependencies of third-party crates are also
+    /// covered. If not present, build-time dependencies use `criteria`.
+    #[serde(rename = "build-criteria")]
+    #[serde(default)]
+    #[serde(with = "serialization::string_or_vec_or_none")]
+    pub build_criteria: Option<Vec<Spanned<CriteriaName>>>,
+
     /// Custom criteria for a specific crate's dependencies.
     ///
     /// Any dependency edge that isn't explicitly specified defaults to `criteria`.
@@ -872,6 +960,16 @@ pub struct PolicyEntry {
     #[serde(default)]
     pub dependency_criteria: CriteriaMap,
 
+    /// The minimum number of independent auditors which must have reviewed
+    /// each audit used to certify this crate. Auditors are identified by the
+    /// `who` field of local audits, and by the import name of imported audits.
+    /// Each audit entry counts as at most one auditor, even if co-signed by
+    /// several people. Trusted entries and exemptions aren't subject to it.
+    ///
+    /// If not present, a single audit is sufficient.
+    #[serde(rename = "min-auditors")]
+    pub min_auditors: Option<usize>,
+
     /// Freeform notes
     pub notes: Option<String>,
 }
@@ -896,11 +994,17 @@ pub static DEFAULT_POLICY_CRITERIA: CriteriaStr = SAFE_TO_DEPLOY;
 pub static DEFAULT_POLICY_DEV_CRITERIA: CriteriaStr = SAFE_TO_RUN;
 
 /// A remote audits.toml that we trust the contents of (by virtue of trusting the maintainer).
-#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default, PartialEq)]
 pub struct RemoteImport {
     /// URL(s) of the foreign audits.toml
     #[serde(with = "serialization::string_or_vec")]
     pub url: Vec<String>,
+    /// A public key which the foreign audits must be signed with. Either an
+    /// OpenSSH public key, or a minisign public key.
+    #[serde(rename = "public-key")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub public_key: Option<String>,
     /// A list of crates for which no audits or violations should be imported.
     #[serde(skip_serializing_if = "Vec::is_empty")]
     #[serde(default)]
@@ -913,6 +1017,63 @@ pub struct RemoteImport {
     pub criteria_map: CriteriaMap,
 }
 
+/// An alternative Cargo registry, such as a private or sparse registry, whose
+/// crates are treated as third-party.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+pub struct CargoRegistryEntry {
+    /// The source of the registry's index, as it appears in `Cargo.lock`
+    /// (e.g. `sparse+https://cargo.example.com/index/`).
+    ///
+    /// Published versions can only be looked up for `sparse+` indexes.
+    pub index: String,
+    /// The URL template used to download crates from the registry, in the same
+    /// format as the `dl` key of the registry's `config.json`. The markers
+    /// `{crate}`, `{version}`, `{prefix}` and `{lowerprefix}` are replaced. If
+    /// no markers are present, `/{crate}/{version}/download` is appended.
+    pub download: String,
+}
+
+/// The location of a local clone of the RustSec advisory database.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub struct AdvisoryDbConfig {
+    /// Path to the root of the advisory database, relative to the store.
+    pub path: String,
+    /// Advisory IDs which should not be treated as violations.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub ignore: Vec<AdvisoryId>,
+}
+
+/// A security advisory from the RustSec advisory database.
+#[derive(Debug, Clone)]
+pub struct Advisory {
+    pub id: AdvisoryId,
+    pub package: PackageName,
+    pub title: String,
+    /// Versions in which the vulnerability has been fixed.
+    pub patched: Vec<VersionReq>,
+    /// Versions which were never affected by the vulnerability.
+    pub unaffected: Vec<VersionReq>,
+}
+
+impl Advisory {
+    /// Whether the given version is affected by this advisory.
+    pub fn affects(&self, version: &VetVersion) -> bool {
+        !self
+            .patched
+            .iter()
+            .chain(&self.unaffected)
+            .any(|req| req.matches(version))
+    }
+}
+
+/// An alternative registry along with its name in the `registries` table.
+#[derive(Debug, Clone, Copy)]
+pub struct CargoRegistry<'a> {
+    pub name: RegistryStr<'a>,
+    pub entry: &'a CargoRegistryEntry,
+}
+
 /// Translations of foreign criteria to local criteria.
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
 pub struct CriteriaMapping {
@@ -938,10 +1099,21 @@ pub struct ExemptedDependency {
     #[serde(default = "get_default_exemptions_suggest")]
     #[serde(skip_serializing_if = "is_default_exemptions_suggest")]
     pub suggest: bool,
+    /// The date on which this exemption stops being considered valid, if any.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires: Option<chrono::NaiveDate>,
     /// Freeform notes, put whatever you want here. Just more stable/reliable than comments.
     pub notes: Option<String>,
 }
 
+impl ExemptedDependency {
+    /// Whether this exemption has expired as of `today`, and should be ignored.
+    pub fn is_expired(&self, today: chrono::NaiveDate) -> bool {
+        self.expires.is_some_and(|expires| expires <= today)
+    }
+}
+
 static DEFAULT_EXEMPTIONS_SUGGEST: bool = true;
 pub fn get_default_exemptions_suggest() -> bool {
     DEFAULT_EXEMPTIONS_SUGGEST
@@ -1071,11 +1243,22 @@ pub struct ImportsFile {
     #[serde(default)]
     #[serde(skip_serializing_if = "SortedMap::is_empty")]
     pub publisher: SortedMap<PackageName, Vec<CratesPublisher>>,
+    /// The hash of each imported audits file, indexed by import name and URL,
+    /// as of when it was last fetched.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "SortedMap::is_empty")]
+    pub hashes: SortedMap<ImportName, ImportHashes>,
     #[serde(default)]
     #[serde(skip_serializing_if = "SortedMap::is_empty")]
     pub audits: SortedMap<ImportName, AuditsFile>,
 }
 
+/// A content hash of an imported audits file, of the form `sha256:<hex>`.
+pub type ImportHash = String;
+
+/// The hashes of the audits files fetched for an import, indexed by URL.
+pub type ImportHashes = SortedMap<String, ImportHash>;
+
 impl Tidyable for ImportsFile {
     fn tidy(&mut self) {
         self.unpublished.tidy();
@@ -1442,6 +1625,11 @@ pub struct RegistryEntry {
 pub struct JsonReport {
     #[serde(flatten)]
     pub conclusion: JsonReportConclusion,
+    /// The target triples the dependency graph was filtered to. If empty,
+    /// dependencies for all platforms were considered.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<String>,
 }
 
 /// The conclusion of running `check` or `suggest`
@@ -1468,6 +1656,10 @@ pub struct JsonReportSuccess {
     pub vetted_partially: Vec<JsonPackage>,
     /// These packages are exempted
     pub vetted_with_exemptions: Vec<JsonPackage>,
+    /// These packages failed, but were already failing in the `--base` revision
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub preexisting_failures: Vec<JsonVetFailure>,
 }
 
 /// Failure! The violations and audits/exemptions are contradictory!
@@ -1483,6 +1675,10 @@ pub struct JsonReportFailForViolationConflict {
 pub struct JsonReportFailForVet {
     /// Here are the problems we found
     pub failures: Vec<JsonVetFailure>,
+    /// These problems were already present in the `--base` revision
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub preexisting_failures: Vec<JsonVetFailure>,
     /// And here are the fixes we recommend
     pub suggest: Option<JsonSuggest>,
 }
@@ -1508,6 +1704,41 @@ pub struct JsonVetFailure {
     pub version: VetVersion,
     /// The missing criteria
     pub missing_criteria: Vec<CriteriaName>,
+    /// Expired audits and exemptions which were ignored, but could otherwise
+    /// have provided some of the missing criteria
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub expired: Vec<JsonExpiredEntry>,
+    /// If the missing criteria would have been satisfied if not for the
+    /// `min-auditors` policy, the number of additional independent auditors needed
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub missing_auditors: Option<usize>,
+}
+
+/// An audit or exemption which was ignored because it has expired
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonExpiredEntry {
+    /// Whether this was an audit or an exemption
+    pub kind: JsonExpiredKind,
+    /// The import the audit came from, if it wasn't a local entry
+    pub source: Option<ImportName>,
+    /// The version the audit was a delta from, if it was a delta audit
+    pub from: Option<VetVersion>,
+    /// The version the audit or exemption was for
+    pub to: VetVersion,
+    /// The criteria the entry would have provided
+    pub criteria: Vec<CriteriaName>,
+    /// The date the entry expired on
+    pub expires: chrono::NaiveDate,
+}
+
+/// The kind of entry which has expired
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum JsonExpiredKind {
+    Audit,
+    Exemption,
 }
 
 /// We recommend auditing the following package
@@ -1537,6 +1768,504 @@ pub struct JsonPackage {
     pub version: VetVersion,
 }
 
+/// cargo-vet's `--output-format=json` for `why`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonWhy {
+    /// The name of the package
+    pub name: PackageName,
+    /// The version of the package
+    pub version: VetVersion,
+    /// The criteria which was asked about
+    pub criteria: CriteriaName,
+    /// All of the criteria the package is required to satisfy
+    pub requirements: Vec<CriteriaName>,
+    /// Why the package requires the criteria, or `None` if it doesn't
+    pub reason: Option<JsonWhyReason>,
+}
+
+/// How a requirement was introduced and propagated to a package
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonWhyReason {
+    /// The policy entry (or default) which introduced the requirement
+    pub origin: RequirementOrigin,
+    /// The dependency path along which the requirement was inherited, starting
+    /// with the package which introduced it and ending with the package itself
+    pub path: Vec<JsonWhyStep>,
+}
+
+/// A package in the dependency path of a `why` explanation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonWhyStep {
+    /// Name of the package
+    pub name: PackageName,
+    /// Version of the package
+    pub version: VetVersion,
+    /// How the previous package in the path depends on this one
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependency: Option<DependencyEdge>,
+}
+
+/// Where the requirement for a package to satisfy some criteria came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RequirementOrigin {
+    /// The first package in the path is a top-level crate, and requires
+    /// `safe-to-deploy` by default
+    Root,
+    /// The first package in the path has a `criteria` policy
+    Criteria,
+    /// The first package in the path has a `build-criteria` policy
+    BuildCriteria,
+    /// The first package in the path has a `dev-criteria` policy which applies
+    /// to its dev-dependency
+    DevCriteria,
+    /// The first package in the path requires `safe-to-run` of its
+    /// dev-dependencies by default
+    DefaultDevCriteria,
+    /// The first package in the path has a `dependency-criteria` policy for
+    /// the second package
+    DependencyCriteria,
+}
+
+/// The kind of dependency between two packages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DependencyEdge {
+    Normal,
+    Build,
+    Dev,
+}
+
+////////////////////////////////////////////////////////////////////////////////////
+//                                                                                //
+//                                                                                //
+//                                                                                //
+//                             <sarif report output>                              //
+//                                                                                //
+//                                                                                //
+//                                                                                //
+////////////////////////////////////////////////////////////////////////////////////
+
+/// cargo-vet's `--output-format=sarif` for `check`, a subset of the SARIF 2.1.0
+/// format used by code scanning tools.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub version: String,
+    pub runs: Vec<SarifRun>,
+}
+
+/// A single run of cargo-vet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    /// The locations which relative artifact URIs are relative to
+    pub original_uri_base_ids: SortedMap<String, SarifArtifactLocation>,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifTool {
+    pub driver: SarifDriver,
+}
+
+/// Information about cargo-vet and the kinds of results it produces
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifDriver {
+    pub name: String,
+    pub version: String,
+    pub information_uri: String,
+    pub rules: Vec<SarifRule>,
+}
+
+/// A kind of result which cargo-vet produces
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRule {
+    pub id: String,
+    pub short_description: SarifMessage,
+    pub help_uri: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifMessage {
+    pub text: String,
+}
+
+/// A single finding, such as an unvetted dependency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    pub level: SarifLevel,
+    pub message: SarifMessage,
+    pub locations: Vec<SarifLocation>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SarifLevel {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    pub physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    pub artifact_location: SarifArtifactLocation,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<SarifRegion>,
+}
+
+/// A file, either as an absolute URI, or relative to a `uri_base_id`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifArtifactLocation {
+    pub uri: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uri_base_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+    /// The 1-based line the result applies to
+    pub start_line: usize,
+}
+
+////////////////////////////////////////////////////////////////////////////////////
+//                                                                                //
+//                                                                                //
+//                                                                                //
+//                                  <sbom output>                                 //
+//                                                                                //
+//                                                                                //
+//                                                                                //
+////////////////////////////////////////////////////////////////////////////////////
+
+/// cargo-vet's `sbom --format=cyclonedx`, a subset of the CycloneDX 1.5 JSON
+/// format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CycloneDxBom {
+    pub bom_format: String,
+    pub spec_version: String,
+    pub serial_number: String,
+    pub version: u32,
+    pub metadata: CycloneDxMetadata,
+    pub components: Vec<CycloneDxComponent>,
+    pub dependencies: Vec<CycloneDxDependency>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycloneDxMetadata {
+    pub timestamp: String,
+    pub tools: CycloneDxTools,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycloneDxTools {
+    pub components: Vec<CycloneDxTool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycloneDxTool {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub name: String,
+    pub version: String,
+}
+
+/// A package in the dependency graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycloneDxComponent {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(rename = "bom-ref")]
+    pub bom_ref: String,
+    pub name: String,
+    pub version: String,
+    /// The package URL, which path dependencies don't have
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purl: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hashes: Vec<CycloneDxHash>,
+    /// The cargo-vet annotations for the package, as `cargo-vet:*` properties
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<CycloneDxProperty>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycloneDxHash {
+    pub alg: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycloneDxProperty {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CycloneDxDependency {
+    #[serde(rename = "ref")]
+    pub bom_ref: String,
+    pub depends_on: Vec<String>,
+}
+
+/// cargo-vet's `sbom --format=spdx`, a subset of the SPDX 2.3 JSON format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxDocument {
+    pub spdx_version: String,
+    pub data_license: String,
+    #[serde(rename = "SPDXID")]
+    pub spdx_id: String,
+    pub name: String,
+    pub document_namespace: String,
+    pub creation_info: SpdxCreationInfo,
+    pub packages: Vec<SpdxPackage>,
+    pub relationships: Vec<SpdxRelationship>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpdxCreationInfo {
+    pub created: String,
+    pub creators: Vec<String>,
+}
+
+/// A package in the dependency graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxPackage {
+    #[serde(rename = "SPDXID")]
+    pub spdx_id: String,
+    pub name: String,
+    pub version_info: String,
+    pub download_location: String,
+    pub files_analyzed: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_info: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub checksums: Vec<SpdxChecksum>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub external_refs: Vec<SpdxExternalRef>,
+    /// The cargo-vet annotations for the package, as a single review
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<SpdxAnnotation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxChecksum {
+    pub algorithm: String,
+    pub checksum_value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxExternalRef {
+    pub reference_category: String,
+    pub reference_type: String,
+    pub reference_locator: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxAnnotation {
+    pub annotation_date: String,
+    pub annotation_type: String,
+    pub annotator: String,
+    pub comment: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxRelationship {
+    pub spdx_element_id: String,
+    pub relationship_type: String,
+    pub related_spdx_element: String,
+}
+
+////////////////////////////////////////////////////////////////////////////////////
+//                                                                                //
+//                                                                                //
+//                                                                                //
+//                               <store-diff output>                              //
+//                                                                                //
+//                                                                                //
+//                                                                                //
+////////////////////////////////////////////////////////////////////////////////////
+
+/// cargo-vet's `--output-format=json` for `store-diff`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JsonStoreDiff {
+    /// Audits and violations, both local and imported
+    pub audits: Vec<JsonStoreChange<AuditEntry>>,
+    /// Wildcard audits, both local and imported
+    pub wildcard_audits: Vec<JsonStoreChange<WildcardEntry>>,
+    pub exemptions: Vec<JsonStoreChange<ExemptedDependency>>,
+    pub policy: Vec<JsonStoreChange<PolicyEntry>>,
+    /// Local criteria definitions
+    pub criteria: Vec<JsonStoreChange<CriteriaEntry>>,
+    /// Imports configured in config.toml
+    pub imports: Vec<JsonStoreChange<RemoteImport>>,
+    /// Trusted publishers, both local and imported
+    pub trusted: Vec<JsonStoreChange<TrustEntry>>,
+}
+
+/// An entry which was added (no `old`), removed (no `new`) or changed between
+/// the two versions of the store.
+///
+/// Audits, exemptions and trusted publishers are only ever added or removed,
+/// as they have no identity beyond their contents.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonStoreChange<T> {
+    /// The package, criteria or import which the entry is for
+    pub name: String,
+    /// The version a policy applies to, if it is versioned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<VetVersion>,
+    /// The import the entry was imported from, if it isn't local
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub import: Option<ImportName>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new: Option<T>,
+}
+
+////////////////////////////////////////////////////////////////////////////////////
+//                                                                                //
+//                                                                                //
+//                                                                                //
+//                                 <stats output>                                 //
+//                                                                                //
+//                                                                                //
+//                                                                                //
+////////////////////////////////////////////////////////////////////////////////////
+
+/// cargo-vet's `--output-format=json` for `stats`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JsonStats {
+    /// How all third-party packages in the graph are vetted
+    pub coverage: JsonStatsCoverage,
+    /// How the third-party packages which require each criteria are vetted
+    /// for that criteria
+    pub criteria: SortedMap<CriteriaName, JsonStatsCoverage>,
+    /// The sources used to vet the third-party packages. A package is counted
+    /// for every source used to vet it, so these can overlap.
+    pub sources: JsonStatsSources,
+    /// The number of packages whose size couldn't be determined, which are
+    /// counted as having no lines
+    pub unknown_lines: u64,
+}
+
+/// The buckets of `JsonReportSuccess`, along with the unvetted packages.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JsonStatsCoverage {
+    pub total: JsonStatsCount,
+    pub vetted_fully: JsonStatsCount,
+    pub vetted_partially: JsonStatsCount,
+    pub vetted_with_exemptions: JsonStatsCount,
+    pub unvetted: JsonStatsCount,
+    /// Packages which couldn't be vetted due to violation conflicts
+    pub violation_conflicts: JsonStatsCount,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JsonStatsSources {
+    /// Audits in the local audits.toml
+    pub local_audits: JsonStatsCount,
+    /// Audits from each import, including wildcard audits
+    pub imports: SortedMap<ImportName, JsonStatsCount>,
+    /// Wildcard audits, both local and imported
+    pub wildcard_audits: JsonStatsCount,
+    /// Trusted publishers
+    pub trusted: JsonStatsCount,
+    pub exemptions: JsonStatsCount,
+}
+
+/// A number of packages and their total lines of code, with the percentage of
+/// all third-party packages and lines they make up.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JsonStatsCount {
+    pub packages: u64,
+    pub packages_percent: f64,
+    pub lines: u64,
+    pub lines_percent: f64,
+}
+
+////////////////////////////////////////////////////////////////////////////////////
+//                                                                                //
+//                                                                                //
+//                                                                                //
+//                                 <query output>                                 //
+//                                                                                //
+//                                                                                //
+//                                                                                //
+////////////////////////////////////////////////////////////////////////////////////
+
+/// cargo-vet's `--output-format=json` for `query`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonQuery {
+    /// The package which was searched for
+    pub name: PackageName,
+    /// The version which was searched for, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<VetVersion>,
+    pub audits: Vec<JsonQueryEntry<AuditEntry>>,
+    pub violations: Vec<JsonQueryEntry<AuditEntry>>,
+    pub wildcard_audits: Vec<JsonQueryEntry<WildcardEntry>>,
+    pub trusted: Vec<JsonQueryEntry<TrustEntry>>,
+    /// Whether the audits in the registry were searched, which requires
+    /// network access
+    pub searched_registry: bool,
+}
+
+/// An entry which was found for the package, along with where it came from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonQueryEntry<T> {
+    pub source: JsonQuerySource,
+    /// The name of the import or registry entry, if the entry isn't local
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub import: Option<ImportName>,
+    pub entry: T,
+}
+
+/// Where an entry found by `query` came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum JsonQuerySource {
+    /// The local audits.toml
+    Local,
+    /// An import configured in config.toml, as cached in imports.lock
+    Import,
+    /// A set of audits in the registry which isn't imported
+    Registry,
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
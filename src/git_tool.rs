@@ -9,7 +9,7 @@ use std::str;
 use tempfile::NamedTempFile;
 use tracing::warn;
 
-use crate::errors::EditError;
+use crate::errors::{CommandError, EditError, GitCommandError};
 use crate::out::Out;
 
 #[cfg(windows)]
@@ -360,3 +360,21 @@ impl io::Write for Pager<'_> {
         }
     }
 }
+
+/// Run `git` with the given arguments in `dir`, returning its output.
+pub async fn run_git(dir: &Path, args: &[&str]) -> Result<Vec<u8>, GitCommandError> {
+    let out = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .await
+        .map_err(CommandError::CommandFailed)?;
+    if !out.status.success() {
+        return Err(GitCommandError::Git {
+            command: args.join(" "),
+            stderr: String::from_utf8_lossy(&out.stderr).trim().to_owned(),
+        });
+    }
+    Ok(out.stdout)
+}
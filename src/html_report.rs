@@ -0,0 +1,531 @@
+//! Static HTML reports of the audit status of a project, as written by
+//! `cargo vet report --html`.
+//!
+//! The report has an index page listing every third-party crate, and a page
+//! for each crate describing how it was (or wasn't) vetted, so that the state
+//! of the supply-chain can be reviewed without running cargo.
+
+use std::fmt::{self, Write as _};
+use std::path::Path;
+
+use crate::criteria::CriteriaSet;
+use crate::describe::{describe_edge, describe_violation_conflict, xml_escape};
+use crate::errors::HtmlReportError;
+use crate::format::{AuditKind, CratesSourceId, PackageStr, SortedMap};
+use crate::resolver::{Conclusion, PackageIdx, PackageNode, ResolveReport};
+use crate::serialization::spanned::Spanned;
+use crate::storage::Store;
+
+const STYLESHEET: &str = "\
+body { font-family: sans-serif; margin: 2em auto; max-width: 60em; padding: 0 1em; }
+table { border-collapse: collapse; width: 100%; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; vertical-align: top; }
+.vetted { color: #1a7f37; }
+.exempted { color: #9a6700; }
+.unvetted, .violation { color: #cf222e; }
+";
+
+/// How a third-party package was vetted.
+#[derive(Clone, Copy)]
+enum Status {
+    Vetted,
+    PartiallyExempted,
+    Exempted,
+    Unvetted,
+    Violation,
+    NotChecked,
+}
+
+impl Status {
+    fn class(self) -> &'static str {
+        match self {
+            Status::Vetted => "vetted",
+            Status::PartiallyExempted | Status::Exempted => "exempted",
+            Status::Unvetted => "unvetted",
+            Status::Violation | Status::NotChecked => "violation",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Status::Vetted => "fully audited",
+            Status::PartiallyExempted => "partially audited",
+            Status::Exempted => "exempted",
+            Status::Unvetted => "unvetted",
+            Status::Violation => "violation conflict",
+            Status::NotChecked => "not checked because of violation conflicts",
+        }
+    }
+}
+
+/// Write an HTML report for `report` into `dir`, creating it if necessary.
+pub fn write_html_report(
+    dir: &Path,
+    report: &ResolveReport<'_>,
+    store: &Store,
+) -> Result<(), HtmlReportError> {
+    let statuses = package_statuses(report);
+
+    let write = |path: &Path, contents: &str| {
+        let path = dir.join(path);
+        let result = match path.parent() {
+            Some(parent) => std::fs::create_dir_all(parent),
+            None => Ok(()),
+        };
+        result
+            .and_then(|_| std::fs::write(&path, contents))
+            .map_err(|error| HtmlReportError { path, error })
+    };
+
+    write(Path::new("style.css"), STYLESHEET)?;
+    write(
+        Path::new("index.html"),
+        &index_page(report, &statuses).expect("writing to a String can't fail"),
+    )?;
+    for (&pkgidx, &status) in &statuses {
+        let package = &report.graph.nodes[pkgidx];
+        write(
+            Path::new(&package_page_path(package)),
+            &package_page(report, store, pkgidx, status).expect("writing to a String can't fail"),
+        )?;
+    }
+    Ok(())
+}
+
+fn package_statuses(report: &ResolveReport<'_>) -> SortedMap<PackageIdx, Status> {
+    let mut statuses = SortedMap::new();
+    let success = match &report.conclusion {
+        Conclusion::Success(success) => Some(success),
+        Conclusion::FailForVet(fail) => {
+            for &(pkgidx, _) in &fail.failures {
+                statuses.insert(pkgidx, Status::Unvetted);
+            }
+            Some(&fail.vetted)
+        }
+        Conclusion::FailForViolationConflict(fail) => {
+            for (pkgidx, package) in report.graph.nodes.iter().enumerate() {
+                if package.is_third_party {
+                    statuses.insert(pkgidx, Status::NotChecked);
+                }
+            }
+            for (pkgidx, _) in &fail.violations {
+                statuses.insert(*pkgidx, Status::Violation);
+            }
+            None
+        }
+    };
+    if let Some(success) = success {
+        for &pkgidx in &success.vetted_fully {
+            statuses.insert(pkgidx, Status::Vetted);
+        }
+        for &pkgidx in &success.vetted_partially {
+            statuses.insert(pkgidx, Status::PartiallyExempted);
+        }
+        for &pkgidx in &success.vetted_with_exemptions {
+            statuses.insert(pkgidx, Status::Exempted);
+        }
+    }
+    statuses
+}
+
+/// The path of a package's page, relative to the root of the report. Crates
+/// from alternative registries are kept apart from those on crates.io, which
+/// may have the same name and version.
+fn package_page_path(package: &PackageNode<'_>) -> String {
+    // Git versions contain characters which aren't allowed in file names on
+    // all platforms.
+    let version: String = package
+        .version
+        .to_string()
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '+' | '_' => c,
+            _ => '_',
+        })
+        .collect();
+    match &package.registry {
+        Some(registry) => format!("registries/{registry}/{}/{version}.html", package.name),
+        None => format!("crates/{}/{version}.html", package.name),
+    }
+}
+
+/// The path of the root of the report, relative to a package's page.
+fn package_page_root(package: &PackageNode<'_>) -> String {
+    "../".repeat(package_page_path(package).matches('/').count())
+}
+
+fn page_header(html: &mut String, title: &str, root: &str) -> fmt::Result {
+    let title = xml_escape(title);
+    writeln!(html, "<!DOCTYPE html>")?;
+    writeln!(html, "<html lang=\"en\">")?;
+    writeln!(html, "<head>")?;
+    writeln!(html, "<meta charset=\"utf-8\">")?;
+    writeln!(html, "<title>{title}</title>")?;
+    writeln!(html, "<link rel=\"stylesheet\" href=\"{root}style.css\">")?;
+    writeln!(html, "</head>")?;
+    writeln!(html, "<body>")?;
+    writeln!(html, "<h1>{title}</h1>")
+}
+
+fn page_footer(html: &mut String) -> fmt::Result {
+    writeln!(html, "</body>")?;
+    writeln!(html, "</html>")
+}
+
+fn criteria_list(report: &ResolveReport<'_>, criteria: &CriteriaSet) -> String {
+    let names: Vec<_> = report.criteria_mapper.criteria_names(criteria).collect();
+    if names.is_empty() {
+        "none".to_owned()
+    } else {
+        xml_escape(&names.join(", "))
+    }
+}
+
+fn index_page(
+    report: &ResolveReport<'_>,
+    statuses: &SortedMap<PackageIdx, Status>,
+) -> Result<String, fmt::Error> {
+    let mut html = String::new();
+    page_header(&mut html, "Supply-chain audit status", "")?;
+
+    let summary = match &report.conclusion {
+        Conclusion::Success(_) => "Vetting succeeded.".to_owned(),
+        Conclusion::FailForVet(fail) => format!(
+            "Vetting failed, with {} unvetted dependencies.",
+            fail.failures.len()
+        ),
+        Conclusion::FailForViolationConflict(_) => {
+            "Vetting failed, because violations were found.".to_owned()
+        }
+    };
+    writeln!(html, "<p>{summary}</p>")?;
+    writeln!(html, "<table>")?;
+    writeln!(
+        html,
+        "<tr><th>Crate</th><th>Version</th><th>Required Criteria</th><th>Status</th></tr>"
+    )?;
+    let mut packages: Vec<_> = statuses
+        .iter()
+        .map(|(&pkgidx, &status)| (pkgidx, &report.graph.nodes[pkgidx], status))
+        .collect();
+    packages.sort_by(|(_, a, _), (_, b, _)| (a.name, &a.version).cmp(&(b.name, &b.version)));
+    for (pkgidx, package, status) in packages {
+        writeln!(
+            html,
+            "<tr><td><a href=\"{}\">{}</a></td><td>{}</td><td>{}</td><td class=\"{}\">{}</td></tr>",
+            xml_escape(&package_page_path(package)),
+            xml_escape(package.name),
+            xml_escape(&package.version.to_string()),
+            criteria_list(report, &report.requirements[pkgidx]),
+            status.class(),
+            status.description()
+        )?;
+    }
+    writeln!(html, "</table>")?;
+    page_footer(&mut html)?;
+    Ok(html)
+}
+
+fn package_page(
+    report: &ResolveReport<'_>,
+    store: &Store,
+    pkgidx: PackageIdx,
+    status: Status,
+) -> Result<String, fmt::Error> {
+    let package = &report.graph.nodes[pkgidx];
+    let root = package_page_root(package);
+    let mut html = String::new();
+    page_header(
+        &mut html,
+        &format!("{} {}", package.name, package.version),
+        &root,
+    )?;
+
+    writeln!(html, "<p><a href=\"{root}index.html\">All crates</a></p>")?;
+    writeln!(html, "<table>")?;
+    writeln!(
+        html,
+        "<tr><th>Version</th><td>{}</td></tr>",
+        xml_escape(&package.version.to_string())
+    )?;
+    if let Some(registry) = &package.registry {
+        writeln!(
+            html,
+            "<tr><th>Registry</th><td>{}</td></tr>",
+            xml_escape(registry)
+        )?;
+    }
+    writeln!(
+        html,
+        "<tr><th>Required Criteria</th><td>{}</td></tr>",
+        criteria_list(report, &report.requirements[pkgidx])
+    )?;
+    writeln!(
+        html,
+        "<tr><th>Status</th><td class=\"{}\">{}</td></tr>",
+        status.class(),
+        status.description()
+    )?;
+    writeln!(html, "</table>")?;
+
+    write_audit_paths(&mut html, report, store, pkgidx)?;
+    write_violations(&mut html, report, pkgidx)?;
+    write_audits(&mut html, store, package)?;
+    write_exemptions(&mut html, store, package.name)?;
+    write_reverse_deps(&mut html, report, package, &root)?;
+
+    page_footer(&mut html)?;
+    Ok(html)
+}
+
+fn write_audit_paths(
+    html: &mut String,
+    report: &ResolveReport<'_>,
+    store: &Store,
+    pkgidx: PackageIdx,
+) -> fmt::Result {
+    let package = &report.graph.nodes[pkgidx];
+    writeln!(html, "<h2>Audit paths</h2>")?;
+    let Some(result) = &report.results[pkgidx] else {
+        writeln!(
+            html,
+            "<p>This crate was not checked because of violation conflicts.</p>"
+        )?;
+        return Ok(());
+    };
+    let required: Vec<_> = report
+        .criteria_mapper
+        .criteria_names(&report.requirements[pkgidx])
+        .collect();
+    if required.is_empty() {
+        writeln!(html, "<p>No criteria are required of this crate.</p>")?;
+        return Ok(());
+    }
+    for criteria_name in required {
+        let criteria_idx = report.criteria_mapper.criteria_index(criteria_name);
+        writeln!(html, "<h3>{}</h3>", xml_escape(criteria_name))?;
+        match &result.search_results[criteria_idx] {
+            Ok(path) => {
+                writeln!(html, "<ol>")?;
+                for edge in path.iter().rev() {
+                    writeln!(
+                        html,
+                        "<li>{}</li>",
+                        xml_escape(&describe_edge(store, package.name, edge))
+                    )?;
+                }
+                writeln!(html, "</ol>")?;
+            }
+            Err(failure) => {
+                writeln!(html, "<p class=\"unvetted\">No audit path was found.</p>")?;
+                let versions: Vec<_> = failure
+                    .reachable_from_root
+                    .iter()
+                    .flatten()
+                    .map(|version| xml_escape(&version.to_string()))
+                    .collect();
+                if !versions.is_empty() {
+                    writeln!(
+                        html,
+                        "<p>These versions would certify: {}</p>",
+                        versions.join(", ")
+                    )?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_violations(
+    html: &mut String,
+    report: &ResolveReport<'_>,
+    pkgidx: PackageIdx,
+) -> fmt::Result {
+    let Conclusion::FailForViolationConflict(fail) = &report.conclusion else {
+        return Ok(());
+    };
+    let Some((_, violations)) = fail.violations.iter().find(|(idx, _)| *idx == pkgidx) else {
+        return Ok(());
+    };
+    let package = &report.graph.nodes[pkgidx];
+    writeln!(html, "<h2>Violations</h2>")?;
+    writeln!(html, "<ul>")?;
+    for violation in violations {
+        let (.., text) = describe_violation_conflict(package, violation);
+        writeln!(html, "<li class=\"violation\">{}</li>", xml_escape(&text))?;
+    }
+    writeln!(html, "</ul>")
+}
+
+fn write_audits(html: &mut String, store: &Store, package: &PackageNode<'_>) -> fmt::Result {
+    writeln!(html, "<h2>Audits</h2>")?;
+    let mut rows = Vec::new();
+    let sources = std::iter::once(("local", &store.audits)).chain(
+        store
+            .imported_audits()
+            .iter()
+            .map(|(name, audits)| (&name[..], audits)),
+    );
+    for (source, audits) in sources {
+        for entry in audits.audits.get(package.name).into_iter().flatten() {
+            if entry.registry != package.registry {
+                continue;
+            }
+            let kind = match &entry.kind {
+                AuditKind::Full { version } => format!("full audit of {version}"),
+                AuditKind::Delta { from, to } => format!("delta audit from {from} to {to}"),
+                AuditKind::Violation { violation } => format!("violation against {violation}"),
+            };
+            let expires = entry
+                .expires
+                .map(|expires| format!("expires {expires}"))
+                .unwrap_or_default();
+            rows.push([
+                source.to_owned(),
+                kind,
+                join(&entry.criteria),
+                join(&entry.who),
+                expires,
+                entry.notes.clone().unwrap_or_default(),
+            ]);
+        }
+        for entry in audits
+            .wildcard_audits
+            .get(package.name)
+            .into_iter()
+            .flatten()
+        {
+            rows.push([
+                source.to_owned(),
+                format!(
+                    "wildcard audit of versions published by {}",
+                    source_name(store, package.name, &entry.source)
+                ),
+                join(&entry.criteria),
+                join(&entry.who),
+                format!("{} to {}", *entry.start, *entry.end),
+                entry.notes.clone().unwrap_or_default(),
+            ]);
+        }
+    }
+    for entry in store.audits.trusted.get(package.name).into_iter().flatten() {
+        rows.push([
+            "local".to_owned(),
+            format!(
+                "trusted publisher {}",
+                source_name(store, package.name, &entry.source)
+            ),
+            join(&entry.criteria),
+            String::new(),
+            format!("{} to {}", *entry.start, *entry.end),
+            entry.notes.clone().unwrap_or_default(),
+        ]);
+    }
+
+    if rows.is_empty() {
+        writeln!(html, "<p>There are no audits of this crate.</p>")?;
+        return Ok(());
+    }
+    write_table(
+        html,
+        ["Source", "Audit", "Criteria", "Who", "When", "Notes"],
+        rows,
+    )
+}
+
+fn join(items: &[Spanned<String>]) -> String {
+    items
+        .iter()
+        .map(|item| item.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A readable name for a crates.io publisher, using their login if it's known.
+fn source_name(store: &Store, package: PackageStr<'_>, source: &CratesSourceId) -> String {
+    let publisher = store
+        .publishers()
+        .get(package)
+        .into_iter()
+        .flatten()
+        .find(|publisher| publisher.source == *source);
+    match (publisher, source) {
+        (Some(publisher), _) => publisher.source.as_identifier().to_owned(),
+        (None, CratesSourceId::User { user_id }) => format!("user {user_id}"),
+        (None, CratesSourceId::TrustedPublisher { trusted_publisher }) => {
+            trusted_publisher.to_string()
+        }
+    }
+}
+
+fn write_exemptions(html: &mut String, store: &Store, package: PackageStr<'_>) -> fmt::Result {
+    let Some(exemptions) = store.config.exemptions.get(package) else {
+        return Ok(());
+    };
+    writeln!(html, "<h2>Exemptions</h2>")?;
+    let rows = exemptions.iter().map(|exemption| {
+        [
+            exemption.version.to_string(),
+            join(&exemption.criteria),
+            exemption
+                .expires
+                .map(|expires| format!("expires {expires}"))
+                .unwrap_or_default(),
+            exemption.notes.clone().unwrap_or_default(),
+        ]
+    });
+    write_table(html, ["Version", "Criteria", "When", "Notes"], rows)
+}
+
+fn write_reverse_deps(
+    html: &mut String,
+    report: &ResolveReport<'_>,
+    package: &PackageNode<'_>,
+    root: &str,
+) -> fmt::Result {
+    writeln!(html, "<h2>Used by</h2>")?;
+    if package.reverse_deps.is_empty() {
+        writeln!(
+            html,
+            "<p>Nothing in the dependency graph uses this crate.</p>"
+        )?;
+        return Ok(());
+    }
+    writeln!(html, "<ul>")?;
+    for &parentidx in &package.reverse_deps {
+        let parent = &report.graph.nodes[parentidx];
+        let label = xml_escape(&format!("{} {}", parent.name, parent.version));
+        if parent.is_third_party {
+            writeln!(
+                html,
+                "<li><a href=\"{root}{}\">{label}</a></li>",
+                xml_escape(&package_page_path(parent))
+            )?;
+        } else {
+            writeln!(html, "<li>{label} (first-party)</li>")?;
+        }
+    }
+    writeln!(html, "</ul>")
+}
+
+fn write_table<const N: usize>(
+    html: &mut String,
+    headings: [&str; N],
+    rows: impl IntoIterator<Item = [String; N]>,
+) -> fmt::Result {
+    writeln!(html, "<table>")?;
+    write!(html, "<tr>")?;
+    for heading in headings {
+        write!(html, "<th>{heading}</th>")?;
+    }
+    writeln!(html, "</tr>")?;
+    for row in rows {
+        write!(html, "<tr>")?;
+        for cell in row {
+            write!(html, "<td>{}</td>", xml_escape(&cell))?;
+        }
+        writeln!(html, "</tr>")?;
+    }
+    writeln!(html, "</table>")
+}
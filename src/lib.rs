@@ -0,0 +1,372 @@
+//! Supply-chain security for Rust.
+//!
+//! This crate implements the `cargo vet` command, and can also be used as a
+//! library to embed vet results in other tools without shelling out and
+//! parsing JSON output.
+//!
+//! # Stable API
+//!
+//! The following items are covered by semver, and won't change incompatibly
+//! without a major version bump:
+//!
+//! * building a [`Config`] with [`PartialConfig::new`] and [`Config::new`],
+//!   rather than from command-line arguments,
+//! * loading and validating the `supply-chain` directory with
+//!   [`storage::Store::acquire_offline`] and [`storage::Store::acquire`],
+//! * [`resolver::resolve`], and the methods of its [`resolver::ResolveReport`]
+//!   and of the [`resolver::Suggest`]ions computed from it,
+//! * the [`out::Out`] trait used for all output.
+//!
+//! The types these return have public fields so that results can be
+//! inspected, but fields may be added to them, and variants to their enums, in
+//! any minor release. The same applies to the file format and JSON output types
+//! in [`format`](mod@format): the TOML and JSON they read and write is stable,
+//! but their Rust definitions aren't. Don't construct these types with struct
+//! literals or match on them exhaustively; [`PartialConfig`],
+//! [`resolver::ResolveReport`], [`resolver::Suggest`] and
+//! [`resolver::Conclusion`] are `#[non_exhaustive]` to enforce this.
+//!
+//! Other public items, such as the command-line definitions in [`cli`], are
+//! exposed for use by the `cargo-vet` binary and may change in any release.
+//!
+//! Many operations run asynchronously on the current [tokio] runtime, so they
+//! must be called from within a multi-threaded runtime context.
+//!
+//! ```no_run
+//! use std::sync::{Arc, Mutex};
+//!
+//! use miette::IntoDiagnostic;
+//! use cargo_vet::{out::Out, resolver, storage::Store, Config, PartialConfig};
+//!
+//! # fn main() -> Result<(), miette::Report> {
+//! let runtime = tokio::runtime::Runtime::new().unwrap();
+//! let _guard = runtime.enter();
+//!
+//! let metadata = cargo_metadata::MetadataCommand::new().exec().unwrap();
+//! let cfg = Config::new(PartialConfig::new(), metadata)?;
+//! let store = Store::acquire_offline(&cfg)?;
+//!
+//! let report = resolver::resolve(&cfg.metadata, None, &store, cfg.today());
+//! if report.has_errors() {
+//!     let output = Arc::new(Mutex::new(Vec::new()));
+//!     report
+//!         .print_human(&(output.clone() as Arc<dyn Out>), &cfg, None)
+//!         .into_diagnostic()?;
+//!     eprintln!("{}", String::from_utf8_lossy(&output.lock().unwrap()));
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::ops::Deref;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use cargo_metadata::{Metadata, Package};
+use clap::Parser;
+use miette::{miette, Context, IntoDiagnostic};
+use reqwest::Url;
+use serde::de::Deserialize;
+
+use crate::cli::*;
+use crate::format::{
+    CargoRegistry, ConfigFile, MetaConfig, MetaConfigInstance, PackageStr, Policy, PolicyEntry,
+    StoreInfo, VetVersion,
+};
+use crate::storage::Store;
+
+pub mod cli;
+mod commands;
+pub mod criteria;
+mod describe;
+pub mod errors;
+mod flock;
+pub mod format;
+mod git_tool;
+mod html_report;
+pub mod network;
+pub mod out;
+mod query;
+pub mod resolver;
+mod sbom;
+mod serialization;
+mod signing;
+mod stats;
+pub mod storage;
+mod store_diff;
+mod string_format;
+#[cfg(test)]
+mod tests;
+
+/// The entry point of the `cargo-vet` binary, which isn't part of the library
+/// API.
+#[doc(hidden)]
+pub use commands::cli_main;
+
+/// Absolutely All The Global Configurations
+pub struct Config {
+    /// Cargo.toml `metadata.vet`
+    pub metacfg: MetaConfig,
+    /// `cargo metadata`
+    pub metadata: Metadata,
+    /// Target triples `cargo metadata` was filtered to, if any
+    pub targets: Vec<String>,
+    /// Freestanding configuration values
+    _rest: PartialConfig,
+}
+
+/// Configuration vars that are available in a free-standing situation
+/// (no actual cargo-vet instance to load/query).
+#[non_exhaustive]
+pub struct PartialConfig {
+    /// Details of the CLI invocation (args)
+    pub cli: Cli,
+    /// The date and time to use as the current time.
+    pub now: chrono::DateTime<chrono::Utc>,
+    /// Path to the cache directory we're using
+    pub cache_dir: PathBuf,
+    /// Whether we should mock the global cache (for unit testing)
+    pub mock_cache: bool,
+}
+
+impl PartialConfig {
+    /// Create a configuration with the default value for every command-line
+    /// option, as if `cargo vet` were run without any arguments, for use when
+    /// embedding cargo-vet as a library.
+    pub fn new() -> Self {
+        let cli::FakeCli::Vet(cli) = cli::FakeCli::parse_from(["cargo", "vet"]);
+        Self::from_cli(cli)
+    }
+
+    fn from_cli(cli: Cli) -> Self {
+        let cache_dir = cli.cache_dir.clone().unwrap_or_else(|| {
+            dirs::cache_dir()
+                .unwrap_or_else(std::env::temp_dir)
+                .join(CACHE_DIR_SUFFIX)
+        });
+        let now = cli
+            .current_time
+            .unwrap_or_else(|| chrono::DateTime::from(SystemTime::now()));
+        PartialConfig {
+            cli,
+            now,
+            cache_dir,
+            mock_cache: false,
+        }
+    }
+
+    pub fn today(&self) -> chrono::NaiveDate {
+        self.now.date_naive()
+    }
+}
+
+impl Default for PartialConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Config {
+    /// Create a configuration for the workspace described by `metadata`,
+    /// locating its `supply-chain` directory the same way as `cargo vet`.
+    ///
+    /// `metadata` should have been produced by `cargo metadata` with the
+    /// features and `--filter-platform` targets which should be vetted. The
+    /// targets listed in `partial.cli.target` are recorded as-is, and are not
+    /// read from the store.
+    pub fn new(partial: PartialConfig, metadata: Metadata) -> Result<Self, miette::Report> {
+        let metacfg = load_metacfg(&partial.cli, &metadata)?;
+        Ok(Config {
+            metacfg,
+            targets: partial.cli.target.clone(),
+            metadata,
+            _rest: partial,
+        })
+    }
+}
+
+// Makes it a bit easier to have both a "partial" and "full" config
+impl Deref for Config {
+    type Target = PartialConfig;
+    fn deref(&self) -> &Self::Target {
+        &self._rest
+    }
+}
+
+pub trait PackageExt {
+    fn is_third_party(&self, config: &ConfigFile) -> bool;
+    fn is_crates_io(&self) -> bool;
+    fn registry<'a>(&self, config: &'a ConfigFile) -> Option<CargoRegistry<'a>>;
+    fn policy_entry<'a>(&self, policy: &'a Policy) -> Option<&'a PolicyEntry>;
+    fn git_rev(&self) -> Option<String>;
+    fn vet_version(&self) -> VetVersion;
+}
+
+impl PackageExt for Package {
+    fn is_third_party(&self, config: &ConfigFile) -> bool {
+        let forced_third_party = self
+            .policy_entry(&config.policy)
+            .and_then(|policy| policy.audit_as_crates_io)
+            .unwrap_or(false);
+
+        forced_third_party || self.is_crates_io() || self.registry(config).is_some()
+    }
+
+    fn is_crates_io(&self) -> bool {
+        self.source
+            .as_ref()
+            .map(|s| s.is_crates_io())
+            .unwrap_or(false)
+    }
+
+    fn registry<'a>(&self, config: &'a ConfigFile) -> Option<CargoRegistry<'a>> {
+        let source = self.source.as_ref()?;
+        config
+            .registries
+            .iter()
+            .find(|(_, entry)| {
+                entry.index.trim_end_matches('/') == source.repr.trim_end_matches('/')
+            })
+            .and_then(|(name, _)| config.cargo_registry(name))
+    }
+
+    fn policy_entry<'a>(&self, policy: &'a Policy) -> Option<&'a PolicyEntry> {
+        policy.get(&self.name, &self.vet_version())
+    }
+
+    fn git_rev(&self) -> Option<String> {
+        self.source.as_ref().and_then(|s| {
+            let git_source = s.repr.strip_prefix("git+")?;
+            let source_url = Url::parse(git_source).ok()?;
+            Some(source_url.fragment()?.to_owned())
+        })
+    }
+
+    fn vet_version(&self) -> VetVersion {
+        VetVersion {
+            semver: self.version.clone(),
+            git_rev: self.git_rev(),
+        }
+    }
+}
+
+const CACHE_DIR_SUFFIX: &str = "cargo-vet";
+const CARGO_ENV: &str = "CARGO";
+// package.metadata.vet
+const PACKAGE_VET_CONFIG: &str = "vet";
+// workspace.metadata.vet
+const WORKSPACE_VET_CONFIG: &str = "vet";
+
+/// Determine where the store lives from the `[metadata.vet]` tables of the
+/// workspace and root package, and the `--store-path` argument.
+fn load_metacfg(cli: &Cli, metadata: &Metadata) -> Result<MetaConfig, miette::Report> {
+    let default_config = MetaConfigInstance {
+        version: Some(1),
+        store: Some(StoreInfo {
+            path: Some(
+                metadata
+                    .workspace_root
+                    .join(storage::DEFAULT_STORE)
+                    .into_std_path_buf(),
+            ),
+        }),
+    };
+
+    // FIXME: what is `store.path` relative to here?
+    let workspace_metacfg = metadata
+        .workspace_metadata
+        .get(WORKSPACE_VET_CONFIG)
+        .map(|cfg| {
+            // ERRORS: immediate fatal diagnostic
+            MetaConfigInstance::deserialize(cfg)
+                .into_diagnostic()
+                .wrap_err("Workspace had [{WORKSPACE_VET_CONFIG}] but it was malformed")
+        })
+        .transpose()?;
+
+    // FIXME: what is `store.path` relative to here?
+    let package_metacfg = metadata
+        .root_package()
+        .and_then(|r| r.metadata.get(PACKAGE_VET_CONFIG))
+        .map(|cfg| {
+            // ERRORS: immediate fatal diagnostic
+            MetaConfigInstance::deserialize(cfg)
+                .into_diagnostic()
+                .wrap_err("Root package had [{PACKAGE_VET_CONFIG}] but it was malformed")
+        })
+        .transpose()?;
+
+    let cli_metacfg = cli.store_path.as_ref().map(|path| MetaConfigInstance {
+        version: Some(1),
+        store: Some(StoreInfo {
+            path: Some(path.clone()),
+        }),
+    });
+
+    if workspace_metacfg.is_some() && package_metacfg.is_some() {
+        // ERRORS: immediate fatal diagnostic
+        return Err(miette!("Both a workspace and a package defined [metadata.vet]! We don't know what that means, if you do, let us know!"));
+    }
+
+    let mut metacfgs = vec![default_config];
+    if let Some(metacfg) = workspace_metacfg {
+        metacfgs.push(metacfg);
+    }
+    if let Some(metacfg) = package_metacfg {
+        metacfgs.push(metacfg);
+    }
+    if let Some(metacfg) = cli_metacfg {
+        metacfgs.push(metacfg);
+    }
+    Ok(MetaConfig(metacfgs))
+}
+
+/// All third-party packages, with the audit-as-crates-io policy applied
+fn foreign_packages<'a>(
+    metadata: &'a Metadata,
+    config: &'a ConfigFile,
+) -> impl Iterator<Item = &'a Package> + 'a {
+    // Only analyze things from crates.io (no source = path-dep / workspace-member)
+    metadata
+        .packages
+        .iter()
+        .filter(|package| package.is_third_party(config))
+}
+
+/// The alternative registry which third-party packages named `package` are
+/// fetched from, or `None` if they're fetched from crates.io.
+pub(crate) fn package_registry<'a>(
+    metadata: &Metadata,
+    config: &'a ConfigFile,
+    package: PackageStr<'_>,
+) -> Option<CargoRegistry<'a>> {
+    metadata
+        .packages
+        .iter()
+        .filter(|p| *p.name == *package)
+        .find_map(|p| p.registry(config))
+}
+
+/// All first-party packages, **without** the audit-as-crates-io policy applied
+/// (because it's used for validating that field's value).
+fn first_party_packages_strict<'a>(
+    metadata: &'a Metadata,
+    config: &'a ConfigFile,
+) -> impl Iterator<Item = &'a Package> + 'a {
+    metadata
+        .packages
+        .iter()
+        .filter(move |package| !package.is_crates_io() && package.registry(config).is_none())
+}
+
+/// All third-party packages, **without** the audit-as-crates-io policy applied (used in crate
+/// policy verification).
+fn foreign_packages_strict<'a>(
+    metadata: &'a Metadata,
+    _config: &ConfigFile,
+) -> impl Iterator<Item = &'a Package> + 'a {
+    metadata
+        .packages
+        .iter()
+        .filter(move |package| package.is_crates_io())
+}
@@ -9,6 +9,7 @@
 
 use std::{
     ffi::{OsStr, OsString},
+    hash::{BuildHasher, Hasher},
     io::Write,
     path::{Path, PathBuf},
     sync::Mutex,
@@ -19,9 +20,12 @@ use base64_stream::FromBase64Writer;
 use bytes::Bytes;
 use reqwest::{Client, Url};
 use tokio::io::AsyncWriteExt;
+use tracing::warn;
 
 use crate::{
+    cli::Cli,
     errors::{DownloadError, SourceFile},
+    storage::CratesIoReplacement,
     PartialConfig,
 };
 
@@ -68,6 +72,12 @@ pub struct Network {
     client: Client,
     /// Semaphore preventing exceeding the maximum number of connections.
     connection_semaphore: tokio::sync::Semaphore,
+    /// How many times to retry requests which fail with spurious errors.
+    retries: u32,
+    /// Explicitly configured locations for crates.io, such as a mirror.
+    crates_io_settings: CratesIoSettings,
+    /// The resolved locations for crates.io, see `Network::crates_io`.
+    crates_io: tokio::sync::OnceCell<CratesIoUrls>,
     /// Cache of source files downloaded by Url
     source_file_cache: Mutex<std::collections::HashMap<Url, SourceFile>>,
     /// Test-only override for download requests.
@@ -76,7 +86,12 @@ pub struct Network {
 }
 
 const DEFAULT_TIMEOUT_SECS: u64 = 60;
-const MAX_CONCURRENT_CONNECTIONS: usize = 40;
+const DEFAULT_MAX_CONNECTIONS: u32 = 40;
+const DEFAULT_NET_RETRY: u32 = 3;
+/// The delay before the first retry, which is doubled for each later retry.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+/// The longest delay between retries, however many retries are configured.
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(10);
 const USER_AGENT: &str = concat!(
     env!("CARGO_PKG_NAME"),
     "/",
@@ -86,6 +101,80 @@ const USER_AGENT: &str = concat!(
     ")"
 );
 
+const CRATES_IO_INDEX: &str = "https://index.crates.io/";
+const CRATES_IO_API: &str = "https://crates.io/";
+pub(crate) const CRATES_IO_DOWNLOAD: &str = "https://crates.io/api/v1/crates";
+
+/// Where crates.io's index, web API and crates are fetched from. These are
+/// crates.io itself unless a mirror has been configured.
+#[derive(Debug, Clone)]
+pub struct CratesIoUrls {
+    /// The sparse index, ending with a `/`.
+    pub index: Url,
+    /// The root of the web API, which `api/v1/crates/{crate}` is joined to.
+    pub api: Url,
+    /// The template used to download crates, in the same format as the `dl`
+    /// key of a registry's `config.json`.
+    pub download: String,
+}
+
+impl Default for CratesIoUrls {
+    fn default() -> Self {
+        CratesIoUrls {
+            index: Url::parse(CRATES_IO_INDEX).unwrap(),
+            api: Url::parse(CRATES_IO_API).unwrap(),
+            download: CRATES_IO_DOWNLOAD.to_owned(),
+        }
+    }
+}
+
+/// Locations for crates.io from the command line or cargo's source
+/// replacement. Anything which isn't set is read from the index's
+/// `config.json` when using a mirror, or is crates.io's own.
+#[derive(Default)]
+struct CratesIoSettings {
+    index: Option<Url>,
+    api: Option<Url>,
+    download: Option<String>,
+}
+
+/// Network settings which can be set both on the command line and in cargo's
+/// configuration.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct NetworkSettings {
+    pub timeout: u64,
+    pub retries: u32,
+    pub proxy: Option<String>,
+}
+
+impl NetworkSettings {
+    /// Settings on the command line take precedence over cargo's
+    /// configuration, which also handles the `CARGO_HTTP_*` and `CARGO_NET_*`
+    /// environment variables.
+    pub(crate) fn new(cli: &Cli, cargo_config: Option<&cargo_config2::Config>) -> Self {
+        let http = cargo_config.map(|cargo_config| &cargo_config.http);
+        let net = cargo_config.map(|cargo_config| &cargo_config.net);
+        NetworkSettings {
+            timeout: cli
+                .http_timeout
+                .or_else(|| http?.timeout.map(u64::from))
+                .unwrap_or(DEFAULT_TIMEOUT_SECS),
+            retries: cli
+                .net_retry
+                .or_else(|| net?.retry)
+                .unwrap_or(DEFAULT_NET_RETRY),
+            proxy: cli.http_proxy.clone().or_else(|| http?.proxy.clone()),
+        }
+    }
+}
+
+/// The subset of a registry's `config.json` which is used by cargo-vet.
+#[derive(serde::Deserialize)]
+struct RegistryConfigJson {
+    dl: String,
+    api: Option<String>,
+}
+
 /// The network payload encoding.
 ///
 /// This is only used in `download` (not `download_and_persist`) because (for now) it's only needed
@@ -134,19 +223,46 @@ impl Network {
         if cfg.cli.frozen {
             None
         } else {
-            // TODO: make this configurable on the CLI or something
-            let timeout = Duration::from_secs(DEFAULT_TIMEOUT_SECS);
-            // TODO: make this configurable on the CLI or something
-            let mut client_builder = Client::builder().user_agent(USER_AGENT).timeout(timeout);
-            if let Ok(cargo_config) = cargo_config2::Config::load() {
-                // Add the cargo `http.cainfo` to the reqwest client if it is set
-                if let Some(cainfo) = cargo_config.http.cainfo {
-                    match Network::parse_ca_file(&cainfo) {
-                        Ok(cert) => client_builder = client_builder.add_root_certificate(cert),
-                        Err(e) => println!(
-                            "failed to load certificate from Cargo http.cainfo `{}`, attempting to download without it. Error: {e:?}", cainfo
-                       ),
-                    }
+            let cargo_config = cargo_config2::Config::load().ok();
+            let http = cargo_config.as_ref().map(|cargo_config| &cargo_config.http);
+            let NetworkSettings {
+                timeout,
+                retries,
+                proxy,
+            } = NetworkSettings::new(&cfg.cli, cargo_config.as_ref());
+            let max_connections = cfg.cli.max_connections.unwrap_or(DEFAULT_MAX_CONNECTIONS);
+            let crates_io_settings = CratesIoSettings {
+                index: cfg
+                    .cli
+                    .crates_io_index
+                    .clone()
+                    .or_else(crates_io_source_replacement),
+                api: cfg.cli.crates_io_api.clone(),
+                download: cfg.cli.crates_io_download.clone(),
+            };
+
+            let mut client_builder = Client::builder()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(timeout));
+
+            // Without an explicit proxy, reqwest falls back to the
+            // `HTTPS_PROXY` and `HTTP_PROXY` environment variables.
+            if let Some(proxy) = proxy {
+                match Network::parse_proxy(&proxy) {
+                    Ok(proxy) => client_builder = client_builder.proxy(proxy),
+                    Err(e) => warn!(
+                        "failed to parse HTTP proxy `{proxy}`, attempting to download without it. Error: {e:?}"
+                    ),
+                }
+            }
+
+            // Add the cargo `http.cainfo` to the reqwest client if it is set
+            if let Some(cainfo) = http.and_then(|http| http.cainfo.as_ref()) {
+                match Network::parse_ca_file(cainfo) {
+                    Ok(cert) => client_builder = client_builder.add_root_certificate(cert),
+                    Err(e) => println!(
+                        "failed to load certificate from Cargo http.cainfo `{}`, attempting to download without it. Error: {e:?}", cainfo
+                    ),
                 }
             }
             let client = client_builder
@@ -154,7 +270,10 @@ impl Network {
                 .expect("Couldn't construct HTTP Client?");
             Some(Self {
                 client,
-                connection_semaphore: tokio::sync::Semaphore::new(MAX_CONCURRENT_CONNECTIONS),
+                connection_semaphore: tokio::sync::Semaphore::new(max_connections as usize),
+                retries,
+                crates_io_settings,
+                crates_io: Default::default(),
                 source_file_cache: Default::default(),
                 #[cfg(test)]
                 mock_network: None,
@@ -162,6 +281,61 @@ impl Network {
         }
     }
 
+    /// The locations to fetch crates.io's index, API and crates from.
+    ///
+    /// When a mirror's index is configured without also configuring its API
+    /// and download URLs, they're read from the mirror's `config.json` the
+    /// first time this is called. Mirrors without an API fall back to
+    /// crates.io's, which is only used for publisher information.
+    pub async fn crates_io(&self) -> Result<&CratesIoUrls, DownloadError> {
+        self.crates_io
+            .get_or_try_init(|| async {
+                let settings = &self.crates_io_settings;
+                let mut urls = CratesIoUrls::default();
+                if let Some(index) = &settings.index {
+                    urls.index = index.clone();
+                    if settings.api.is_none() || settings.download.is_none() {
+                        let config_url = index.join("config.json").unwrap();
+                        let config = self.download(config_url.clone()).await?;
+                        let config: RegistryConfigJson =
+                            serde_json::from_slice(&config).map_err(|error| {
+                                DownloadError::InvalidRegistryConfig {
+                                    url: Box::new(config_url.clone()),
+                                    error,
+                                }
+                            })?;
+                        urls.download = config.dl;
+                        if let Some(api) = config.api {
+                            urls.api = Url::parse(&format!("{}/", api.trim_end_matches('/')))
+                                .map_err(|error| DownloadError::InvalidRegistryApi {
+                                    url: Box::new(config_url),
+                                    api,
+                                    error,
+                                })?;
+                        }
+                    }
+                }
+                if let Some(api) = &settings.api {
+                    urls.api = api.clone();
+                }
+                if let Some(download) = &settings.download {
+                    urls.download = download.clone();
+                }
+                Ok(urls)
+            })
+            .await
+    }
+
+    /// Parse a proxy in cargo's `[protocol://]host[:port]` format, where the
+    /// protocol defaults to `http`.
+    pub(crate) fn parse_proxy(proxy: &str) -> Result<reqwest::Proxy, reqwest::Error> {
+        if proxy.contains("://") {
+            reqwest::Proxy::all(proxy)
+        } else {
+            reqwest::Proxy::all(format!("http://{proxy}"))
+        }
+    }
+
     fn parse_ca_file(path: &str) -> Result<reqwest::Certificate, Box<dyn std::error::Error>> {
         Ok(reqwest::Certificate::from_pem(&std::fs::read(path)?)?)
     }
@@ -176,8 +350,9 @@ impl Network {
             persist_to.as_os_str(),
             OsStr::new(".part"),
         ]));
-        {
-            let mut res = self.fetch_core(url).await?;
+        let mut attempt = 0;
+        loop {
+            let mut res = self.fetch_core(&url, &mut attempt).await?;
 
             let mut download_tmp =
                 tokio::fs::File::create(&download_tmp_path)
@@ -187,13 +362,31 @@ impl Network {
                         error,
                     })?;
 
-            while let Some(chunk) = res.chunk().await? {
-                download_tmp.write_all(&chunk[..]).await.map_err(|error| {
-                    DownloadError::FailedToWriteDownload {
-                        target: download_tmp_path.clone(),
-                        error,
-                    }
-                })?;
+            let result = async {
+                while let Some(chunk) = res.chunk().await? {
+                    download_tmp.write_all(&chunk[..]).await.map_err(|error| {
+                        DownloadError::FailedToWriteDownload {
+                            target: download_tmp_path.clone(),
+                            error,
+                        }
+                    })?;
+                }
+                Ok(())
+            }
+            .await;
+            drop(res);
+            // Retry the whole download if the connection was dropped while
+            // reading the response.
+            let (read_url, error) = match result {
+                Ok(()) => break,
+                Err(DownloadError::FailedToReadDownload { url, error }) => (url, error),
+                Err(error) => return Err(error),
+            };
+            if !self.wait_to_retry(&url, &mut attempt, &error).await {
+                return Err(DownloadError::FailedToReadDownload {
+                    url: read_url,
+                    error,
+                });
             }
         }
 
@@ -214,23 +407,40 @@ impl Network {
 
     /// Download a file into memory
     pub async fn download(&self, url: Url) -> Result<Vec<u8>, DownloadError> {
-        let mut res = self.fetch_core(url).await?;
-
-        let encoding = PayloadEncoding::for_response(&res);
-
-        let mut output = vec![];
-        {
-            let mut writer = encoding.to_plaintext(&mut output);
-            while let Some(chunk) = res.chunk().await? {
+        let mut attempt = 0;
+        loop {
+            let mut res = self.fetch_core(&url, &mut attempt).await?;
+
+            let encoding = PayloadEncoding::for_response(&res);
+
+            let mut output = vec![];
+            let result = async {
+                let mut writer = encoding.to_plaintext(&mut output);
+                while let Some(chunk) = res.chunk().await? {
+                    writer
+                        .write_all(&chunk[..])
+                        .map_err(|error| DownloadError::InvalidEncoding { encoding, error })?;
+                }
                 writer
-                    .write_all(&chunk[..])
-                    .map_err(|error| DownloadError::InvalidEncoding { encoding, error })?;
+                    .flush()
+                    .map_err(|error| DownloadError::InvalidEncoding { encoding, error })
+            }
+            .await;
+            drop(res);
+            // Retry the whole download if the connection was dropped while
+            // reading the response.
+            let (read_url, error) = match result {
+                Ok(()) => return Ok(output),
+                Err(DownloadError::FailedToReadDownload { url, error }) => (url, error),
+                Err(error) => return Err(error),
+            };
+            if !self.wait_to_retry(&url, &mut attempt, &error).await {
+                return Err(DownloadError::FailedToReadDownload {
+                    url: read_url,
+                    error,
+                });
             }
-            writer
-                .flush()
-                .map_err(|error| DownloadError::InvalidEncoding { encoding, error })?;
         }
-        Ok(output)
     }
 
     /// Download a file into memory as a SourceFile, with in-memory caching
@@ -258,11 +468,20 @@ impl Network {
 
     /// Internal core implementation of network fetching which is shared between
     /// `download` and `download_and_persist`.
-    async fn fetch_core(&self, url: Url) -> Result<Response<'_>, DownloadError> {
+    ///
+    /// Requests which fail with a spurious error are retried, counting the
+    /// tries in `attempt`. Callers share the count with retries of errors
+    /// reading the response body, so that the whole download is retried at
+    /// most `retries` times.
+    async fn fetch_core(
+        &self,
+        url: &Url,
+        attempt: &mut u32,
+    ) -> Result<Response<'_>, DownloadError> {
         #[cfg(test)]
         if let Some(mock_network) = &self.mock_network {
             let chunk = mock_network
-                .get(&url)
+                .get(url)
                 .cloned()
                 // The error is complete nonsense, but this is test-only.
                 .ok_or_else(|| {
@@ -277,34 +496,123 @@ impl Network {
             return Ok(Response::Mock(Some(chunk)));
         }
 
-        let permit = self
-            .connection_semaphore
-            .acquire()
-            .await
-            .expect("Semaphore dropped?!");
+        loop {
+            let permit = self
+                .connection_semaphore
+                .acquire()
+                .await
+                .expect("Semaphore dropped?!");
+
+            let error = match self
+                .client
+                .get(url.clone())
+                .send()
+                .await
+                .and_then(|res| res.error_for_status())
+            {
+                Ok(res) => return Ok(Response::Real(res, permit)),
+                Err(error) => error,
+            };
+
+            // Don't hold onto the connection while waiting to retry.
+            drop(permit);
+            if !self.wait_to_retry(url, attempt, &error).await {
+                return Err(DownloadError::FailedToStartDownload {
+                    url: Box::new(url.clone()),
+                    error,
+                });
+            }
+        }
+    }
 
-        let res = self
-            .client
-            .get(url.clone())
-            .send()
-            .await
-            .and_then(|res| res.error_for_status())
-            .map_err(|error| DownloadError::FailedToStartDownload {
-                url: Box::new(url.clone()),
-                error,
-            })?;
+    /// If `error` is spurious and there are retries remaining, wait before the
+    /// next try and return `true`.
+    async fn wait_to_retry(&self, url: &Url, attempt: &mut u32, error: &reqwest::Error) -> bool {
+        if *attempt >= self.retries || !is_spurious_error(error) {
+            return false;
+        }
+        let delay = retry_delay(*attempt);
+        *attempt += 1;
+        warn!(
+            "spurious network error fetching {url} ({} tries remaining): {error}",
+            self.retries - *attempt + 1
+        );
+        tokio::time::sleep(delay).await;
+        true
+    }
+}
 
-        Ok(Response::Real(res, permit))
+/// Parse the URL of a sparse index, with or without the `sparse+` prefix used
+/// by cargo, making sure it ends with a `/` so that paths can be joined to it.
+pub fn parse_sparse_index(index: &str) -> Result<Url, String> {
+    let index = index.strip_prefix("sparse+").unwrap_or(index);
+    Url::parse(&format!("{}/", index.trim_end_matches('/'))).map_err(|error| error.to_string())
+}
+
+/// Find the index crates.io has been replaced with by cargo's source
+/// replacement (`[source.crates-io] replace-with = "..."`), if it's a sparse
+/// registry.
+fn crates_io_source_replacement() -> Option<Url> {
+    match crate::storage::crates_io_replacement()? {
+        CratesIoReplacement::Registry { index, .. } if index.starts_with("sparse+") => {
+            match parse_sparse_index(&index) {
+                Ok(index) => Some(index),
+                Err(error) => {
+                    warn!("ignoring invalid replacement for crates.io `{index}`: {error}");
+                    None
+                }
+            }
+        }
+        // Vendored sources are only used for the crates they contain, crates.io
+        // itself is still used for everything else.
+        CratesIoReplacement::Directory(_) => None,
+        CratesIoReplacement::Registry { name, .. } | CratesIoReplacement::Other { name } => {
+            warn!("crates.io is replaced with source `{name}`, but only sparse registries can be used as mirrors by cargo-vet");
+            None
+        }
     }
 }
 
+/// How long to wait before retrying a request for the `attempt`th time (from
+/// 0). The delay grows exponentially up to `RETRY_MAX_DELAY`, with a random
+/// jitter of up to half of the delay so that concurrent requests which failed
+/// together don't all retry at the same moment.
+pub(crate) fn retry_delay(attempt: u32) -> Duration {
+    let delay = RETRY_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(RETRY_MAX_DELAY);
+    // A randomly-seeded hasher is a cheap source of randomness without pulling
+    // in an RNG.
+    let random = std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish();
+    let jitter = (delay / 2).mul_f64(random as f64 / u64::MAX as f64);
+    delay - jitter
+}
+
+/// Whether a failed request is worth retrying, following what cargo considers
+/// to be spurious network errors.
+pub(crate) fn is_spurious_error(error: &reqwest::Error) -> bool {
+    // Body errors are the connection being dropped part way through reading
+    // the response.
+    if error.is_timeout() || error.is_connect() || error.is_body() {
+        return true;
+    }
+    error.status().is_some_and(|status| {
+        status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+    })
+}
+
 #[cfg(test)]
 impl Network {
     /// Create a new Network which is serving mocked out resources.
     pub(crate) fn new_mock() -> Self {
         let mut network = Network {
             client: Client::new(),
-            connection_semaphore: tokio::sync::Semaphore::new(MAX_CONCURRENT_CONNECTIONS),
+            connection_semaphore: tokio::sync::Semaphore::new(DEFAULT_MAX_CONNECTIONS as usize),
+            retries: 0,
+            crates_io_settings: Default::default(),
+            crates_io: Default::default(),
             source_file_cache: Default::default(),
             #[cfg(test)]
             mock_network: Some(Default::default()),
@@ -317,6 +625,28 @@ impl Network {
         network
     }
 
+    /// Create a new Network which makes real requests, such as to a server
+    /// running within the test, retrying spurious errors `retries` times.
+    pub(crate) fn new_local(retries: u32) -> Self {
+        Network {
+            client: Client::builder()
+                .timeout(Duration::from_secs(1))
+                .build()
+                .unwrap(),
+            connection_semaphore: tokio::sync::Semaphore::new(DEFAULT_MAX_CONNECTIONS as usize),
+            retries,
+            crates_io_settings: Default::default(),
+            crates_io: Default::default(),
+            source_file_cache: Default::default(),
+            mock_network: None,
+        }
+    }
+
+    /// Use the sparse index at the given URL as a mirror of crates.io.
+    pub(crate) fn mock_crates_io_mirror(&mut self, index: &str) {
+        self.crates_io_settings.index = Some(parse_sparse_index(index).unwrap());
+    }
+
     /// Add a new resource to be served by a mocked-out network.
     pub(crate) fn mock_serve(&mut self, url: impl AsRef<str>, data: impl AsRef<[u8]>) {
         self.mock_network
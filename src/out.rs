@@ -8,7 +8,7 @@ use crate::git_tool::Editor;
 use console::{Style, Term};
 use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
 use lazy_static::lazy_static;
-use std::{borrow::Cow, fmt, fs::File, io, mem, time::Duration};
+use std::{borrow::Cow, fmt, fs::File, io, mem, sync::Mutex, time::Duration};
 
 /// Object-safe extension of `std::io::Write` with extra features for
 /// interacting with the terminal. Can be mocked in tests to allow them to test
@@ -102,6 +102,14 @@ impl Out for File {
     }
 }
 
+// In-memory output, e.g. for capturing reports when used as a library.
+impl Out for Mutex<Vec<u8>> {
+    fn write(&self, buf: &[u8]) -> io::Result<usize> {
+        self.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+}
+
 impl io::Write for &'_ dyn Out {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         Out::write(*self, buf)
@@ -173,12 +181,12 @@ impl Drop for IncProgressOnDrop<'_> {
 /// tearing.
 ///
 /// This is used for tracing logs when no log file is specified.
-pub struct StderrLogWriter {
+pub(crate) struct StderrLogWriter {
     buffer: Vec<u8>,
 }
 
 impl StderrLogWriter {
-    pub fn new() -> Self {
+    pub(crate) fn new() -> Self {
         StderrLogWriter { buffer: Vec::new() }
     }
 }
@@ -0,0 +1,203 @@
+//! Searching every known audit for a package, as printed by `cargo vet query`.
+//!
+//! Unlike the resolver, this doesn't need the package to be in the dependency
+//! graph. Entries are collected from the local audits.toml, the imports cached
+//! in imports.lock, and any sets of audits in the registry which aren't already
+//! imported, so that existing reviews can be found before adopting a crate.
+
+use std::io;
+use std::sync::Arc;
+
+use crate::format::{
+    AuditEntry, AuditKind, AuditsFile, ImportName, JsonQuery, JsonQueryEntry, JsonQuerySource,
+    PackageStr, RegistryEntry, VetVersion,
+};
+use crate::out::Out;
+use crate::storage::Store;
+use crate::store_diff::{describe_audit, describe_trusted, describe_wildcard_audit};
+
+/// Collect the entries for `package` from the store, and from the audits
+/// fetched from the registry if they're available.
+///
+/// Registry entries with the same name as a configured import are skipped, as
+/// their audits are already listed under the import. This is only the case if
+/// the imports were fetched, as imports.lock only contains the audits which are
+/// used, such as when running with `--locked`.
+pub fn query(
+    store: &Store,
+    registry: Option<&[(ImportName, RegistryEntry, AuditsFile)]>,
+    package: PackageStr<'_>,
+    version: Option<&VetVersion>,
+) -> JsonQuery {
+    let mut result = JsonQuery {
+        name: package.to_owned(),
+        version: version.cloned(),
+        audits: Vec::new(),
+        violations: Vec::new(),
+        wildcard_audits: Vec::new(),
+        trusted: Vec::new(),
+        searched_registry: registry.is_some(),
+    };
+
+    add_entries(
+        &mut result,
+        JsonQuerySource::Local,
+        None,
+        &store.audits,
+        package,
+        version,
+    );
+    for (import_name, audits_file) in store.imported_audits() {
+        add_entries(
+            &mut result,
+            JsonQuerySource::Import,
+            Some(import_name),
+            audits_file,
+            package,
+            version,
+        );
+    }
+    for (import_name, _, audits_file) in registry.into_iter().flatten() {
+        if store.live_imports.is_some() && store.config.imports.contains_key(import_name) {
+            continue;
+        }
+        add_entries(
+            &mut result,
+            JsonQuerySource::Registry,
+            Some(import_name),
+            audits_file,
+            package,
+            version,
+        );
+    }
+
+    result
+}
+
+/// Whether there were no entries at all for the package.
+pub fn is_empty(result: &JsonQuery) -> bool {
+    result.audits.is_empty()
+        && result.violations.is_empty()
+        && result.wildcard_audits.is_empty()
+        && result.trusted.is_empty()
+}
+
+fn add_entries(
+    result: &mut JsonQuery,
+    source: JsonQuerySource,
+    import: Option<&ImportName>,
+    audits_file: &AuditsFile,
+    package: PackageStr<'_>,
+    version: Option<&VetVersion>,
+) {
+    for audit in audits_file.audits.get(package).into_iter().flatten() {
+        if !audit_matches(audit, version) {
+            continue;
+        }
+        if matches!(audit.kind, AuditKind::Violation { .. }) {
+            result.violations.push(tag(source, import, audit));
+        } else {
+            result.audits.push(tag(source, import, audit));
+        }
+    }
+
+    // Wildcard audits and trusted publishers apply to whichever versions were
+    // published by a given user, which we can't know without fetching the
+    // package's publishers, so they're always listed.
+    for wildcard_audit in audits_file
+        .wildcard_audits
+        .get(package)
+        .into_iter()
+        .flatten()
+    {
+        result
+            .wildcard_audits
+            .push(tag(source, import, wildcard_audit));
+    }
+    for trusted in audits_file.trusted.get(package).into_iter().flatten() {
+        result.trusted.push(tag(source, import, trusted));
+    }
+}
+
+fn tag<T: Clone>(
+    source: JsonQuerySource,
+    import: Option<&ImportName>,
+    entry: &T,
+) -> JsonQueryEntry<T> {
+    JsonQueryEntry {
+        source,
+        import: import.cloned(),
+        entry: entry.clone(),
+    }
+}
+
+/// Whether an audit or violation covers `version`, if one was given. Delta
+/// audits are included if they start or end at the version.
+fn audit_matches(audit: &AuditEntry, version: Option<&VetVersion>) -> bool {
+    let Some(version) = version else {
+        return true;
+    };
+    match &audit.kind {
+        AuditKind::Full { version: audited } => audited == version,
+        AuditKind::Delta { from, to } => from == version || to == version,
+        AuditKind::Violation { violation } => violation.matches(version),
+    }
+}
+
+/// Print a human-readable list of the entries found for the package.
+pub fn print_human(out: &Arc<dyn Out>, result: &JsonQuery) -> Result<(), io::Error> {
+    let name = match &result.version {
+        Some(version) => format!("{}:{version}", result.name),
+        None => result.name.clone(),
+    };
+    if is_empty(result) {
+        writeln!(
+            out,
+            "No audits, violations, wildcard audits or trusted publishers found for {name}"
+        );
+        writeln!(out);
+    } else {
+        writeln!(out, "Entries found for {name}");
+        writeln!(out);
+        print_section(out, "audits", &result.audits, describe_audit);
+        print_section(out, "violations", &result.violations, describe_audit);
+        print_section(
+            out,
+            "wildcard audits",
+            &result.wildcard_audits,
+            describe_wildcard_audit,
+        );
+        print_section(out, "trusted publishers", &result.trusted, describe_trusted);
+    }
+
+    if !result.searched_registry {
+        writeln!(
+            out,
+            "NOTE: the registry wasn't searched, as it can't be fetched with --frozen"
+        );
+    }
+    Ok(())
+}
+
+fn print_section<T>(
+    out: &Arc<dyn Out>,
+    title: &str,
+    entries: &[JsonQueryEntry<T>],
+    describe: impl Fn(&T) -> String,
+) {
+    if entries.is_empty() {
+        return;
+    }
+
+    writeln!(out, "{title}:");
+    for entry in entries {
+        let source = match (entry.source, &entry.import) {
+            (JsonQuerySource::Local, _) => "local".to_owned(),
+            (JsonQuerySource::Import, Some(import)) => format!("imported from {import}"),
+            (JsonQuerySource::Registry, Some(import)) => format!("registry entry {import}"),
+            (_, None) => unreachable!("non-local entries always have an import name"),
+        };
+        writeln!(out, "  [{source}] {}", describe(&entry.entry));
+    }
+    writeln!(out);
+}
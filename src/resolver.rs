@@ -40,33 +40,42 @@
 //!   existing set of criteria, to suggest the best audit and criteria which could
 //!   be used to allow the crate to vet successfully.
 
-use cargo_metadata::{DependencyKind, Metadata, Node, PackageId};
+use cargo_metadata::{DependencyKind, Metadata, Node, PackageId, TargetKind};
 use futures_util::future::join_all;
 use miette::IntoDiagnostic;
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::cmp::Reverse;
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, VecDeque};
+use std::path::Path;
 use std::sync::Arc;
 use tracing::{trace, trace_span, warn};
 
 use crate::cli::{DumpGraphArgs, GraphFilter, GraphFilterProperty, GraphFilterQuery, OutputFormat};
 use crate::criteria::{CriteriaMapper, CriteriaSet};
+use crate::describe::{describe_expired_entry, describe_violation_conflict, xml_escape};
 use crate::errors::SuggestError;
 use crate::format::{
-    self, AuditEntry, AuditKind, AuditsFile, CratesPublisher, CratesPublisherSource,
-    CratesSourceId, CriteriaName, Delta, DiffStat, ExemptedDependency, FastMap, FastSet,
-    ImportName, ImportsFile, JsonPackage, JsonReport, JsonReportConclusion, JsonReportFailForVet,
+    self, AuditEntry, AuditKind, AuditsFile, CargoRegistry, ConfigFile, CratesPublisher,
+    CratesPublisherSource, CratesSourceId, CriteriaName, Delta, DependencyEdge, DiffStat,
+    ExemptedDependency, FastMap, FastSet, ImportName, ImportsFile, JsonExpiredEntry,
+    JsonExpiredKind, JsonPackage, JsonReport, JsonReportConclusion, JsonReportFailForVet,
     JsonReportFailForViolationConflict, JsonReportSuccess, JsonSuggest, JsonSuggestItem,
-    JsonVetFailure, PackageName, PackageStr, Policy, UnpublishedEntry, VetVersion, WildcardEntry,
+    JsonVetFailure, PackageName, PackageStr, Policy, PolicyEntry, RegistryName, RegistryStr,
+    RequirementOrigin, SarifArtifactLocation, SarifDriver, SarifLevel, SarifLocation, SarifLog,
+    SarifMessage, SarifPhysicalLocation, SarifRegion, SarifResult, SarifRule, SarifRun, SarifTool,
+    UnpublishedEntry, VetVersion, WildcardEntry,
 };
 use crate::format::{SortedMap, SortedSet};
 use crate::network::Network;
 use crate::out::{progress_bar, IncProgressOnDrop, Out};
+use crate::serialization::spanned::Spanned;
 use crate::storage::Cache;
 use crate::string_format::FormatShortList;
 use crate::{Config, PackageExt, Store};
 
+#[non_exhaustive]
 pub struct ResolveReport<'a> {
     /// The Cargo dependency graph as parsed and understood by cargo-vet.
     ///
@@ -81,11 +90,16 @@ pub struct ResolveReport<'a> {
     /// crates or crates with violation conflicts.
     pub results: Vec<Option<ResolveResult>>,
 
+    /// The criteria each package is required to satisfy, indexed by
+    /// [`PackageIdx`][].
+    pub requirements: Vec<CriteriaSet>,
+
     /// The final conclusion of our analysis.
     pub conclusion: Conclusion,
 }
 
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Conclusion {
     Success(Success),
     FailForViolationConflict(FailForViolationConflict),
@@ -102,6 +116,61 @@ pub struct Success {
     pub vetted_fully: Vec<PackageIdx>,
 }
 
+impl Success {
+    /// Which bucket a package was sorted into, or `None` if it isn't vetted.
+    pub fn bucket(&self, pkgidx: PackageIdx) -> Option<VettedBucket> {
+        if self.vetted_fully.contains(&pkgidx) {
+            Some(VettedBucket::Fully)
+        } else if self.vetted_partially.contains(&pkgidx) {
+            Some(VettedBucket::Partially)
+        } else if self.vetted_with_exemptions.contains(&pkgidx) {
+            Some(VettedBucket::Exemptions)
+        } else {
+            None
+        }
+    }
+}
+
+/// The buckets of `Success` which vetted packages are sorted into.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum VettedBucket {
+    /// Vetted using only audits.
+    Fully,
+    /// Vetted using both audits and exemptions.
+    Partially,
+    /// Vetted using only exemptions.
+    Exemptions,
+}
+
+impl VettedBucket {
+    /// Sort a package into a bucket based on the paths which vetted each of
+    /// its required criteria.
+    pub fn for_paths<'a>(paths: impl IntoIterator<Item = &'a [DeltaEdgeOrigin]>) -> Self {
+        let mut needed_exemptions = false;
+        let mut directly_exempted = false;
+        for path in paths {
+            needed_exemptions |= path
+                .iter()
+                .any(|o| matches!(o, DeltaEdgeOrigin::Exemption { .. }));
+            // Ignore `Unpublished` entries when deciding if a crate is directly
+            // exempted.
+            directly_exempted |= path.iter().all(|o| {
+                matches!(
+                    o,
+                    DeltaEdgeOrigin::Exemption { .. } | DeltaEdgeOrigin::Unpublished { .. }
+                )
+            });
+        }
+        if !needed_exemptions {
+            VettedBucket::Fully
+        } else if directly_exempted {
+            VettedBucket::Exemptions
+        } else {
+            VettedBucket::Partially
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct FailForViolationConflict {
     pub violations: Vec<(PackageIdx, Vec<ViolationConflict>)>,
@@ -111,7 +180,12 @@ pub struct FailForViolationConflict {
 pub struct FailForVet {
     /// These packages are to blame and need to be fixed
     pub failures: Vec<(PackageIdx, AuditFailure)>,
+    /// Failures which were already present in the base revision passed to
+    /// `check --base`. These are reported, but don't fail the check.
+    pub preexisting: Vec<(PackageIdx, AuditFailure)>,
     pub suggest: Option<Suggest>,
+    /// The remaining third-party packages, which were vetted successfully
+    pub vetted: Success,
 }
 
 // FIXME: This format is pretty janky and unstable, so we probably should come
@@ -130,9 +204,16 @@ pub enum ViolationConflict {
         audit_source: Option<ImportName>,
         audit: AuditEntry,
     },
+    /// A violation synthesized from a security advisory which affects a locked
+    /// version, but doesn't conflict with any specific audit or exemption.
+    AdvisoryConflict {
+        violation_source: Option<ImportName>,
+        violation: AuditEntry,
+    },
 }
 
 #[derive(Debug, Default)]
+#[non_exhaustive]
 pub struct Suggest {
     pub suggestions: Vec<SuggestItem>,
     pub suggestions_by_criteria: SortedMap<CriteriaName, Vec<SuggestItem>>,
@@ -166,6 +247,24 @@ pub struct DiffRecommendation {
     pub diffstat: DiffStat,
 }
 
+impl DiffRecommendation {
+    /// The command to run to review this diff.
+    pub fn command(&self, package: PackageStr<'_>) -> String {
+        match &self.from {
+            Some(from) => format!("cargo vet diff {package} {from} {}", self.to),
+            None => format!("cargo vet inspect {package} {}", self.to),
+        }
+    }
+
+    /// A short description of the size of this diff.
+    pub fn size(&self) -> String {
+        match &self.from {
+            Some(_) => format!("{}", self.diffstat),
+            None => format!("{} lines", self.diffstat.count()),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RegistrySuggestion {
     pub name: ImportName,
@@ -206,12 +305,18 @@ pub struct PackageNode<'a> {
     pub reverse_deps: SortedSet<PackageIdx>,
     /// Whether this package is a workspace member (can have dev-deps)
     pub is_workspace_member: bool,
-    /// Whether this package is third-party (from crates.io)
+    /// Whether this package is third-party (from crates.io or an alternative
+    /// registry)
     pub is_third_party: bool,
+    /// The alternative registry this package is from, if it isn't from crates.io
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registry: Option<RegistryName>,
     /// Whether this package is a root in the "normal" build graph
     pub is_root: bool,
     /// Whether this package only shows up in dev (test/bench) builds
     pub is_dev_only: bool,
+    /// Whether this package is a proc-macro (only run at build time)
+    pub is_proc_macro: bool,
 }
 
 /// The dependency graph in a form we can use more easily.
@@ -232,6 +337,33 @@ pub struct ResolveResult {
 #[derive(Debug, Clone)]
 pub struct AuditFailure {
     pub criteria_failures: CriteriaSet,
+    /// Expired entries which were ignored, but which could otherwise have
+    /// provided some of the failing criteria.
+    pub expired: Vec<ExpiredEntry>,
+    /// If the failing criteria would have been satisfied if not for the
+    /// `min-auditors` policy, the number of additional independent auditors
+    /// which are needed.
+    pub missing_auditors: Option<usize>,
+}
+
+/// An audit or exemption which was left out of the audit graph because its
+/// `expires` date has passed.
+#[derive(Debug, Clone)]
+pub struct ExpiredEntry {
+    /// Where the entry would have come from if it hadn't expired.
+    pub origin: DeltaEdgeOrigin,
+    /// The import which the entry came from, if it isn't local.
+    pub source: Option<ImportName>,
+    pub from: Option<VetVersion>,
+    pub to: VetVersion,
+    pub criteria: CriteriaSet,
+    pub expires: chrono::NaiveDate,
+}
+
+impl ExpiredEntry {
+    pub fn is_exemption(&self) -> bool {
+        matches!(self.origin, DeltaEdgeOrigin::Exemption { .. })
+    }
 }
 
 /// Value indicating a failure to find a path in the audit graph between two nodes.
@@ -274,6 +406,8 @@ type DirectedAuditGraph<'a> = SortedMap<Option<&'a VetVersion>, Vec<DeltaEdge<'a
 pub struct AuditGraph<'a> {
     forward_audits: DirectedAuditGraph<'a>,
     backward_audits: DirectedAuditGraph<'a>,
+    /// Entries which were not added to the graph because they have expired.
+    pub expired: Vec<ExpiredEntry>,
 }
 
 /// The precise origin of an edge in the audit graph.
@@ -382,6 +516,12 @@ struct DeltaEdge<'a> {
     /// Whether or not the edge is a "fresh import", and should be
     /// de-prioritized to avoid unnecessary imports.lock updates.
     freshness: DeltaEdgeFreshness,
+    /// The auditors who reviewed this edge, used to enforce `min-auditors`.
+    /// An edge counts as at most one independent auditor however many people
+    /// co-signed it. `None` if the edge doesn't represent a review (e.g. it's
+    /// an exemption or trusted entry), in which case `min-auditors` doesn't
+    /// apply to it.
+    auditors: Option<Vec<&'a str>>,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -400,10 +540,10 @@ impl<'a> DepGraph<'a> {
     pub fn new(
         metadata: &'a Metadata,
         filter_graph: Option<&Vec<GraphFilter>>,
-        policy: Option<&Policy>,
+        config: Option<&ConfigFile>,
     ) -> Self {
-        let default_policy = Policy::default();
-        let policy = policy.unwrap_or(&default_policy);
+        let default_config = ConfigFile::default();
+        let config = config.unwrap_or(&default_config);
         let package_list = &*metadata.packages;
         let resolve_list = &*metadata
             .resolve
@@ -433,7 +573,12 @@ impl<'a> DepGraph<'a> {
                 package_id: &resolve_node.id,
                 name: &package.name,
                 version: package.vet_version(),
-                is_third_party: package.is_third_party(policy),
+                is_third_party: package.is_third_party(config),
+                registry: package.registry(config).map(|r| r.name.to_owned()),
+                is_proc_macro: package
+                    .targets
+                    .iter()
+                    .any(|target| target.kind.contains(&TargetKind::ProcMacro)),
                 // These will get (re)computed later
                 normal_deps: vec![],
                 build_deps: vec![],
@@ -712,8 +857,10 @@ impl<'a> DepGraph<'a> {
                 reverse_deps: SortedSet::new(),
                 is_workspace_member: package.is_workspace_member,
                 is_third_party: package.is_third_party,
+                registry: package.registry.clone(),
                 is_root: package.is_root,
                 is_dev_only: package.is_dev_only,
+                is_proc_macro: package.is_proc_macro,
             });
             interner_by_pkgid.insert(package.package_id, new_idx);
         }
@@ -865,10 +1012,11 @@ pub fn resolve<'a>(
     metadata: &'a Metadata,
     filter_graph: Option<&Vec<GraphFilter>>,
     store: &Store,
+    today: chrono::NaiveDate,
 ) -> ResolveReport<'a> {
     // A large part of our algorithm is unioning and intersecting criteria, so we map all
     // the criteria into indexed boolean sets (*whispers* an integer with lots of bits).
-    let graph = DepGraph::new(metadata, filter_graph, Some(&store.config.policy));
+    let graph = DepGraph::new(metadata, filter_graph, Some(&store.config));
     // trace!("built DepGraph: {:#?}", graph);
     trace!("built DepGraph!");
 
@@ -877,24 +1025,69 @@ pub fn resolve<'a>(
 
     let requirements = resolve_requirements(&graph, &store.config.policy, &criteria_mapper);
 
-    let (results, conclusion) = resolve_audits(&graph, store, &criteria_mapper, &requirements);
+    let (results, conclusion) =
+        resolve_audits(&graph, store, &criteria_mapper, &requirements, today);
 
     ResolveReport {
         graph,
         criteria_mapper,
         results,
+        requirements,
         conclusion,
     }
 }
 
-fn resolve_requirements(
+pub fn resolve_requirements(
     graph: &DepGraph<'_>,
     policy: &Policy,
     criteria_mapper: &CriteriaMapper,
 ) -> Vec<CriteriaSet> {
+    propagate_requirements(graph, policy, criteria_mapper).normal
+}
+
+/// The criteria required of each package, along with the state used to
+/// propagate requirements to build-time dependencies.
+struct Requirements {
+    /// The criteria each package is required to satisfy.
+    normal: Vec<CriteriaSet>,
+    /// The build criteria each package passes on to all of its dependencies,
+    /// either from its own `build-criteria` policy or inherited from its
+    /// reverse-dependencies.
+    inherited_build: Vec<CriteriaSet>,
+    /// Whether each package's build-time dependencies also require the
+    /// package's own requirements.
+    build_inherits_normal: Vec<bool>,
+}
+
+impl Requirements {
+    /// The criteria required of the build-time dependencies of a package.
+    fn build(&self, pkgidx: PackageIdx) -> CriteriaSet {
+        let mut build_criteria = self.inherited_build[pkgidx].clone();
+        if self.build_inherits_normal[pkgidx] {
+            build_criteria.unioned_with(&self.normal[pkgidx]);
+        }
+        build_criteria
+    }
+}
+
+fn propagate_requirements(
+    graph: &DepGraph<'_>,
+    policy: &Policy,
+    criteria_mapper: &CriteriaMapper,
+) -> Requirements {
     let _resolve_requirements = trace_span!("resolve_requirements").entered();
 
     let mut requirements = vec![criteria_mapper.no_criteria(); graph.nodes.len()];
+    let mut all_inherited_build = vec![criteria_mapper.no_criteria(); graph.nodes.len()];
+    let mut all_inherits_normal = vec![false; graph.nodes.len()];
+
+    // Criteria required of build-time dependencies (build-dependencies and
+    // proc-macros), inherited from reverse-dependencies with a `build-criteria`
+    // policy. If `build_inherits_normal` is set, build-time dependencies also
+    // require the package's own requirements, which is the default when no
+    // `build-criteria` is in effect.
+    let mut build_requirements = vec![criteria_mapper.no_criteria(); graph.nodes.len()];
+    let mut build_inherits_normal = vec![false; graph.nodes.len()];
 
     // For any packages which have dev-dependencies, apply policy-specified
     // dependency-criteria or dev-criteria to those dependencies.
@@ -917,6 +1110,7 @@ fn resolve_requirements(
                 .map(|criteria| criteria_mapper.criteria_from_list(criteria));
             requirements[depidx]
                 .unioned_with(dependency_criteria.as_ref().unwrap_or(&dev_criteria));
+            build_inherits_normal[depidx] = true;
         }
     }
 
@@ -939,18 +1133,268 @@ fn resolve_requirements(
         }
         let normal_criteria = requirements[pkgidx].clone();
 
+        // If we specify a build-criteria policy on ourselves, it replaces any
+        // build requirements inherited from reverse-dependencies.
+        let own_build_criteria = policy
+            .and_then(|p| p.build_criteria.as_ref())
+            .map(|c| criteria_mapper.criteria_from_list(c));
+        let inherits_normal =
+            own_build_criteria.is_none() && (package.is_root || build_inherits_normal[pkgidx]);
+        let inherited_build_criteria =
+            own_build_criteria.unwrap_or_else(|| build_requirements[pkgidx].clone());
+        let mut build_criteria = inherited_build_criteria.clone();
+        if inherits_normal {
+            build_criteria.unioned_with(&normal_criteria);
+        }
+
         // For each dependency, elaborate the dependency criteria from the configured policy and add it to the dependency requirements.
         for &depidx in &package.normal_and_build_deps {
             let dep_package = &graph.nodes[depidx];
             let dependency_criteria = policy
                 .and_then(|policy| policy.dependency_criteria.get(dep_package.name))
                 .map(|criteria| criteria_mapper.criteria_from_list(criteria));
-            requirements[depidx]
-                .unioned_with(dependency_criteria.as_ref().unwrap_or(&normal_criteria));
+            if let Some(dependency_criteria) = &dependency_criteria {
+                requirements[depidx].unioned_with(dependency_criteria);
+            } else {
+                // proc-macros are only run at build time, so are treated like
+                // build-dependencies even when used as normal dependencies.
+                if package.build_deps.contains(&depidx) || dep_package.is_proc_macro {
+                    requirements[depidx].unioned_with(&build_criteria);
+                }
+                if package.normal_deps.contains(&depidx) && !dep_package.is_proc_macro {
+                    requirements[depidx].unioned_with(&normal_criteria);
+                }
+            }
+            build_requirements[depidx].unioned_with(&inherited_build_criteria);
+            build_inherits_normal[depidx] |= inherits_normal;
+        }
+        all_inherited_build[pkgidx] = inherited_build_criteria;
+        all_inherits_normal[pkgidx] = inherits_normal;
+    }
+
+    Requirements {
+        normal: requirements,
+        inherited_build: all_inherited_build,
+        build_inherits_normal: all_inherits_normal,
+    }
+}
+
+/// Why a package is required to satisfy some criteria.
+#[derive(Debug, Clone)]
+pub struct RequirementExplanation {
+    /// The policy entry (or default) which introduced the requirement.
+    pub origin: RequirementOrigin,
+    /// The dependency path from the package which introduced the requirement
+    /// to the package itself, along with how each package is depended upon by
+    /// the previous one.
+    pub path: Vec<(PackageIdx, Option<DependencyEdge>)>,
+}
+
+/// Explain why the given package is required to satisfy the given criteria,
+/// by retracing the steps of `resolve_requirements` back to the policy entry
+/// which introduced the requirement. If the requirement was introduced in
+/// multiple places, the shortest dependency path is returned.
+///
+/// Returns `None` if the package doesn't require the criteria.
+pub fn explain_requirement(
+    graph: &DepGraph<'_>,
+    policy: &Policy,
+    criteria_mapper: &CriteriaMapper,
+    pkgidx: PackageIdx,
+    criteria_idx: usize,
+) -> Option<RequirementExplanation> {
+    /// A fact about the requirements which needs to be explained, in terms of
+    /// the intermediate state of `resolve_requirements`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Fact {
+        /// The package requires the criteria.
+        Normal(PackageIdx),
+        /// The package's build-time dependencies require the criteria.
+        Build(PackageIdx),
+        /// The package passes the criteria on as a build requirement to all
+        /// of its dependencies.
+        InheritedBuild(PackageIdx),
+    }
+    impl Fact {
+        fn package(self) -> PackageIdx {
+            match self {
+                Fact::Normal(idx) | Fact::Build(idx) | Fact::InheritedBuild(idx) => idx,
+            }
+        }
+    }
+
+    let requirements = propagate_requirements(graph, policy, criteria_mapper);
+    if !requirements.normal[pkgidx].has_criteria(criteria_idx) {
+        return None;
+    }
+
+    let policy_criteria = |idx: PackageIdx, get: fn(&PolicyEntry) -> Option<&Vec<_>>| {
+        let package = &graph.nodes[idx];
+        policy
+            .get(package.name, &package.version)
+            .and_then(get)
+            .map(|c| criteria_mapper.criteria_from_list(c))
+    };
+    let dependency_criteria = |idx: PackageIdx, depidx: PackageIdx| {
+        let package = &graph.nodes[idx];
+        policy
+            .get(package.name, &package.version)
+            .and_then(|p| p.dependency_criteria.get(graph.nodes[depidx].name))
+            .map(|c| criteria_mapper.criteria_from_list(c))
+    };
+    let edge = |idx: PackageIdx, depidx: PackageIdx| {
+        if graph.nodes[idx].normal_deps.contains(&depidx) {
+            DependencyEdge::Normal
+        } else {
+            DependencyEdge::Build
+        }
+    };
+
+    // Breadth-first search backwards from the package, remembering for each
+    // fact the fact it helps explain, until we find where the requirement was
+    // introduced.
+    let mut explains: FastMap<Fact, Option<(Fact, Option<DependencyEdge>)>> = FastMap::new();
+    let mut queue = VecDeque::new();
+    explains.insert(Fact::Normal(pkgidx), None);
+    queue.push_back(Fact::Normal(pkgidx));
+
+    let mut introduced = None;
+    'search: while let Some(fact) = queue.pop_front() {
+        // Either the requirement was introduced by `introducer` (with an
+        // optional dependency edge to this fact's package), or the fact is
+        // explained by another fact.
+        let mut found = |origin, introducer: Option<(PackageIdx, DependencyEdge)>| {
+            introduced = Some((origin, introducer, fact));
+        };
+        let mut visit = |prior: Fact, edge: Option<DependencyEdge>| {
+            if let std::collections::hash_map::Entry::Vacant(entry) = explains.entry(prior) {
+                entry.insert(Some((fact, edge)));
+                queue.push_back(prior);
+            }
+        };
+
+        let idx = fact.package();
+        let package = &graph.nodes[idx];
+        match fact {
+            Fact::Normal(idx) => {
+                if policy_criteria(idx, |p| p.criteria.as_ref()).is_some() {
+                    found(RequirementOrigin::Criteria, None);
+                    break 'search;
+                }
+                if package.is_root
+                    && criteria_mapper
+                        .criteria_from_list([format::DEFAULT_POLICY_CRITERIA])
+                        .has_criteria(criteria_idx)
+                {
+                    found(RequirementOrigin::Root, None);
+                    break 'search;
+                }
+                for &parent in &package.reverse_deps {
+                    let parent_package = &graph.nodes[parent];
+                    let dependency_criteria = dependency_criteria(parent, idx);
+                    if parent_package.dev_deps.contains(&idx) {
+                        let origin = match &dependency_criteria {
+                            Some(c) => c
+                                .has_criteria(criteria_idx)
+                                .then_some(RequirementOrigin::DependencyCriteria),
+                            None => match policy_criteria(parent, |p| p.dev_criteria.as_ref()) {
+                                Some(c) => c
+                                    .has_criteria(criteria_idx)
+                                    .then_some(RequirementOrigin::DevCriteria),
+                                None => criteria_mapper
+                                    .criteria_from_list([format::DEFAULT_POLICY_DEV_CRITERIA])
+                                    .has_criteria(criteria_idx)
+                                    .then_some(RequirementOrigin::DefaultDevCriteria),
+                            },
+                        };
+                        if let Some(origin) = origin {
+                            found(origin, Some((parent, DependencyEdge::Dev)));
+                            break 'search;
+                        }
+                    }
+                    if !parent_package.normal_and_build_deps.contains(&idx) {
+                        continue;
+                    }
+                    if let Some(c) = &dependency_criteria {
+                        if c.has_criteria(criteria_idx) {
+                            found(
+                                RequirementOrigin::DependencyCriteria,
+                                Some((parent, edge(parent, idx))),
+                            );
+                            break 'search;
+                        }
+                        continue;
+                    }
+                    if (parent_package.build_deps.contains(&idx) || package.is_proc_macro)
+                        && requirements.build(parent).has_criteria(criteria_idx)
+                    {
+                        let edge = if parent_package.build_deps.contains(&idx) {
+                            DependencyEdge::Build
+                        } else {
+                            DependencyEdge::Normal
+                        };
+                        visit(Fact::Build(parent), Some(edge));
+                    }
+                    if parent_package.normal_deps.contains(&idx)
+                        && !package.is_proc_macro
+                        && requirements.normal[parent].has_criteria(criteria_idx)
+                    {
+                        visit(Fact::Normal(parent), Some(DependencyEdge::Normal));
+                    }
+                }
+            }
+            Fact::Build(idx) => {
+                if policy_criteria(idx, |p| p.build_criteria.as_ref()).is_some() {
+                    found(RequirementOrigin::BuildCriteria, None);
+                    break 'search;
+                }
+                if requirements.inherited_build[idx].has_criteria(criteria_idx) {
+                    visit(Fact::InheritedBuild(idx), None);
+                }
+                if requirements.build_inherits_normal[idx]
+                    && requirements.normal[idx].has_criteria(criteria_idx)
+                {
+                    visit(Fact::Normal(idx), None);
+                }
+            }
+            Fact::InheritedBuild(idx) => {
+                for &parent in &package.reverse_deps {
+                    if !graph.nodes[parent].normal_and_build_deps.contains(&idx)
+                        || !requirements.inherited_build[parent].has_criteria(criteria_idx)
+                    {
+                        continue;
+                    }
+                    if policy_criteria(parent, |p| p.build_criteria.as_ref()).is_some() {
+                        found(
+                            RequirementOrigin::BuildCriteria,
+                            Some((parent, edge(parent, idx))),
+                        );
+                        break 'search;
+                    }
+                    visit(Fact::InheritedBuild(parent), Some(edge(parent, idx)));
+                }
+            }
         }
     }
 
-    requirements
+    // Walk forwards from where the requirement was introduced to build up the
+    // dependency path.
+    let (origin, introducer, mut fact) = introduced?;
+    let mut path = Vec::new();
+    match introducer {
+        Some((introducer, edge)) => {
+            path.push((introducer, None));
+            path.push((fact.package(), Some(edge)));
+        }
+        None => path.push((fact.package(), None)),
+    }
+    while let Some(&(next, edge)) = explains[&fact].as_ref() {
+        if let Some(edge) = edge {
+            path.push((next.package(), Some(edge)));
+        }
+        fact = next;
+    }
+    Some(RequirementExplanation { origin, path })
 }
 
 fn resolve_audits(
@@ -958,6 +1402,7 @@ fn resolve_audits(
     store: &Store,
     criteria_mapper: &CriteriaMapper,
     requirements: &[CriteriaSet],
+    today: chrono::NaiveDate,
 ) -> (Vec<Option<ResolveResult>>, Conclusion) {
     let _resolve_audits = trace_span!("resolve_audits").entered();
     let mut violations = Vec::new();
@@ -974,9 +1419,28 @@ fn resolve_audits(
                 return None; // first-party crates don't need audits
             }
 
-            let audit_graph = AuditGraph::build(store, criteria_mapper, package.name, None)
-                .map_err(|v| violations.push((pkgidx, v)))
-                .ok()?;
+            let audit_graph = AuditGraph::build(
+                store,
+                criteria_mapper,
+                package.name,
+                package.registry.as_deref(),
+                None,
+                today,
+            )
+            .map_err(|v| violations.push((pkgidx, v)))
+            .ok()?;
+
+            // If the policy requires multiple independent auditors, search a
+            // restricted graph which only contains sufficiently-reviewed edges.
+            let min_auditors = store
+                .config
+                .policy
+                .get(package.name, &package.version)
+                .and_then(|policy| policy.min_auditors)
+                .unwrap_or(1);
+            let restricted_graph =
+                (min_auditors > 1).then(|| audit_graph.with_min_auditors(min_auditors));
+            let search_graph = restricted_graph.as_ref().unwrap_or(&audit_graph);
 
             // NOTE: We currently always compute all search results even if we
             // only need those in `req_criteria` because some later passes using
@@ -984,45 +1448,71 @@ fn resolve_audits(
             // to look into simplifying this in the future.
             let search_results: Vec<_> = (0..criteria_mapper.len())
                 .map(|criteria_idx| {
-                    audit_graph.search(criteria_idx, &package.version, SearchMode::PreferExemptions)
+                    search_graph.search(
+                        criteria_idx,
+                        &package.version,
+                        SearchMode::PreferExemptions,
+                    )
                 })
                 .collect();
 
-            let mut needed_exemptions = false;
-            let mut directly_exempted = false;
             let mut criteria_failures = criteria_mapper.no_criteria();
             for criteria_idx in required_criteria.indices() {
-                match &search_results[criteria_idx] {
-                    Ok(path) => {
-                        needed_exemptions |= path
-                            .iter()
-                            .any(|o| matches!(o, DeltaEdgeOrigin::Exemption { .. }));
-                        // Ignore `Unpublished` entries when deciding if a crate
-                        // is directly exempted.
-                        directly_exempted |= path.iter().all(|o| {
-                            matches!(
-                                o,
-                                DeltaEdgeOrigin::Exemption { .. }
-                                    | DeltaEdgeOrigin::Unpublished { .. }
-                            )
-                        });
-                    }
-                    Err(_) => criteria_failures.set_criteria(criteria_idx),
+                if search_results[criteria_idx].is_err() {
+                    criteria_failures.set_criteria(criteria_idx);
                 }
             }
 
             if !criteria_failures.is_empty() {
-                failures.push((pkgidx, AuditFailure { criteria_failures }));
+                // Report any expired entries which could have contributed to
+                // the failing criteria, so that the user knows to renew them.
+                let expired = audit_graph
+                    .expired
+                    .iter()
+                    .filter(|expired| {
+                        let mut criteria = expired.criteria.clone();
+                        criteria.intersected_with(&criteria_failures);
+                        !criteria.is_empty()
+                    })
+                    .cloned()
+                    .collect();
+                // If the criteria would have been satisfied without
+                // `min-auditors`, report how many more auditors are needed.
+                let missing_auditors = restricted_graph.as_ref().and_then(|_| {
+                    criteria_failures
+                        .indices()
+                        .filter_map(|criteria_idx| {
+                            let path = audit_graph
+                                .search(
+                                    criteria_idx,
+                                    &package.version,
+                                    SearchMode::PreferExemptions,
+                                )
+                                .ok()?;
+                            Some(audit_graph.missing_auditors(&path, criteria_idx, min_auditors))
+                        })
+                        .max()
+                        .filter(|&missing| missing > 0)
+                });
+                failures.push((
+                    pkgidx,
+                    AuditFailure {
+                        criteria_failures,
+                        expired,
+                        missing_auditors,
+                    },
+                ));
             }
 
             // XXX: Callers using these fields in success should perhaps be
             // changed to instead walk the results?
-            if !needed_exemptions {
-                vetted_fully.push(pkgidx);
-            } else if directly_exempted {
-                vetted_with_exemptions.push(pkgidx);
-            } else {
-                vetted_partially.push(pkgidx);
+            let paths = required_criteria
+                .indices()
+                .filter_map(|criteria_idx| search_results[criteria_idx].as_deref().ok());
+            match VettedBucket::for_paths(paths) {
+                VettedBucket::Fully => vetted_fully.push(pkgidx),
+                VettedBucket::Partially => vetted_partially.push(pkgidx),
+                VettedBucket::Exemptions => vetted_with_exemptions.push(pkgidx),
             }
 
             Some(ResolveResult { search_results })
@@ -1032,8 +1522,21 @@ fn resolve_audits(
     let conclusion = if !violations.is_empty() {
         Conclusion::FailForViolationConflict(FailForViolationConflict { violations })
     } else if !failures.is_empty() {
+        let failed: FastSet<PackageIdx> = failures.iter().map(|(pkgidx, _)| *pkgidx).collect();
+        let vetted = |packages: Vec<PackageIdx>| {
+            packages
+                .into_iter()
+                .filter(|pkgidx| !failed.contains(pkgidx))
+                .collect()
+        };
         Conclusion::FailForVet(FailForVet {
+            vetted: Success {
+                vetted_with_exemptions: vetted(vetted_with_exemptions),
+                vetted_partially: vetted(vetted_partially),
+                vetted_fully: vetted(vetted_fully),
+            },
             failures,
+            preexisting: Vec::new(),
             suggest: None,
         })
     } else {
@@ -1051,11 +1554,16 @@ impl<'a> AuditGraph<'a> {
     /// Given the store, and a package name, builds up an audit graph. This can
     /// then be searched in order to find a specific path which satisfies a
     /// given criteria.
+    ///
+    /// Only audits tagged with `registry` are considered, where `None` is
+    /// crates.io.
     pub fn build(
         store: &'a Store,
         criteria_mapper: &CriteriaMapper,
         package: PackageStr<'_>,
+        registry: Option<RegistryStr<'_>>,
         extra_audits_file: Option<&'a AuditsFile>,
+        today: chrono::NaiveDate,
     ) -> Result<Self, Vec<ViolationConflict>> {
         // Pre-build the namespaces for each audit so that we can take a reference
         // to each one as-needed rather than cloning the name each time.
@@ -1065,6 +1573,20 @@ impl<'a> AuditGraph<'a> {
             .map(|import_name| Some(import_name.clone()))
             .collect();
 
+        // Imported audits are attributed to the import they came from, rather
+        // than their `who` field, when counting independent auditors.
+        let import_names: Vec<&'a str> = store
+            .imported_audits()
+            .keys()
+            .map(|import_name| &import_name[..])
+            .collect();
+        let auditors = |import_index: Option<usize>, who: &'a [Spanned<String>]| {
+            Some(match import_index {
+                Some(import_index) => vec![import_names[import_index]],
+                None => who.iter().map(|who| &who[..]).collect(),
+            })
+        };
+
         // Iterator over every audits file, including imported audits.
         let all_audits_files = store
             .imported_audits()
@@ -1098,6 +1620,7 @@ impl<'a> AuditGraph<'a> {
                         .unwrap_or(&[])
                         .iter()
                         .enumerate()
+                        .filter(move |(_, audit)| audit.registry.as_deref() == registry)
                         .map(move |(audit_index, audit)| {
                             (
                                 namespace,
@@ -1158,6 +1681,20 @@ impl<'a> AuditGraph<'a> {
         let mut forward_audits = DirectedAuditGraph::new();
         let mut backward_audits = DirectedAuditGraph::new();
         let mut violation_nodes = Vec::new();
+        let mut expired = Vec::new();
+
+        // Advisories only cover crates.io, and are treated like violations
+        // from a source named after the advisory.
+        if registry.is_none() {
+            for (advisory_id, entry) in store
+                .advisory_violations
+                .get(package)
+                .map(|v| &v[..])
+                .unwrap_or(&[])
+            {
+                violation_nodes.push((Some(advisory_id.clone()), entry, true));
+            }
+        }
 
         // Collect up all the deltas, and their criteria
         for (namespace, origin, entry) in all_audits.clone() {
@@ -1166,19 +1703,40 @@ impl<'a> AuditGraph<'a> {
                 AuditKind::Full { version } => (None, version),
                 AuditKind::Delta { from, to } => (Some(from), to),
                 AuditKind::Violation { .. } => {
-                    violation_nodes.push((namespace.clone(), entry));
+                    violation_nodes.push((namespace.clone(), entry, false));
                     continue;
                 }
             };
 
             let criteria = criteria_mapper.criteria_from_list(&entry.criteria);
+
+            // Expired audits are left out of the graph, but remembered so that
+            // failures can explain why they weren't used.
+            if let Some(expires) = entry.expires.filter(|_| entry.is_expired(today)) {
+                expired.push(ExpiredEntry {
+                    origin,
+                    source: namespace.clone(),
+                    from: from_ver.cloned(),
+                    to: to_ver.clone(),
+                    criteria,
+                    expires,
+                });
+                continue;
+            }
+
             let freshness = DeltaEdgeFreshness::new(entry.is_fresh_import, false);
+            let import_index = match origin {
+                DeltaEdgeOrigin::ImportedAudit { import_index, .. } => Some(import_index),
+                _ => None,
+            };
+            let auditors = auditors(import_index, &entry.who);
 
             forward_audits.entry(from_ver).or_default().push(DeltaEdge {
                 version: Some(to_ver),
                 criteria: criteria.clone(),
                 origin: origin.clone(),
                 freshness,
+                auditors: auditors.clone(),
             });
             backward_audits
                 .entry(Some(to_ver))
@@ -1188,6 +1746,7 @@ impl<'a> AuditGraph<'a> {
                     criteria,
                     origin,
                     freshness,
+                    auditors,
                 });
         }
 
@@ -1210,18 +1769,21 @@ impl<'a> AuditGraph<'a> {
                     };
                     let freshness =
                         DeltaEdgeFreshness::new(entry.is_fresh_import, publisher.is_fresh_import);
+                    let auditors = auditors(import_index, &entry.who);
 
                     forward_audits.entry(from_ver).or_default().push(DeltaEdge {
                         version: to_ver,
                         criteria: criteria.clone(),
                         origin: origin.clone(),
                         freshness,
+                        auditors: auditors.clone(),
                     });
                     backward_audits.entry(to_ver).or_default().push(DeltaEdge {
                         version: from_ver,
                         criteria,
                         origin,
                         freshness,
+                        auditors,
                     });
                 }
             }
@@ -1246,12 +1808,14 @@ impl<'a> AuditGraph<'a> {
                         criteria: criteria.clone(),
                         origin: origin.clone(),
                         freshness,
+                        auditors: None,
                     });
                     backward_audits.entry(to_ver).or_default().push(DeltaEdge {
                         version: from_ver,
                         criteria,
                         origin,
                         freshness,
+                        auditors: None,
                     });
                 }
             }
@@ -1270,12 +1834,14 @@ impl<'a> AuditGraph<'a> {
                 criteria: criteria.clone(),
                 origin: origin.clone(),
                 freshness,
+                auditors: None,
             });
             backward_audits.entry(to_ver).or_default().push(DeltaEdge {
                 version: from_ver,
                 criteria,
                 origin,
                 freshness,
+                auditors: None,
             });
         }
 
@@ -1287,25 +1853,40 @@ impl<'a> AuditGraph<'a> {
                 let criteria = criteria_mapper.criteria_from_list(&allowed.criteria);
                 let origin = DeltaEdgeOrigin::Exemption { exemption_index };
 
+                if let Some(expires) = allowed.expires.filter(|_| allowed.is_expired(today)) {
+                    expired.push(ExpiredEntry {
+                        origin,
+                        source: None,
+                        from: None,
+                        to: allowed.version.clone(),
+                        criteria,
+                        expires,
+                    });
+                    continue;
+                }
+
                 // For simplicity, turn 'exemptions' entries into deltas from None.
                 forward_audits.entry(from_ver).or_default().push(DeltaEdge {
                     version: to_ver,
                     criteria: criteria.clone(),
                     origin: origin.clone(),
                     freshness: DeltaEdgeFreshness::Stale,
+                    auditors: None,
                 });
                 backward_audits.entry(to_ver).or_default().push(DeltaEdge {
                     version: from_ver,
                     criteria,
                     origin,
                     freshness: DeltaEdgeFreshness::Stale,
+                    auditors: None,
                 });
             }
         }
 
         // Reject forbidden packages (violations)
         let mut violations = Vec::new();
-        for (violation_source, violation_entry) in &violation_nodes {
+        for (violation_source, violation_entry, is_advisory) in &violation_nodes {
+            let prior_violations = violations.len();
             // Ok this is kind of weird. We want to reject any audits which contain any of these criteria.
             // Normally we would slap all the criteria in this entry into a set and do some kind of set
             // comparison, but that's not quite right. Here are the cases we want to work:
@@ -1392,6 +1973,16 @@ impl<'a> AuditGraph<'a> {
                     }
                 }
             }
+
+            // An advisory must reject the crate even if the affected version
+            // isn't vetted by an audit or exemption (e.g. it's trusted, or
+            // not vetted at all).
+            if *is_advisory && violations.len() == prior_violations {
+                violations.push(ViolationConflict::AdvisoryConflict {
+                    violation_source: violation_source.clone(),
+                    violation: (*violation_entry).clone(),
+                });
+            }
         }
 
         // If we enountered any violations, report them.
@@ -1402,6 +1993,7 @@ impl<'a> AuditGraph<'a> {
         Ok(AuditGraph {
             forward_audits,
             backward_audits,
+            expired,
         })
     }
 
@@ -1450,6 +2042,150 @@ impl<'a> AuditGraph<'a> {
             }
         })
     }
+
+    /// Build a copy of this AuditGraph which enforces `min-auditors`. Reviewed
+    /// edges will only be usable for a criteria if the same delta was reviewed
+    /// for that criteria by at least `min_auditors` independent auditors.
+    pub fn with_min_auditors(&self, min_auditors: usize) -> Self {
+        fn restrict(graph: &mut DirectedAuditGraph<'_>, min_auditors: usize) {
+            for edges in graph.values_mut() {
+                let restricted: Vec<_> = edges
+                    .iter()
+                    .map(|edge| {
+                        let mut criteria = edge.criteria.clone();
+                        if edge.auditors.is_some() {
+                            for criteria_idx in edge.criteria.indices() {
+                                if count_auditors(edges, edge.version, criteria_idx) < min_auditors
+                                {
+                                    criteria.unset_criteria(criteria_idx);
+                                }
+                            }
+                        }
+                        criteria
+                    })
+                    .collect();
+                for (edge, criteria) in edges.iter_mut().zip(restricted) {
+                    edge.criteria = criteria;
+                }
+            }
+        }
+
+        let mut graph = self.clone();
+        restrict(&mut graph.forward_audits, min_auditors);
+        restrict(&mut graph.backward_audits, min_auditors);
+        graph
+    }
+
+    /// Get the edges which reviewed the same delta as the edge with the given
+    /// origin for the given criteria. These are the edges whose auditors count
+    /// towards `min-auditors` for that edge. Empty if the origin isn't a review.
+    pub fn cosigning_edges(
+        &self,
+        origin: &DeltaEdgeOrigin,
+        criteria_idx: usize,
+    ) -> Vec<DeltaEdgeOrigin> {
+        self.parallel_reviews(origin, criteria_idx)
+            .map(|edge| edge.origin.clone())
+            .collect()
+    }
+
+    /// Determine how many more independent auditors would need to review the
+    /// edges in `path` for it to satisfy `min_auditors` for the given criteria.
+    pub fn missing_auditors(
+        &self,
+        path: &[DeltaEdgeOrigin],
+        criteria_idx: usize,
+        min_auditors: usize,
+    ) -> usize {
+        path.iter()
+            .filter_map(|origin| {
+                let mut reviews = self.parallel_reviews(origin, criteria_idx).peekable();
+                reviews.peek()?;
+                let auditors = count_independent_auditors(reviews);
+                Some(min_auditors.saturating_sub(auditors))
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn parallel_reviews<'b>(
+        &'b self,
+        origin: &DeltaEdgeOrigin,
+        criteria_idx: usize,
+    ) -> impl Iterator<Item = &'b DeltaEdge<'a>> + 'b {
+        let (from_ver, to_ver) = self
+            .forward_audits
+            .iter()
+            .find_map(|(from_ver, edges)| {
+                edges
+                    .iter()
+                    .find(|edge| edge.auditors.is_some() && edge.origin == *origin)
+                    .map(|edge| (from_ver, edge.version))
+            })
+            .unzip();
+        from_ver
+            .and_then(|from_ver| self.forward_audits.get(from_ver))
+            .map(|edges| &edges[..])
+            .unwrap_or(&[])
+            .iter()
+            .filter(move |edge| {
+                Some(edge.version) == to_ver
+                    && edge.auditors.is_some()
+                    && edge.criteria.has_criteria(criteria_idx)
+            })
+    }
+}
+
+/// Count the independent auditors of the reviewed edges in `edges` which lead
+/// to `version` and are valid for the given criteria.
+fn count_auditors(
+    edges: &[DeltaEdge<'_>],
+    version: Option<&VetVersion>,
+    criteria_idx: usize,
+) -> usize {
+    count_independent_auditors(
+        edges
+            .iter()
+            .filter(|edge| edge.version == version && edge.criteria.has_criteria(criteria_idx)),
+    )
+}
+
+/// Count the independent auditors of a set of reviewed edges. Each review
+/// counts for at most one of the auditors who co-signed it, and each auditor
+/// is counted at most once, so this is the size of a maximum matching between
+/// reviews and auditors.
+fn count_independent_auditors<'a, 'b: 'a>(
+    reviews: impl Iterator<Item = &'a DeltaEdge<'b>>,
+) -> usize {
+    fn assign<'b>(
+        review: usize,
+        reviews: &[&[&'b str]],
+        assigned: &mut SortedMap<&'b str, usize>,
+        visited: &mut SortedSet<&'b str>,
+    ) -> bool {
+        for &auditor in reviews[review] {
+            if !visited.insert(auditor) {
+                continue;
+            }
+            let available = match assigned.get(auditor).copied() {
+                None => true,
+                Some(other) => assign(other, reviews, assigned, visited),
+            };
+            if available {
+                assigned.insert(auditor, review);
+                return true;
+            }
+        }
+        false
+    }
+
+    let reviews: Vec<&[&str]> = reviews
+        .filter_map(|edge| edge.auditors.as_deref())
+        .collect();
+    let mut assigned = SortedMap::new();
+    (0..reviews.len())
+        .filter(|&review| assign(review, &reviews, &mut assigned, &mut SortedSet::new()))
+        .count()
 }
 
 /// Core algorithm used to search for a path between two versions within a
@@ -1667,8 +2403,94 @@ fn search_for_path(
 
 impl ResolveReport<'_> {
     pub fn has_errors(&self) -> bool {
-        // Just check the conclusion
-        !matches!(self.conclusion, Conclusion::Success(_))
+        // Just check the conclusion, ignoring any pre-existing failures
+        match &self.conclusion {
+            Conclusion::Success(_) => false,
+            Conclusion::FailForVet(fail) => !fail.failures.is_empty(),
+            Conclusion::FailForViolationConflict(_) => true,
+        }
+    }
+
+    /// Compare this report against one for an older revision of the project
+    /// (`check --base`), and move any failures which were already present in
+    /// the base revision into `FailForVet::preexisting`.
+    ///
+    /// As the two revisions may have different graphs and criteria, failures
+    /// are matched by package name and version, and by criteria name. Only
+    /// the criteria which were already failing are considered pre-existing.
+    /// If the base revision had violation conflicts, its failures aren't known,
+    /// so every failure is kept.
+    pub fn retain_new_failures(&mut self, base: &ResolveReport<'_>) {
+        let ResolveReport {
+            graph,
+            criteria_mapper,
+            conclusion,
+            ..
+        } = self;
+        let Conclusion::FailForVet(fail) = conclusion else {
+            return;
+        };
+        let base_fail = match &base.conclusion {
+            Conclusion::FailForVet(base_fail) => base_fail,
+            Conclusion::Success(_) => return,
+            Conclusion::FailForViolationConflict(_) => {
+                // If the base revision had violation conflicts, we don't know
+                // which of its packages were failing.
+                warn!("the base revision has violation conflicts, so all failures are reported as new");
+                return;
+            }
+        };
+
+        let mut base_failures: FastMap<(PackageStr<'_>, &VetVersion), FastSet<&str>> =
+            FastMap::new();
+        for (pkgidx, audit_fail) in &base_fail.failures {
+            let package = &base.graph.nodes[*pkgidx];
+            base_failures
+                .entry((package.name, &package.version))
+                .or_default()
+                .extend(
+                    audit_fail
+                        .criteria_failures
+                        .indices()
+                        .map(|idx| base.criteria_mapper.criteria_name(idx)),
+                );
+        }
+
+        for (pkgidx, audit_fail) in std::mem::take(&mut fail.failures) {
+            let package = &graph.nodes[pkgidx];
+            let Some(base_criteria) = base_failures.get(&(package.name, &package.version)) else {
+                fail.failures.push((pkgidx, audit_fail));
+                continue;
+            };
+
+            let mut preexisting = criteria_mapper.no_criteria();
+            for criteria_idx in audit_fail.criteria_failures.indices() {
+                if base_criteria.contains(criteria_mapper.criteria_name(criteria_idx)) {
+                    preexisting.set_criteria(criteria_idx);
+                }
+            }
+            let mut new = audit_fail.criteria_failures.clone();
+            new.clear_criteria(&preexisting);
+
+            if !new.is_empty() {
+                fail.failures.push((
+                    pkgidx,
+                    AuditFailure {
+                        criteria_failures: new,
+                        ..audit_fail.clone()
+                    },
+                ));
+            }
+            if !preexisting.is_empty() {
+                fail.preexisting.push((
+                    pkgidx,
+                    AuditFailure {
+                        criteria_failures: preexisting,
+                        ..audit_fail
+                    },
+                ));
+            }
+        }
     }
 
     pub fn _has_warnings(&self) -> bool {
@@ -1682,11 +2504,11 @@ impl ResolveReport<'_> {
         network: Option<&Network>,
     ) -> Result<Option<Suggest>, SuggestError> {
         let _suggest_span = trace_span!("suggest").entered();
-        let fail = if let Conclusion::FailForVet(fail) = &self.conclusion {
-            fail
-        } else {
-            // Nothing to suggest unless we failed for vet
-            return Ok(None);
+        let fail = match &self.conclusion {
+            Conclusion::FailForVet(fail) if !fail.failures.is_empty() => fail,
+            // Nothing to suggest unless we failed for vet, ignoring any
+            // failures which were already present in the `--base` revision.
+            _ => return Ok(None),
         };
 
         let cache = Cache::acquire(cfg)?;
@@ -1694,7 +2516,7 @@ impl ResolveReport<'_> {
         let warnings = RefCell::new(Vec::new());
 
         let mut store = store.clone_for_suggest(false);
-        let registry = if let (false, OutputFormat::Human, Some(network)) = (
+        let registry = if let (false, OutputFormat::Human | OutputFormat::Markdown, Some(network)) = (
             cfg.cli.no_registry_suggestions,
             cfg.cli.output_format,
             network,
@@ -1746,10 +2568,16 @@ impl ResolveReport<'_> {
                         .map(|&parent| self.graph.nodes[parent].name.to_string())
                         .collect();
 
+                    let cargo_registry = package
+                        .registry
+                        .as_deref()
+                        .and_then(|name| store.config.cargo_registry(name));
+
                     let Some((suggested_diff, extra_suggested_diff)) = suggest_delta(
                         &cfg.metadata,
                         network,
                         &cache,
+                        cargo_registry,
                         package.name,
                         &package.version,
                         audit_failure
@@ -1768,7 +2596,12 @@ impl ResolveReport<'_> {
                     // Attempt to look up the publisher of the target version
                     // for the suggested diff, and also record whether the given
                     // package has a sole publisher.
-                    let crates_io_info = cache.crates_io_info(network, package.name).await.ok();
+                    // Publisher information is only available for crates.io.
+                    let crates_io_info = if cargo_registry.is_none() {
+                        cache.crates_io_info(network, package.name).await.ok()
+                    } else {
+                        None
+                    };
                     let publisher_source = match (suggested_diff.to.as_semver(), &crates_io_info) {
                         (Some(semver), Some(metadata)) => metadata
                             .versions
@@ -1848,7 +2681,9 @@ impl ResolveReport<'_> {
                                 &store,
                                 &self.criteria_mapper,
                                 package.name,
+                                package.registry.as_deref(),
                                 Some(audits),
+                                cfg.today(),
                             )
                             .ok()?;
 
@@ -1878,6 +2713,7 @@ impl ResolveReport<'_> {
                                 &cfg.metadata,
                                 network,
                                 &cache,
+                                cargo_registry,
                                 package.name,
                                 target_version,
                                 failures.iter(),
@@ -2095,35 +2931,100 @@ impl ResolveReport<'_> {
         Ok(())
     }
 
-    /// Print a full json report
-    pub fn print_json(
+    /// Print a full Markdown report
+    pub fn print_markdown(
         &self,
         out: &Arc<dyn Out>,
         suggest: Option<&Suggest>,
-    ) -> Result<(), miette::Report> {
-        let result = JsonReport {
-            conclusion: match &self.conclusion {
-                Conclusion::Success(success) => {
-                    let json_package = |pkgidx: &PackageIdx| {
-                        let package = &self.graph.nodes[*pkgidx];
-                        JsonPackage {
-                            name: package.name.to_owned(),
-                            version: package.version.clone(),
-                        }
-                    };
-                    JsonReportConclusion::Success(JsonReportSuccess {
-                        vetted_fully: success.vetted_fully.iter().map(json_package).collect(),
-                        vetted_partially: success
-                            .vetted_partially
-                            .iter()
-                            .map(json_package)
-                            .collect(),
-                        vetted_with_exemptions: success
-                            .vetted_with_exemptions
+    ) -> Result<(), std::io::Error> {
+        match &self.conclusion {
+            Conclusion::Success(res) => res.print_markdown(out),
+            Conclusion::FailForViolationConflict(res) => res.print_markdown(out, self),
+            Conclusion::FailForVet(res) => res.print_markdown(out, self, suggest),
+        }
+    }
+
+    /// Print only the suggest portion of a Markdown report
+    pub fn print_suggest_markdown(
+        &self,
+        out: &Arc<dyn Out>,
+        suggest: Option<&Suggest>,
+    ) -> Result<(), std::io::Error> {
+        if let Some(suggest) = suggest {
+            suggest.print_markdown(out, self)?;
+        } else {
+            // This API is only used for vet-suggest
+            writeln!(out, "Nothing to suggest, you're fully audited!");
+        }
+        Ok(())
+    }
+
+    /// Print a full json report
+    pub fn print_json(
+        &self,
+        out: &Arc<dyn Out>,
+        cfg: &Config,
+        suggest: Option<&Suggest>,
+    ) -> Result<(), miette::Report> {
+        let json_package = |pkgidx: &PackageIdx| {
+            let package = &self.graph.nodes[*pkgidx];
+            JsonPackage {
+                name: package.name.to_owned(),
+                version: package.version.clone(),
+            }
+        };
+        let json_success = |success: &Success, preexisting_failures| JsonReportSuccess {
+            vetted_fully: success.vetted_fully.iter().map(json_package).collect(),
+            vetted_partially: success.vetted_partially.iter().map(json_package).collect(),
+            vetted_with_exemptions: success
+                .vetted_with_exemptions
+                .iter()
+                .map(json_package)
+                .collect(),
+            preexisting_failures,
+        };
+        let json_failures = |failures: &[(PackageIdx, AuditFailure)]| {
+            failures
+                .iter()
+                .map(|(pkgidx, audit_fail)| {
+                    let package = &self.graph.nodes[*pkgidx];
+                    JsonVetFailure {
+                        name: package.name.to_owned(),
+                        version: package.version.clone(),
+                        missing_criteria: self
+                            .criteria_mapper
+                            .criteria_names(&audit_fail.criteria_failures)
+                            .map(|s| s.to_owned())
+                            .collect(),
+                        expired: audit_fail
+                            .expired
                             .iter()
-                            .map(json_package)
+                            .map(|expired| JsonExpiredEntry {
+                                kind: if expired.is_exemption() {
+                                    JsonExpiredKind::Exemption
+                                } else {
+                                    JsonExpiredKind::Audit
+                                },
+                                source: expired.source.clone(),
+                                from: expired.from.clone(),
+                                to: expired.to.clone(),
+                                criteria: self
+                                    .criteria_mapper
+                                    .criteria_names(&expired.criteria)
+                                    .map(|s| s.to_owned())
+                                    .collect(),
+                                expires: expired.expires,
+                            })
                             .collect(),
-                    })
+                        missing_auditors: audit_fail.missing_auditors,
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+        let result = JsonReport {
+            conclusion: match &self.conclusion {
+                Conclusion::Success(success) => {
+                    JsonReportConclusion::Success(json_success(success, Vec::new()))
                 }
                 Conclusion::FailForViolationConflict(fail) => {
                     JsonReportConclusion::FailForViolationConflict(
@@ -2140,6 +3041,13 @@ impl ResolveReport<'_> {
                         },
                     )
                 }
+                // Every failure was already present in the `--base` revision.
+                Conclusion::FailForVet(fail) if fail.failures.is_empty() => {
+                    JsonReportConclusion::Success(json_success(
+                        &fail.vetted,
+                        json_failures(&fail.preexisting),
+                    ))
+                }
                 Conclusion::FailForVet(fail) => {
                     // FIXME: How to report confidence for suggested criteria?
                     let json_suggest_item = |item: &SuggestItem| {
@@ -2156,22 +3064,8 @@ impl ResolveReport<'_> {
                         }
                     };
                     JsonReportConclusion::FailForVet(JsonReportFailForVet {
-                        failures: fail
-                            .failures
-                            .iter()
-                            .map(|(pkgidx, audit_fail)| {
-                                let package = &self.graph.nodes[*pkgidx];
-                                JsonVetFailure {
-                                    name: package.name.to_owned(),
-                                    version: package.version.clone(),
-                                    missing_criteria: self
-                                        .criteria_mapper
-                                        .criteria_names(&audit_fail.criteria_failures)
-                                        .map(|s| s.to_owned())
-                                        .collect(),
-                                }
-                            })
-                            .collect(),
+                        failures: json_failures(&fail.failures),
+                        preexisting_failures: json_failures(&fail.preexisting),
                         suggest: suggest.as_ref().map(|suggest| JsonSuggest {
                             suggestions: suggest
                                 .suggestions
@@ -2193,12 +3087,397 @@ impl ResolveReport<'_> {
                     })
                 }
             },
+            targets: cfg.targets.clone(),
         };
 
         serde_json::to_writer_pretty(&**out, &result).into_diagnostic()?;
 
         Ok(())
     }
+
+    pub fn print_sarif(
+        &self,
+        out: &Arc<dyn Out>,
+        cfg: &Config,
+        store: &Store,
+    ) -> Result<(), miette::Report> {
+        let store_path = cfg.metacfg.store_path().as_path_unlocked().to_owned();
+        let result = self.sarif_log(cfg, store, &store_path);
+        serde_json::to_writer_pretty(&**out, &result).into_diagnostic()?;
+        Ok(())
+    }
+
+    /// Build a SARIF log of the failures and violation conflicts in this
+    /// report, along with any expired wildcard audits. Results are located at
+    /// the package's entry in `Cargo.lock`, or at the relevant line of the
+    /// store's `audits.toml` for local audits.
+    pub fn sarif_log(&self, cfg: &Config, store: &Store, store_path: &Path) -> SarifLog {
+        const SRCROOT: &str = "%SRCROOT%";
+        const UNVETTED_DEPENDENCY: &str = "unvetted-dependency";
+        const VIOLATION_CONFLICT: &str = "violation-conflict";
+        const EXPIRED_WILDCARD_AUDIT: &str = "expired-wildcard-audit";
+
+        // Paths are written relative to the workspace root where possible, so
+        // that results line up with the files in the repository.
+        let workspace_root = cfg.metadata.workspace_root.as_std_path();
+        let artifact = |path: &Path| match path.strip_prefix(workspace_root) {
+            Ok(relative) => SarifArtifactLocation {
+                uri: relative
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/"),
+                uri_base_id: Some(SRCROOT.to_owned()),
+            },
+            Err(_) => SarifArtifactLocation {
+                uri: Url::from_file_path(path)
+                    .map(|url| url.to_string())
+                    .unwrap_or_else(|_| path.display().to_string()),
+                uri_base_id: None,
+            },
+        };
+        let location = |path: &Path, line: Option<usize>| SarifLocation {
+            physical_location: SarifPhysicalLocation {
+                artifact_location: artifact(path),
+                region: line.map(|start_line| SarifRegion { start_line }),
+            },
+        };
+
+        let lock_path = workspace_root.join("Cargo.lock");
+        let lock_lines = std::fs::read_to_string(&lock_path)
+            .map(|lock| cargo_lock_lines(&lock))
+            .unwrap_or_default();
+        let package_location = |pkgidx: PackageIdx| {
+            let package = &self.graph.nodes[pkgidx];
+            let line = lock_lines
+                .get(&(package.name.to_owned(), package.version.semver.to_string()))
+                .copied();
+            location(&lock_path, line)
+        };
+
+        // Spans are only meaningful for entries loaded from the local
+        // audits.toml, so other entries are located using Cargo.lock.
+        let audits_path = store_path.join("audits.toml");
+        let audits_source = store.audits_src.source();
+        let audits_line = |offset: usize| {
+            (offset > 0 && offset <= audits_source.len())
+                .then(|| audits_source[..offset].matches('\n').count() + 1)
+        };
+
+        let mut results = Vec::new();
+        match &self.conclusion {
+            Conclusion::Success(_) => {}
+            Conclusion::FailForVet(fail) => {
+                for (pkgidx, audit_fail) in &fail.failures {
+                    let package = &self.graph.nodes[*pkgidx];
+                    let criteria: Vec<_> = self
+                        .criteria_mapper
+                        .criteria_names(&audit_fail.criteria_failures)
+                        .collect();
+                    let mut text = format!(
+                        "{}:{} is missing audits for {}",
+                        package.name,
+                        package.version,
+                        FormatShortList::new(criteria)
+                    );
+                    if let Some(missing_auditors) = audit_fail.missing_auditors {
+                        text +=
+                            &format!(", which need {missing_auditors} more independent auditor(s)");
+                    }
+                    if !audit_fail.expired.is_empty() {
+                        text += ", and expired audits or exemptions were ignored";
+                    }
+                    results.push(SarifResult {
+                        rule_id: UNVETTED_DEPENDENCY.to_owned(),
+                        level: SarifLevel::Error,
+                        message: SarifMessage { text },
+                        locations: vec![package_location(*pkgidx)],
+                    });
+                }
+            }
+            Conclusion::FailForViolationConflict(fail) => {
+                for (pkgidx, violations) in &fail.violations {
+                    let package = &self.graph.nodes[*pkgidx];
+                    for violation in violations {
+                        let (violation_source, entry, text) =
+                            describe_violation_conflict(package, violation);
+                        let line = match violation_source {
+                            None => entry
+                                .criteria
+                                .first()
+                                .and_then(|criteria| audits_line(Spanned::start(criteria))),
+                            Some(_) => None,
+                        };
+                        let location = match line {
+                            Some(line) => location(&audits_path, Some(line)),
+                            None => package_location(*pkgidx),
+                        };
+                        results.push(SarifResult {
+                            rule_id: VIOLATION_CONFLICT.to_owned(),
+                            level: SarifLevel::Error,
+                            message: SarifMessage { text },
+                            locations: vec![location],
+                        });
+                    }
+                }
+            }
+        }
+
+        for (package, entries) in &store.audits.wildcard_audits {
+            for entry in entries {
+                // Entries with `renew = false` are still expired, they're just
+                // not suggested for renewal.
+                if *entry.end >= cfg.today() {
+                    continue;
+                }
+                let publisher = match &entry.source {
+                    CratesSourceId::User { user_id } => format!("user {user_id}"),
+                    CratesSourceId::TrustedPublisher { trusted_publisher } => {
+                        trusted_publisher.to_string()
+                    }
+                };
+                results.push(SarifResult {
+                    rule_id: EXPIRED_WILDCARD_AUDIT.to_owned(),
+                    level: SarifLevel::Warning,
+                    message: SarifMessage {
+                        text: format!(
+                            "wildcard audit of {package} published by {publisher} expired on {}",
+                            *entry.end
+                        ),
+                    },
+                    locations: vec![location(
+                        &audits_path,
+                        audits_line(Spanned::start(&entry.end)),
+                    )],
+                });
+            }
+        }
+
+        let rule = |id: &str, description: &str| SarifRule {
+            id: id.to_owned(),
+            short_description: SarifMessage {
+                text: description.to_owned(),
+            },
+            help_uri: "https://mozilla.github.io/cargo-vet/".to_owned(),
+        };
+        SarifLog {
+            schema: "https://json.schemastore.org/sarif-2.1.0.json".to_owned(),
+            version: "2.1.0".to_owned(),
+            runs: vec![SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: "cargo-vet".to_owned(),
+                        version: env!("CARGO_PKG_VERSION").to_owned(),
+                        information_uri: "https://mozilla.github.io/cargo-vet/".to_owned(),
+                        rules: vec![
+                            rule(
+                                UNVETTED_DEPENDENCY,
+                                "A dependency doesn't have the audits its policy requires",
+                            ),
+                            rule(
+                                VIOLATION_CONFLICT,
+                                "An audit or exemption conflicts with a violation",
+                            ),
+                            rule(EXPIRED_WILDCARD_AUDIT, "A wildcard audit has expired"),
+                        ],
+                    },
+                },
+                original_uri_base_ids: [(
+                    SRCROOT.to_owned(),
+                    SarifArtifactLocation {
+                        uri: Url::from_directory_path(workspace_root)
+                            .map(|url| url.to_string())
+                            .unwrap_or_else(|_| workspace_root.display().to_string()),
+                        uri_base_id: None,
+                    },
+                )]
+                .into_iter()
+                .collect(),
+                results,
+            }],
+        }
+    }
+
+    /// Print a JUnit XML report, in which each third-party package is a test
+    /// case. Packages pass when vetted, fail with the missing criteria and the
+    /// suggested audit when not vetted, and are skipped when they rely on
+    /// exemptions.
+    pub fn print_junit(
+        &self,
+        out: &Arc<dyn Out>,
+        suggest: Option<&Suggest>,
+    ) -> Result<(), std::io::Error> {
+        enum Outcome {
+            Pass,
+            Skipped(String),
+            Failure(String, String),
+        }
+
+        let mut outcomes: SortedMap<PackageIdx, Outcome> = SortedMap::new();
+        let mut record_vetted = |vetted: &Success| {
+            for &pkgidx in &vetted.vetted_fully {
+                outcomes.insert(pkgidx, Outcome::Pass);
+            }
+            for &pkgidx in &vetted.vetted_partially {
+                let message = "vetted using both audits and exemptions".to_owned();
+                outcomes.insert(pkgidx, Outcome::Skipped(message));
+            }
+            for &pkgidx in &vetted.vetted_with_exemptions {
+                outcomes.insert(
+                    pkgidx,
+                    Outcome::Skipped("vetted using exemptions".to_owned()),
+                );
+            }
+        };
+        match &self.conclusion {
+            Conclusion::Success(success) => record_vetted(success),
+            Conclusion::FailForVet(fail) => {
+                record_vetted(&fail.vetted);
+                for (pkgidx, audit_fail) in &fail.preexisting {
+                    let criteria: Vec<_> = self
+                        .criteria_mapper
+                        .criteria_names(&audit_fail.criteria_failures)
+                        .collect();
+                    let message = format!(
+                        "missing {} (already failing in the base revision)",
+                        FormatShortList::new(criteria)
+                    );
+                    outcomes.insert(*pkgidx, Outcome::Skipped(message));
+                }
+                for (pkgidx, audit_fail) in &fail.failures {
+                    let package = &self.graph.nodes[*pkgidx];
+                    let criteria: Vec<_> = self
+                        .criteria_mapper
+                        .criteria_names(&audit_fail.criteria_failures)
+                        .collect();
+                    let message = format!("missing {}", FormatShortList::new(criteria.clone()));
+                    let mut details = vec![format!(
+                        "{}:{} is missing audits for {}",
+                        package.name,
+                        package.version,
+                        FormatShortList::new(criteria)
+                    )];
+                    if let Some(missing_auditors) = audit_fail.missing_auditors {
+                        details.push(format!(
+                            "{missing_auditors} more independent auditor(s) are required"
+                        ));
+                    }
+                    details.extend(audit_fail.expired.iter().map(describe_expired_entry));
+                    let suggestion = suggest.and_then(|suggest| {
+                        suggest
+                            .suggestions
+                            .iter()
+                            .find(|item| item.package == *pkgidx)
+                    });
+                    if let Some(item) = suggestion {
+                        details.push(format!(
+                            "suggested audit: {} ({})",
+                            item.suggested_diff.command(package.name),
+                            item.suggested_diff.size()
+                        ));
+                    }
+                    outcomes.insert(*pkgidx, Outcome::Failure(message, details.join("\n")));
+                }
+            }
+            Conclusion::FailForViolationConflict(fail) => {
+                for (pkgidx, node) in self.graph.nodes.iter().enumerate() {
+                    if node.is_third_party {
+                        let message = "not checked because of violation conflicts".to_owned();
+                        outcomes.insert(pkgidx, Outcome::Skipped(message));
+                    }
+                }
+                for (pkgidx, violations) in &fail.violations {
+                    let package = &self.graph.nodes[*pkgidx];
+                    let details: Vec<_> = violations
+                        .iter()
+                        .map(|violation| describe_violation_conflict(package, violation).2)
+                        .collect();
+                    outcomes.insert(
+                        *pkgidx,
+                        Outcome::Failure(
+                            format!(
+                                "{}:{} conflicts with a violation",
+                                package.name, package.version
+                            ),
+                            details.join("\n"),
+                        ),
+                    );
+                }
+            }
+        }
+
+        let failures = outcomes
+            .values()
+            .filter(|outcome| matches!(outcome, Outcome::Failure(..)))
+            .count();
+        let skipped = outcomes
+            .values()
+            .filter(|outcome| matches!(outcome, Outcome::Skipped(..)))
+            .count();
+        let counts = format!(
+            r#"tests="{}" failures="{failures}" skipped="{skipped}""#,
+            outcomes.len()
+        );
+
+        writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        writeln!(out, r#"<testsuites name="cargo-vet" {counts}>"#);
+        writeln!(out, r#"  <testsuite name="cargo-vet" {counts}>"#);
+        for (pkgidx, outcome) in &outcomes {
+            let package = &self.graph.nodes[*pkgidx];
+            let testcase = format!(
+                r#"<testcase classname="{}" name="{}""#,
+                xml_escape(package.name),
+                xml_escape(&format!("{}:{}", package.name, package.version))
+            );
+            match outcome {
+                Outcome::Pass => writeln!(out, "    {testcase}/>"),
+                Outcome::Skipped(message) => {
+                    writeln!(out, "    {testcase}>");
+                    writeln!(out, r#"      <skipped message="{}"/>"#, xml_escape(message));
+                    writeln!(out, "    </testcase>");
+                }
+                Outcome::Failure(message, details) => {
+                    writeln!(out, "    {testcase}>");
+                    writeln!(
+                        out,
+                        r#"      <failure message="{}" type="cargo-vet">{}</failure>"#,
+                        xml_escape(message),
+                        xml_escape(details)
+                    );
+                    writeln!(out, "    </testcase>");
+                }
+            }
+        }
+        writeln!(out, "  </testsuite>");
+        writeln!(out, "</testsuites>");
+        Ok(())
+    }
+}
+
+/// Escape a string for use in a Markdown table cell.
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
+
+/// Find the line of each package's entry in a `Cargo.lock` file, keyed by its
+/// name and version.
+fn cargo_lock_lines(lock: &str) -> SortedMap<(PackageName, String), usize> {
+    let unquote = |value: &str| value.trim().trim_matches('"').to_owned();
+    let mut lines = SortedMap::new();
+    let mut current: Option<(usize, Option<PackageName>)> = None;
+    for (index, line) in lock.lines().enumerate() {
+        let line = line.trim();
+        if line == "[[package]]" {
+            current = Some((index + 1, None));
+        } else if let Some((start, name)) = &mut current {
+            if let Some(value) = line.strip_prefix("name = ") {
+                *name = Some(unquote(value));
+            } else if let (Some(value), Some(name)) = (line.strip_prefix("version = "), name) {
+                lines.insert((name.clone(), unquote(value)), *start);
+            }
+        }
+    }
+    lines
 }
 
 impl Success {
@@ -2252,6 +3531,21 @@ impl Success {
         }
         Ok(())
     }
+
+    fn print_markdown(&self, out: &Arc<dyn Out>) -> Result<(), std::io::Error> {
+        writeln!(out, "## Vetting Succeeded");
+        writeln!(out);
+        writeln!(out, "| Fully Audited | Partially Audited | Exempted |");
+        writeln!(out, "|---:|---:|---:|");
+        writeln!(
+            out,
+            "| {} | {} | {} |",
+            self.vetted_fully.len(),
+            self.vetted_partially.len(),
+            self.vetted_with_exemptions.len()
+        );
+        Ok(())
+    }
 }
 
 impl Suggest {
@@ -2267,25 +3561,13 @@ impl Suggest {
                 .iter()
                 .map(|item| {
                     let package = &report.graph.nodes[item.package];
-                    let cmd = match &item.suggested_diff.from {
-                        Some(from) => format!(
-                            "cargo vet diff {} {} {}",
-                            package.name, from, item.suggested_diff.to
-                        ),
-                        None => format!(
-                            "cargo vet inspect {} {}",
-                            package.name, item.suggested_diff.to
-                        ),
-                    };
+                    let cmd = item.suggested_diff.command(package.name);
                     let publisher = item
                         .publisher_login
                         .clone()
                         .unwrap_or_else(|| "UNKNOWN".into());
                     let parents = FormatShortList::string(item.notable_parents.clone());
-                    let diffstat = match &item.suggested_diff.from {
-                        Some(_) => format!("{}", item.suggested_diff.diffstat),
-                        None => format!("{} lines", item.suggested_diff.diffstat.count()),
-                    };
+                    let diffstat = item.suggested_diff.size();
                     (cmd, publisher, parents, diffstat, item)
                 })
                 .collect::<Vec<_>>();
@@ -2408,6 +3690,84 @@ impl Suggest {
 
         Ok(())
     }
+
+    fn print_markdown(
+        &self,
+        out: &Arc<dyn Out>,
+        report: &ResolveReport<'_>,
+    ) -> Result<(), std::io::Error> {
+        for (criteria, suggestions) in &self.suggestions_by_criteria {
+            writeln!(out, "### Recommended audits for `{criteria}`");
+            writeln!(out);
+            writeln!(out, "| Command | Publisher | Used By | Audit Size |");
+            writeln!(out, "|---|---|---|---|");
+            for item in suggestions {
+                let package = &report.graph.nodes[item.package];
+                writeln!(
+                    out,
+                    "| `{}` | {} | {} | {} |",
+                    item.suggested_diff.command(package.name),
+                    markdown_cell(item.publisher_login.as_deref().unwrap_or("UNKNOWN")),
+                    markdown_cell(&FormatShortList::string(item.notable_parents.clone())),
+                    item.suggested_diff.size()
+                );
+            }
+            writeln!(out);
+
+            let imports: Vec<_> = suggestions
+                .iter()
+                .flat_map(|item| {
+                    item.registry_suggestion
+                        .iter()
+                        .map(move |suggestion| (item, suggestion))
+                })
+                .collect();
+            if !imports.is_empty() {
+                writeln!(out, "<details>");
+                writeln!(
+                    out,
+                    "<summary>Audits available from the registry ({})</summary>",
+                    imports.len()
+                );
+                writeln!(out);
+                writeln!(out, "| Crate | Command | Remaining Audit |");
+                writeln!(out, "|---|---|---|");
+                for (item, suggestion) in imports {
+                    let package = &report.graph.nodes[item.package];
+                    let remaining = match suggestion.diff.diffstat.count() {
+                        0 => "none".to_owned(),
+                        _ => format!(
+                            "`{}` ({})",
+                            suggestion.diff.command(package.name),
+                            suggestion.diff.size()
+                        ),
+                    };
+                    writeln!(
+                        out,
+                        "| `{}` | `cargo vet import {}` | {remaining} |",
+                        package.name, suggestion.name
+                    );
+                }
+                writeln!(out);
+                writeln!(out, "</details>");
+                writeln!(out);
+            }
+        }
+
+        writeln!(out, "Estimated audit backlog: {} lines", self.total_lines);
+
+        if !self.warnings.is_empty() {
+            writeln!(out);
+            for warning in &self.warnings {
+                writeln!(out, "> **Warning:** {}", markdown_cell(warning));
+            }
+        }
+
+        writeln!(out);
+        writeln!(out, "Use `cargo vet certify` to record the audits.");
+
+        Ok(())
+    }
 }
 
 impl FailForVet {
@@ -2415,27 +3775,27 @@ impl FailForVet {
         &self,
         out: &Arc<dyn Out>,
         report: &ResolveReport<'_>,
-        _cfg: &Config,
+        cfg: &Config,
         suggest: Option<&Suggest>,
     ) -> Result<(), std::io::Error> {
-        writeln!(out, "Vetting Failed!");
-        writeln!(out);
-        writeln!(out, "{} unvetted dependencies:", self.failures.len());
-        let mut failures = self
-            .failures
-            .iter()
-            .map(|(failed_idx, failure)| (&report.graph.nodes[*failed_idx], failure))
-            .collect::<Vec<_>>();
-        failures.sort_by_key(|(failed, _)| &failed.version);
-        failures.sort_by_key(|(failed, _)| failed.name);
-        for (failed_package, failed_audit) in failures {
-            let criteria = report
-                .criteria_mapper
-                .criteria_names(&failed_audit.criteria_failures)
-                .collect::<Vec<_>>();
+        if self.failures.is_empty() {
+            // Everything which failed was already failing in the base revision.
+            self.vetted.print_human(out, report, cfg)?;
+        } else {
+            writeln!(out, "Vetting Failed!");
+            writeln!(out);
+            writeln!(out, "{} unvetted dependencies:", self.failures.len());
+            print_failures_human(out, report, &self.failures);
+        }
 
-            let label = format!("  {}:{}", failed_package.name, failed_package.version);
-            writeln!(out, "{label} missing {criteria:?}");
+        if !self.preexisting.is_empty() {
+            writeln!(out);
+            writeln!(
+                out,
+                "{} pre-existing unvetted dependencies (also failing in the base revision):",
+                self.preexisting.len()
+            );
+            print_failures_human(out, report, &self.preexisting);
         }
 
         // Suggest output generally requires hitting the network.
@@ -2446,6 +3806,128 @@ impl FailForVet {
 
         Ok(())
     }
+
+    fn print_markdown(
+        &self,
+        out: &Arc<dyn Out>,
+        report: &ResolveReport<'_>,
+        suggest: Option<&Suggest>,
+    ) -> Result<(), std::io::Error> {
+        if self.failures.is_empty() {
+            // Everything which failed was already failing in the base revision.
+            self.vetted.print_markdown(out)?;
+        } else {
+            writeln!(out, "## Vetting Failed");
+            writeln!(out);
+            writeln!(out, "<details>");
+            writeln!(
+                out,
+                "<summary>{} unvetted dependencies</summary>",
+                self.failures.len()
+            );
+            writeln!(out);
+            print_failures_markdown(out, report, &self.failures);
+            writeln!(out);
+            writeln!(out, "</details>");
+        }
+
+        if !self.preexisting.is_empty() {
+            writeln!(out);
+            writeln!(out, "<details>");
+            writeln!(
+                out,
+                "<summary>{} pre-existing unvetted dependencies (also failing in the base revision)</summary>",
+                self.preexisting.len()
+            );
+            writeln!(out);
+            print_failures_markdown(out, report, &self.preexisting);
+            writeln!(out);
+            writeln!(out, "</details>");
+        }
+
+        // Suggest output generally requires hitting the network.
+        if let Some(suggest) = suggest {
+            writeln!(out);
+            suggest.print_markdown(out, report)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn print_failures_human(
+    out: &Arc<dyn Out>,
+    report: &ResolveReport<'_>,
+    failures: &[(PackageIdx, AuditFailure)],
+) {
+    let mut failures = failures
+        .iter()
+        .map(|(failed_idx, failure)| (&report.graph.nodes[*failed_idx], failure))
+        .collect::<Vec<_>>();
+    failures.sort_by_key(|(failed, _)| &failed.version);
+    failures.sort_by_key(|(failed, _)| failed.name);
+    for (failed_package, failed_audit) in failures {
+        let criteria = report
+            .criteria_mapper
+            .criteria_names(&failed_audit.criteria_failures)
+            .collect::<Vec<_>>();
+
+        let label = format!("  {}:{}", failed_package.name, failed_package.version);
+        writeln!(out, "{label} missing {criteria:?}");
+        for expired in &failed_audit.expired {
+            writeln!(out, "    {}", describe_expired_entry(expired));
+        }
+        if let Some(missing) = failed_audit.missing_auditors {
+            let reviews = if missing == 1 { "review" } else { "reviews" };
+            writeln!(
+                out,
+                "    needs {missing} more independent {reviews} to satisfy min-auditors"
+            );
+        }
+    }
+}
+
+fn print_failures_markdown(
+    out: &Arc<dyn Out>,
+    report: &ResolveReport<'_>,
+    failures: &[(PackageIdx, AuditFailure)],
+) {
+    writeln!(out, "| Crate | Version | Missing Criteria | Notes |");
+    writeln!(out, "|---|---|---|---|");
+    let mut failures = failures
+        .iter()
+        .map(|(failed_idx, failure)| (&report.graph.nodes[*failed_idx], failure))
+        .collect::<Vec<_>>();
+    failures.sort_by_key(|(failed, _)| &failed.version);
+    failures.sort_by_key(|(failed, _)| failed.name);
+    for (failed_package, failed_audit) in failures {
+        let criteria = report
+            .criteria_mapper
+            .criteria_names(&failed_audit.criteria_failures)
+            .map(|criteria| format!("`{criteria}`"))
+            .collect::<Vec<_>>();
+
+        let mut notes: Vec<_> = failed_audit
+            .expired
+            .iter()
+            .map(describe_expired_entry)
+            .collect();
+        if let Some(missing) = failed_audit.missing_auditors {
+            let reviews = if missing == 1 { "review" } else { "reviews" };
+            notes.push(format!(
+                "needs {missing} more independent {reviews} to satisfy min-auditors"
+            ));
+        }
+
+        writeln!(
+            out,
+            "| `{}` | {} | {} | {} |",
+            failed_package.name,
+            failed_package.version,
+            criteria.join(", "),
+            markdown_cell(&notes.join("\n"))
+        );
+    }
 }
 
 impl FailForViolationConflict {
@@ -2483,6 +3965,13 @@ impl FailForViolationConflict {
                         write!(out, "    conflicts with ");
                         print_entry(out, violation_source, violation)?;
                     }
+                    ViolationConflict::AdvisoryConflict {
+                        violation_source,
+                        violation,
+                    } => {
+                        write!(out, "    the locked version conflicts with ");
+                        print_entry(out, violation_source, violation)?;
+                    }
                 }
                 writeln!(out);
             }
@@ -2539,21 +4028,51 @@ impl FailForViolationConflict {
 
         Ok(())
     }
+
+    fn print_markdown(
+        &self,
+        out: &Arc<dyn Out>,
+        report: &ResolveReport<'_>,
+    ) -> Result<(), std::io::Error> {
+        writeln!(out, "## Violations Found");
+        writeln!(out);
+        writeln!(out, "| Crate | Version | Conflict |");
+        writeln!(out, "|---|---|---|");
+        for (pkgidx, violations) in &self.violations {
+            let package = &report.graph.nodes[*pkgidx];
+            for violation in violations {
+                let (.., text) = describe_violation_conflict(package, violation);
+                writeln!(
+                    out,
+                    "| `{}` | {} | {} |",
+                    package.name,
+                    package.version,
+                    markdown_cell(&text)
+                );
+            }
+        }
+        Ok(())
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn suggest_delta(
     metadata: &cargo_metadata::Metadata,
     network: Option<&Network>,
     cache: &Cache,
+    cargo_registry: Option<CargoRegistry<'_>>,
     package_name: PackageStr<'_>,
     package_version: &VetVersion,
     failures: impl Iterator<Item = &SearchFailure>,
     warnings: &RefCell<Vec<String>>,
 ) -> Option<(DiffRecommendation, Option<DiffRecommendation>)> {
-    // Fetch the set of known versions from crates.io so we know which versions
-    // we'll have sources for.
+    // Fetch the set of known versions from the registry so we know which
+    // versions we'll have sources for.
     let known_versions = if let Some(network) = network {
-        cache.published_versions(network, package_name).await.ok()
+        cache
+            .published_versions(network, cargo_registry, package_name)
+            .await
+            .ok()
     } else {
         None
     };
@@ -2686,7 +4205,7 @@ async fn suggest_delta(
 
     let do_fetch_and_diffstat = |delta| async move {
         match cache
-            .fetch_and_diffstat_package(metadata, network, package_name, &delta)
+            .fetch_and_diffstat_package(metadata, network, cargo_registry, package_name, &delta)
             .await
         {
             Ok(diffstat) => Some(DiffRecommendation {
@@ -2739,6 +4258,7 @@ fn resolve_package_required_entries(
     store: &Store,
     package_name: PackageStr<'_>,
     search_mode: SearchMode,
+    today: chrono::NaiveDate,
 ) -> Option<SortedMap<RequiredEntry, CriteriaSet>> {
     assert_eq!(graph.nodes.len(), requirements.len());
 
@@ -2755,22 +4275,62 @@ fn resolve_package_required_entries(
         return Some(SortedMap::new());
     }
 
-    let Ok(audit_graph) = AuditGraph::build(store, criteria_mapper, package_name, None) else {
-        // There were violations when building the audit graph, return `None` to
-        // indicate that this package is failing.
-        return None;
-    };
+    // Audits only apply to crates from the registry they're tagged with, so
+    // build a separate audit graph for each registry the package comes from.
+    let mut audit_graphs = SortedMap::new();
+    for &(package, _) in &packages {
+        let registry = package.registry.as_deref();
+        if audit_graphs.contains_key(&registry) {
+            continue;
+        }
+        let Ok(audit_graph) =
+            AuditGraph::build(store, criteria_mapper, package_name, registry, None, today)
+        else {
+            // There were violations when building the audit graph, return `None` to
+            // indicate that this package is failing.
+            return None;
+        };
+        audit_graphs.insert(registry, audit_graph);
+    }
 
     let mut required_entries = SortedMap::new();
     for &(package, reqs) in &packages {
+        let audit_graph = &audit_graphs[&package.registry.as_deref()];
+        let min_auditors = store
+            .config
+            .policy
+            .get(package.name, &package.version)
+            .and_then(|policy| policy.min_auditors)
+            .unwrap_or(1);
+        let restricted_graph =
+            (min_auditors > 1).then(|| audit_graph.with_min_auditors(min_auditors));
+        let search_graph = restricted_graph.as_ref().unwrap_or(audit_graph);
+
         // Do the minimal set of searches to validate that the required criteria
         // are matched.
         for criteria_idx in criteria_mapper.minimal_indices(reqs) {
-            let Ok(path) = audit_graph.search(criteria_idx, &package.version, search_mode) else {
+            let Ok(mut path) = search_graph.search(criteria_idx, &package.version, search_mode)
+            else {
                 // This package failed to vet, return `None`.
                 return None;
             };
 
+            // Every audit which contributed towards `min-auditors` for an edge
+            // in the path is also required.
+            if restricted_graph.is_some() {
+                path = path
+                    .iter()
+                    .flat_map(|origin| {
+                        let cosigners = audit_graph.cosigning_edges(origin, criteria_idx);
+                        if cosigners.is_empty() {
+                            vec![origin.clone()]
+                        } else {
+                            cosigners
+                        }
+                    })
+                    .collect();
+            }
+
             let mut add_entry = |entry: RequiredEntry| {
                 required_entries
                     .entry(entry)
@@ -2903,7 +4463,7 @@ pub(crate) fn get_store_updates(
     let graph = DepGraph::new(
         &cfg.metadata,
         cfg.cli.filter_graph.as_ref(),
-        Some(&store.config.policy),
+        Some(&store.config),
     );
     let criteria_mapper = CriteriaMapper::new(&store.audits.criteria);
     let requirements = resolve_requirements(&graph, &store.config.policy, &criteria_mapper);
@@ -2918,6 +4478,7 @@ pub(crate) fn get_store_updates(
                 store,
                 package.name,
                 mode(package.name).search_mode,
+                cfg.today(),
             )
         });
     }
@@ -2935,9 +4496,10 @@ pub(crate) fn get_store_updates(
                 .into_iter()
                 .enumerate()
                 .filter(|&(audit_index, ref entry)| {
-                    // Keep the entry if it's importable (i.e. it could be used externally) or it's
-                    // used locally.
+                    // Keep the entry if it's importable (i.e. it could be used externally), it's
+                    // used locally, or it has expired and is waiting to be renewed.
                     entry.importable
+                        || entry.is_expired(cfg.today())
                         || entries.contains_key(&RequiredEntry::LocalAudit { audit_index })
                 })
                 .map(|(_, entry)| entry)
@@ -2952,6 +4514,7 @@ pub(crate) fn get_store_updates(
     let mut new_imports = ImportsFile {
         unpublished: SortedMap::new(),
         publisher: SortedMap::new(),
+        hashes: SortedMap::new(),
         audits: SortedMap::new(),
     };
 
@@ -3058,6 +4621,28 @@ pub(crate) fn get_store_updates(
             // We never import trusted entries in imports.lock.
             trusted: SortedMap::new(),
         };
+
+        // Only record the hashes of the live files if the entries stored for
+        // this import (or its URLs) changed, so that new audits upstream which
+        // weren't imported don't cause imports.lock to be rewritten.
+        let existing_hashes = store.imports.hashes.get(import_name);
+        let live_hashes = store.import_hashes().get(import_name);
+        let same_urls = match (existing_hashes, live_hashes) {
+            (Some(existing), Some(live)) => existing.keys().eq(live.keys()),
+            _ => true,
+        };
+        let unchanged =
+            same_urls && store.imports.audits.get(import_name) == Some(&new_audits_file);
+        let hashes = if unchanged {
+            existing_hashes
+        } else {
+            live_hashes
+        };
+        if let Some(hashes) = hashes {
+            new_imports
+                .hashes
+                .insert(import_name.clone(), hashes.clone());
+        }
         new_imports
             .audits
             .insert(import_name.clone(), new_audits_file);
@@ -3161,7 +4746,9 @@ pub(crate) fn get_store_updates(
             };
 
             // If we're not pruning exemptions, maintain all existing criteria.
-            if !prune_exemptions {
+            // Expired exemptions are never used, so also keep them as-is until
+            // they're renewed or removed by hand.
+            if !prune_exemptions || entry.is_expired(cfg.today()) {
                 useful_criteria.unioned_with(&original_criteria);
             }
             if useful_criteria.is_empty() {
@@ -3183,6 +4770,7 @@ pub(crate) fn get_store_updates(
                         .map(|n| n.to_owned().into())
                         .collect(),
                     suggest: true,
+                    expires: None,
                     notes: None,
                 });
                 useful_criteria = original_criteria;
@@ -3196,6 +4784,7 @@ pub(crate) fn get_store_updates(
                     .map(|n| n.to_owned().into())
                     .collect(),
                 suggest: entry.suggest,
+                expires: entry.expires,
                 notes: entry.notes.clone(),
             });
         }
@@ -3229,6 +4818,7 @@ pub(crate) fn get_store_updates(
                         .map(|n| n.to_owned().into())
                         .collect(),
                     suggest: true,
+                    expires: None,
                     notes: None,
                 });
         }
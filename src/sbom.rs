@@ -0,0 +1,490 @@
+//! Software bills of materials for the dependency graph, annotated with how
+//! each package was vetted, as written by `cargo vet sbom`.
+//!
+//! Every package in the graph is included with its version, source and
+//! checksum. Third-party packages are also annotated with the criteria they
+//! satisfy, the auditors, imports and trusted publishers which vouched for
+//! them, and whether they rely on an exemption.
+
+use cargo_metadata::Metadata;
+use sha2::{Digest, Sha256};
+
+use crate::criteria::CriteriaSet;
+use crate::format::{
+    CriteriaName, CycloneDxBom, CycloneDxComponent, CycloneDxDependency, CycloneDxHash,
+    CycloneDxMetadata, CycloneDxProperty, CycloneDxTool, CycloneDxTools, FastMap, ImportName,
+    SortedSet, SpdxAnnotation, SpdxChecksum, SpdxCreationInfo, SpdxDocument, SpdxExternalRef,
+    SpdxPackage, SpdxRelationship,
+};
+use crate::network::CRATES_IO_DOWNLOAD;
+use crate::resolver::{DeltaEdgeOrigin, PackageNode, ResolveReport};
+use crate::storage::{self, Store};
+
+const CRATES_IO_SOURCE: &str = "registry+https://github.com/rust-lang/crates.io-index";
+
+/// The evidence cargo-vet has for a third-party package.
+struct VetAnnotations {
+    /// Whether every required criteria is satisfied.
+    vetted: bool,
+    required: Vec<CriteriaName>,
+    satisfied: Vec<CriteriaName>,
+    auditors: SortedSet<String>,
+    imports: SortedSet<ImportName>,
+    trusted_publishers: SortedSet<String>,
+    /// Whether an exemption is used to satisfy a required criteria.
+    exempted: bool,
+}
+
+/// A package in the dependency graph, with the information needed for either
+/// SBOM format.
+struct SbomPackage<'a> {
+    node: &'a PackageNode<'a>,
+    source: Option<String>,
+    checksum: Option<String>,
+    /// Where the package can be downloaded from, if it can be.
+    download_location: Option<String>,
+    annotations: Option<VetAnnotations>,
+}
+
+impl SbomPackage<'_> {
+    /// The package URL identifying the package, or `None` for path
+    /// dependencies, which can't be identified outside of the workspace.
+    ///
+    /// Packages from anywhere other than crates.io are qualified with the
+    /// `repository_url` of their registry, or the `vcs_url` of their git
+    /// repository.
+    fn purl(&self) -> Option<String> {
+        let source = self.source.as_deref()?;
+        let purl = format!("pkg:cargo/{}@{}", self.node.name, self.node.version.semver);
+        let qualifier = if source == CRATES_IO_SOURCE {
+            None
+        } else if let Some(vcs_url) = git_vcs_url(source) {
+            Some(("vcs_url", vcs_url))
+        } else {
+            registry_index(source).map(|index| ("repository_url", index.to_owned()))
+        };
+        Some(match qualifier {
+            Some((key, value)) => {
+                let value: String =
+                    url::form_urlencoded::byte_serialize(value.as_bytes()).collect();
+                format!("{purl}?{key}={value}")
+            }
+            None => purl,
+        })
+    }
+}
+
+/// The index URL of a registry source from `Cargo.lock`.
+fn registry_index(source: &str) -> Option<&str> {
+    source
+        .strip_prefix("registry+")
+        .or_else(|| source.strip_prefix("sparse+"))
+}
+
+/// Convert a git source from `Cargo.lock`, such as
+/// `git+https://github.com/owner/repo?branch=main#rev`, into a VCS URL in the
+/// SPDX format, `git+https://github.com/owner/repo@rev`.
+fn git_vcs_url(source: &str) -> Option<String> {
+    let url = source.strip_prefix("git+")?;
+    let (url, rev) = match url.split_once('#') {
+        Some((url, rev)) => (url, Some(rev)),
+        None => (url, None),
+    };
+    let url = url.split_once('?').map_or(url, |(url, _)| url);
+    Some(match rev {
+        Some(rev) => format!("git+{url}@{rev}"),
+        None => format!("git+{url}"),
+    })
+}
+
+/// Where a package can be downloaded from: the crate file for packages from
+/// crates.io or a configured registry, or the git repository for git packages.
+fn download_location(store: &Store, node: &PackageNode<'_>, source: &str) -> Option<String> {
+    let template = if source == CRATES_IO_SOURCE {
+        CRATES_IO_DOWNLOAD
+    } else if let Some(vcs_url) = git_vcs_url(source) {
+        return Some(vcs_url);
+    } else {
+        let registry = store.config.cargo_registry(node.registry.as_deref()?)?;
+        &registry.entry.download
+    };
+    Some(storage::registry_download_url(
+        template,
+        node.name,
+        &node.version.semver,
+    ))
+}
+
+fn collect_packages<'a>(
+    metadata: &Metadata,
+    report: &'a ResolveReport<'a>,
+    store: &Store,
+) -> Vec<SbomPackage<'a>> {
+    let packages: FastMap<_, _> = metadata
+        .packages
+        .iter()
+        .map(|package| (&package.id, package))
+        .collect();
+    let checksums = storage::cargo_lock_checksums(metadata);
+
+    report
+        .graph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(pkgidx, node)| {
+            let package = packages[node.package_id];
+            let annotations = node.is_third_party.then(|| {
+                vet_annotations(report, store, &report.requirements[pkgidx], pkgidx, node)
+            });
+            let source = package.source.as_ref().map(|source| source.repr.clone());
+            SbomPackage {
+                node,
+                download_location: source
+                    .as_deref()
+                    .and_then(|source| download_location(store, node, source)),
+                source,
+                checksum: checksums.get(&storage::cargo_lock_key(package)).cloned(),
+                annotations,
+            }
+        })
+        .collect()
+}
+
+fn vet_annotations(
+    report: &ResolveReport<'_>,
+    store: &Store,
+    required: &CriteriaSet,
+    pkgidx: usize,
+    node: &PackageNode<'_>,
+) -> VetAnnotations {
+    let mapper = &report.criteria_mapper;
+    let mut annotations = VetAnnotations {
+        vetted: false,
+        required: mapper.criteria_names(required).map(str::to_owned).collect(),
+        satisfied: Vec::new(),
+        auditors: SortedSet::new(),
+        imports: SortedSet::new(),
+        trusted_publishers: SortedSet::new(),
+        exempted: false,
+    };
+    // Packages with violation conflicts aren't resolved.
+    let Some(result) = &report.results[pkgidx] else {
+        return annotations;
+    };
+
+    annotations.vetted = true;
+    for criteria_name in mapper.all_criteria_names() {
+        let criteria_idx = mapper.criteria_index(criteria_name);
+        let is_required = required.has_criteria(criteria_idx);
+        let Ok(path) = &result.search_results[criteria_idx] else {
+            annotations.vetted &= !is_required;
+            continue;
+        };
+        annotations.satisfied.push(criteria_name.to_owned());
+        for edge in path {
+            add_edge_annotations(&mut annotations, store, node, edge, is_required);
+        }
+    }
+    annotations
+}
+
+fn add_edge_annotations(
+    annotations: &mut VetAnnotations,
+    store: &Store,
+    node: &PackageNode<'_>,
+    edge: &DeltaEdgeOrigin,
+    is_required: bool,
+) {
+    let package = node.name;
+    let who = match *edge {
+        DeltaEdgeOrigin::StoredLocalAudit { audit_index, .. } => {
+            &store.audits.audits[package][audit_index].who
+        }
+        DeltaEdgeOrigin::ImportedAudit {
+            import_index,
+            audit_index,
+        } => {
+            let (import_name, audits_file) =
+                store.imported_audits().iter().nth(import_index).unwrap();
+            annotations.imports.insert(import_name.clone());
+            &audits_file.audits[package][audit_index].who
+        }
+        DeltaEdgeOrigin::WildcardAudit {
+            import_index,
+            audit_index,
+            ..
+        } => {
+            let audits_file = match import_index {
+                Some(import_index) => {
+                    let (import_name, audits_file) =
+                        store.imported_audits().iter().nth(import_index).unwrap();
+                    annotations.imports.insert(import_name.clone());
+                    audits_file
+                }
+                None => &store.audits,
+            };
+            &audits_file.wildcard_audits[package][audit_index].who
+        }
+        DeltaEdgeOrigin::Trusted { publisher_index } => {
+            let publisher = &store.publishers()[package][publisher_index];
+            annotations
+                .trusted_publishers
+                .insert(publisher.source.as_identifier().to_owned());
+            return;
+        }
+        DeltaEdgeOrigin::Exemption { .. } | DeltaEdgeOrigin::FreshExemption { .. } => {
+            annotations.exempted |= is_required;
+            return;
+        }
+        DeltaEdgeOrigin::Unpublished { .. } => return,
+    };
+    annotations
+        .auditors
+        .extend(who.iter().map(|who| who.to_string()));
+}
+
+/// A unique identifier for the SBOM, derived from the packages in the graph so
+/// that it's stable for an unchanged `Cargo.lock`.
+fn bom_uuid(packages: &[SbomPackage<'_>]) -> String {
+    let mut hasher = Sha256::new();
+    for package in packages {
+        hasher.update(package.node.package_id.repr.as_bytes());
+        hasher.update(b"\n");
+    }
+    let hex = format!("{:x}", hasher.finalize());
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+/// Build a CycloneDX SBOM for the dependency graph.
+pub fn cyclonedx(
+    metadata: &Metadata,
+    report: &ResolveReport<'_>,
+    store: &Store,
+    now: chrono::DateTime<chrono::Utc>,
+) -> CycloneDxBom {
+    let packages = collect_packages(metadata, report, store);
+
+    let components = packages
+        .iter()
+        .map(|package| {
+            let property = |name: &str, value: String| CycloneDxProperty {
+                name: format!("cargo-vet:{name}"),
+                value,
+            };
+            let mut properties = Vec::new();
+            if let Some(source) = &package.source {
+                properties.push(property("source", source.clone()));
+            }
+            if let Some(annotations) = &package.annotations {
+                properties.push(property("vetted", annotations.vetted.to_string()));
+                for criteria in &annotations.required {
+                    properties.push(property("required-criteria", criteria.clone()));
+                }
+                for criteria in &annotations.satisfied {
+                    properties.push(property("criteria", criteria.clone()));
+                }
+                for auditor in &annotations.auditors {
+                    properties.push(property("auditor", auditor.clone()));
+                }
+                for import in &annotations.imports {
+                    properties.push(property("import", import.clone()));
+                }
+                for publisher in &annotations.trusted_publishers {
+                    properties.push(property("trusted-publisher", publisher.clone()));
+                }
+                properties.push(property("exempted", annotations.exempted.to_string()));
+            }
+            CycloneDxComponent {
+                kind: if package.node.is_workspace_member {
+                    "application".to_owned()
+                } else {
+                    "library".to_owned()
+                },
+                bom_ref: package.node.package_id.repr.clone(),
+                name: package.node.name.to_owned(),
+                version: package.node.version.semver.to_string(),
+                purl: package.purl(),
+                hashes: package
+                    .checksum
+                    .iter()
+                    .map(|checksum| CycloneDxHash {
+                        alg: "SHA-256".to_owned(),
+                        content: checksum.clone(),
+                    })
+                    .collect(),
+                properties,
+            }
+        })
+        .collect();
+
+    let dependencies = packages
+        .iter()
+        .map(|package| CycloneDxDependency {
+            bom_ref: package.node.package_id.repr.clone(),
+            depends_on: package
+                .node
+                .all_deps
+                .iter()
+                .map(|&dep| report.graph.nodes[dep].package_id.repr.clone())
+                .collect(),
+        })
+        .collect();
+
+    CycloneDxBom {
+        bom_format: "CycloneDX".to_owned(),
+        spec_version: "1.5".to_owned(),
+        serial_number: format!("urn:uuid:{}", bom_uuid(&packages)),
+        version: 1,
+        metadata: CycloneDxMetadata {
+            timestamp: now.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            tools: CycloneDxTools {
+                components: vec![CycloneDxTool {
+                    kind: "application".to_owned(),
+                    name: "cargo-vet".to_owned(),
+                    version: env!("CARGO_PKG_VERSION").to_owned(),
+                }],
+            },
+        },
+        components,
+        dependencies,
+    }
+}
+
+/// Build an SPDX SBOM for the dependency graph.
+pub fn spdx(
+    metadata: &Metadata,
+    report: &ResolveReport<'_>,
+    store: &Store,
+    now: chrono::DateTime<chrono::Utc>,
+) -> SpdxDocument {
+    let packages = collect_packages(metadata, report, store);
+    let created = now.to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let creator = format!("Tool: cargo-vet-{}", env!("CARGO_PKG_VERSION"));
+
+    // SPDX identifiers may only contain letters, numbers, `.` and `-`.
+    let spdx_ids: Vec<String> = packages
+        .iter()
+        .enumerate()
+        .map(|(pkgidx, package)| {
+            let id: String = format!("{}-{}", package.node.name, package.node.version)
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || c == '.' {
+                        c
+                    } else {
+                        '-'
+                    }
+                })
+                .collect();
+            format!("SPDXRef-Package-{pkgidx}-{id}")
+        })
+        .collect();
+
+    let spdx_packages = packages
+        .iter()
+        .zip(&spdx_ids)
+        .map(|(package, spdx_id)| {
+            let download_location = package
+                .download_location
+                .clone()
+                .unwrap_or_else(|| "NOASSERTION".to_owned());
+            let annotations = package.annotations.iter().map(|annotations| {
+                let mut comment = vec![
+                    "cargo-vet audit status".to_owned(),
+                    format!("vetted: {}", annotations.vetted),
+                    format!("required criteria: {}", annotations.required.join(", ")),
+                    format!("satisfied criteria: {}", annotations.satisfied.join(", ")),
+                ];
+                let sets = [
+                    ("auditors", &annotations.auditors),
+                    ("imports", &annotations.imports),
+                    ("trusted publishers", &annotations.trusted_publishers),
+                ];
+                for (label, set) in sets {
+                    if !set.is_empty() {
+                        let items: Vec<_> = set.iter().cloned().collect();
+                        comment.push(format!("{label}: {}", items.join(", ")));
+                    }
+                }
+                comment.push(format!("exempted: {}", annotations.exempted));
+                SpdxAnnotation {
+                    annotation_date: created.clone(),
+                    annotation_type: "REVIEW".to_owned(),
+                    annotator: creator.clone(),
+                    comment: comment.join("\n"),
+                }
+            });
+            SpdxPackage {
+                spdx_id: spdx_id.clone(),
+                name: package.node.name.to_owned(),
+                version_info: package.node.version.semver.to_string(),
+                download_location,
+                files_analyzed: false,
+                source_info: package.source.clone(),
+                checksums: package
+                    .checksum
+                    .iter()
+                    .map(|checksum| SpdxChecksum {
+                        algorithm: "SHA256".to_owned(),
+                        checksum_value: checksum.clone(),
+                    })
+                    .collect(),
+                external_refs: package
+                    .purl()
+                    .into_iter()
+                    .map(|purl| SpdxExternalRef {
+                        reference_category: "PACKAGE-MANAGER".to_owned(),
+                        reference_type: "purl".to_owned(),
+                        reference_locator: purl,
+                    })
+                    .collect(),
+                annotations: annotations.collect(),
+            }
+        })
+        .collect();
+
+    let mut relationships = Vec::new();
+    for (package, spdx_id) in packages.iter().zip(&spdx_ids) {
+        if package.node.is_workspace_member {
+            relationships.push(SpdxRelationship {
+                spdx_element_id: "SPDXRef-DOCUMENT".to_owned(),
+                relationship_type: "DESCRIBES".to_owned(),
+                related_spdx_element: spdx_id.clone(),
+            });
+        }
+        for &dep in &package.node.all_deps {
+            relationships.push(SpdxRelationship {
+                spdx_element_id: spdx_id.clone(),
+                relationship_type: "DEPENDS_ON".to_owned(),
+                related_spdx_element: spdx_ids[dep].clone(),
+            });
+        }
+    }
+
+    let name = metadata
+        .workspace_root
+        .file_name()
+        .unwrap_or("workspace")
+        .to_owned();
+    SpdxDocument {
+        spdx_version: "SPDX-2.3".to_owned(),
+        data_license: "CC0-1.0".to_owned(),
+        spdx_id: "SPDXRef-DOCUMENT".to_owned(),
+        document_namespace: format!("https://spdx.org/spdxdocs/{name}-{}", bom_uuid(&packages)),
+        name,
+        creation_info: SpdxCreationInfo {
+            created,
+            creators: vec![creator],
+        },
+        packages: spdx_packages,
+        relationships,
+    }
+}
@@ -303,6 +303,12 @@ pub mod audit {
         version: Option<VetVersion>,
         delta: Option<Delta>,
         violation: Option<VersionReq>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        expires: Option<chrono::NaiveDate>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        registry: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        checksum: Option<String>,
         importable: Option<bool>,
         notes: Option<String>,
         #[serde(rename = "aggregated-from")]
@@ -315,6 +321,12 @@ pub mod audit {
     impl TryFrom<AuditEntryAll> for AuditEntry {
         type Error = String;
         fn try_from(val: AuditEntryAll) -> Result<AuditEntry, Self::Error> {
+            if val.violation.is_some() && val.expires.is_some() {
+                return Err("'expires' cannot be used with 'violation' entries".to_string());
+            }
+            if val.violation.is_some() && val.checksum.is_some() {
+                return Err("'checksum' cannot be used with 'violation' entries".to_string());
+            }
             let kind = match (val.version, val.delta, val.violation) {
                 (Some(version), None, None) => Ok(AuditKind::Full { version }),
                 (None, Some(delta), None) => {
@@ -335,6 +347,9 @@ pub mod audit {
                 notes: val.notes,
                 criteria: val.criteria,
                 kind: kind?,
+                expires: val.expires,
+                registry: val.registry,
+                checksum: val.checksum,
                 importable: val.importable.unwrap_or(true),
                 aggregated_from: val.aggregated_from,
                 // By default, always read entries as non-fresh. The import code
@@ -365,6 +380,9 @@ pub mod audit {
                 version,
                 delta,
                 violation,
+                expires: val.expires,
+                registry: val.registry,
+                checksum: val.checksum,
                 importable: if val.importable { None } else { Some(false) },
                 aggregated_from: val.aggregated_from,
             }
@@ -868,7 +886,9 @@ mod test {
                 audit_as_crates_io: None,
                 criteria: Some(vec!["long-criteria".to_owned().into()]),
                 dev_criteria: None,
+                build_criteria: None,
                 dependency_criteria: dc_long,
+                min_auditors: Some(2),
                 notes: Some("notes go here!".to_owned()),
             }),
         );
@@ -878,7 +898,9 @@ mod test {
                 audit_as_crates_io: None,
                 criteria: Some(vec!["short-criteria".to_owned().into()]),
                 dev_criteria: None,
+                build_criteria: Some(vec!["short-criteria".to_owned().into()]),
                 dependency_criteria: dc_short,
+                min_auditors: None,
                 notes: Some("notes go here!".to_owned()),
             }),
         );
@@ -889,7 +911,10 @@ mod test {
                     version: StoreVersion { major: 1, minor: 0 },
                 },
                 default_criteria: get_default_criteria(),
+                targets: Vec::new(),
                 imports: SortedMap::new(),
+                registries: SortedMap::new(),
+                advisory_db: None,
                 policy,
                 exemptions: SortedMap::new(),
             },
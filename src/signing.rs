@@ -0,0 +1,95 @@
+//! Detached signatures for imported audit files.
+//!
+//! Imports may declare a `public-key`, in which case the audits file must be
+//! accompanied by a detached signature, which is verified before the file is
+//! parsed. Both OpenSSH (`ssh-keygen -Y sign`) and minisign signatures are
+//! supported.
+
+use std::path::Path;
+
+use ssh_key::{HashAlg, LineEnding, PrivateKey, SshSig};
+
+use crate::errors::SignatureError;
+
+/// The namespace used for SSH signatures, as passed to `ssh-keygen -Y sign -n`.
+pub const SSH_SIGNATURE_NAMESPACE: &str = "cargo-vet";
+
+/// A public key which an import's audits must be signed with.
+pub enum ImportKey {
+    Ssh(ssh_key::PublicKey),
+    Minisign(minisign_verify::PublicKey),
+}
+
+impl ImportKey {
+    /// Parse a key from the `public-key` field of an import. This is either
+    /// an OpenSSH public key (e.g. `ssh-ed25519 AAAA...`), or a minisign
+    /// public key, optionally preceded by its `untrusted comment:` line.
+    pub fn parse(key: &str) -> Result<Self, SignatureError> {
+        let key = key.trim();
+        if key.starts_with("ssh-") || key.starts_with("sk-") {
+            ssh_key::PublicKey::from_openssh(key)
+                .map(ImportKey::Ssh)
+                .map_err(SignatureError::Ssh)
+        } else {
+            let key = key.lines().last().unwrap_or_default().trim();
+            minisign_verify::PublicKey::from_base64(key)
+                .map(ImportKey::Minisign)
+                .map_err(SignatureError::Minisign)
+        }
+    }
+
+    /// The extension added to the location of a file to find its detached
+    /// signature. SSH signatures are expected at `.sig`, and minisign
+    /// signatures at `.minisig`, matching the default output paths of
+    /// `ssh-keygen` and `minisign`.
+    pub fn signature_extension(&self) -> &'static str {
+        match self {
+            ImportKey::Ssh(_) => "sig",
+            ImportKey::Minisign(_) => "minisig",
+        }
+    }
+
+    /// Check that `signature` is a valid signature of `data` made with this key.
+    pub fn verify(&self, data: &[u8], signature: &str) -> Result<(), SignatureError> {
+        match self {
+            ImportKey::Ssh(key) => {
+                let signature = SshSig::from_pem(signature).map_err(SignatureError::Ssh)?;
+                if signature.public_key() != key.key_data() {
+                    return Err(SignatureError::WrongKey);
+                }
+                key.verify(SSH_SIGNATURE_NAMESPACE, data, &signature)
+                    .map_err(|error| match error {
+                        ssh_key::Error::Crypto => SignatureError::Mismatch,
+                        error => SignatureError::Ssh(error),
+                    })
+            }
+            ImportKey::Minisign(key) => {
+                let signature = minisign_verify::Signature::decode(signature)
+                    .map_err(SignatureError::Minisign)?;
+                key.verify(data, &signature, false)
+                    .map_err(|error| match error {
+                        minisign_verify::Error::UnexpectedKeyId => SignatureError::WrongKey,
+                        minisign_verify::Error::InvalidSignature => SignatureError::Mismatch,
+                        error => SignatureError::Minisign(error),
+                    })
+            }
+        }
+    }
+}
+
+/// Sign `data` with the unencrypted OpenSSH private key at `key_path`,
+/// producing the same detached signature as `ssh-keygen -Y sign -n cargo-vet`.
+pub fn sign_with_ssh_key(key_path: &Path, data: &[u8]) -> Result<String, SignatureError> {
+    let key = PrivateKey::read_openssh_file(key_path).map_err(|error| SignatureError::ReadKey {
+        path: key_path.to_owned(),
+        error,
+    })?;
+    if key.is_encrypted() {
+        return Err(SignatureError::EncryptedKey {
+            path: key_path.to_owned(),
+        });
+    }
+    key.sign(SSH_SIGNATURE_NAMESPACE, HashAlg::Sha512, data)
+        .and_then(|signature| signature.to_pem(LineEnding::LF))
+        .map_err(SignatureError::Ssh)
+}
@@ -0,0 +1,395 @@
+//! Coverage statistics for the resolved graph, as printed by `cargo vet stats`.
+//!
+//! Each third-party package is sorted into the same buckets as a successful
+//! `cargo vet` run's JSON output (vetted fully, partially or with exemptions),
+//! along with unvetted packages and those with violation conflicts. The
+//! buckets are counted both by number of packages and by lines of code, and
+//! are also broken down by criteria and by the source of the audits used.
+
+use std::io;
+use std::sync::Arc;
+
+use futures_util::future::join_all;
+
+use crate::errors::CacheAcquireError;
+use crate::format::{
+    Delta, ImportName, JsonStats, JsonStatsCount, JsonStatsCoverage, JsonStatsSources,
+};
+use crate::network::Network;
+use crate::out::{progress_bar, IncProgressOnDrop, Out};
+use crate::resolver::{Conclusion, DeltaEdgeOrigin, ResolveReport, VettedBucket};
+use crate::storage::{Cache, Store};
+use crate::Config;
+
+/// How a package, or a single criteria of a package, was vetted.
+#[derive(Copy, Clone)]
+enum Bucket {
+    Vetted(VettedBucket),
+    Unvetted,
+    ViolationConflict,
+}
+
+/// Running totals of packages and lines, which are turned into a
+/// `JsonStatsCount` once the totals for the whole graph are known.
+#[derive(Copy, Clone, Default)]
+struct Tally {
+    packages: u64,
+    lines: u64,
+}
+
+impl Tally {
+    fn add(&mut self, lines: u64) {
+        self.packages += 1;
+        self.lines += lines;
+    }
+
+    fn count(self, total: Tally) -> JsonStatsCount {
+        let percent = |count: u64, total: u64| {
+            if total == 0 {
+                0.0
+            } else {
+                (count as f64 * 1000.0 / total as f64).round() / 10.0
+            }
+        };
+        JsonStatsCount {
+            packages: self.packages,
+            packages_percent: percent(self.packages, total.packages),
+            lines: self.lines,
+            lines_percent: percent(self.lines, total.lines),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Default)]
+struct CoverageTally {
+    total: Tally,
+    fully: Tally,
+    partially: Tally,
+    exemptions: Tally,
+    unvetted: Tally,
+    violation_conflicts: Tally,
+}
+
+impl CoverageTally {
+    fn add(&mut self, bucket: Bucket, lines: u64) {
+        self.total.add(lines);
+        match bucket {
+            Bucket::Vetted(VettedBucket::Fully) => self.fully.add(lines),
+            Bucket::Vetted(VettedBucket::Partially) => self.partially.add(lines),
+            Bucket::Vetted(VettedBucket::Exemptions) => self.exemptions.add(lines),
+            Bucket::Unvetted => self.unvetted.add(lines),
+            Bucket::ViolationConflict => self.violation_conflicts.add(lines),
+        }
+    }
+
+    fn coverage(self) -> JsonStatsCoverage {
+        JsonStatsCoverage {
+            total: self.total.count(self.total),
+            vetted_fully: self.fully.count(self.total),
+            vetted_partially: self.partially.count(self.total),
+            vetted_with_exemptions: self.exemptions.count(self.total),
+            unvetted: self.unvetted.count(self.total),
+            violation_conflicts: self.violation_conflicts.count(self.total),
+        }
+    }
+}
+
+/// Compute coverage statistics for the third-party packages in the report.
+///
+/// The lines of code of each package are measured from the full contents of
+/// the crate, fetching it if necessary. Packages which can't be fetched are
+/// counted as having no lines, and are reported in `unknown_lines`.
+pub fn compute_stats(
+    cfg: &Config,
+    report: &ResolveReport<'_>,
+    store: &Store,
+    network: Option<&Network>,
+) -> Result<JsonStats, CacheAcquireError> {
+    let cache = Cache::acquire(cfg)?;
+    let import_names: Vec<&ImportName> = store.imported_audits().keys().collect();
+
+    let third_party: Vec<_> = report
+        .graph
+        .nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, package)| package.is_third_party)
+        .collect();
+
+    let progress = progress_bar("Measuring", "packages", third_party.len() as u64);
+    let lines: Vec<Option<u64>> = tokio::runtime::Handle::current().block_on(join_all(
+        third_party.iter().map(|(_, package)| async {
+            let _guard = IncProgressOnDrop(&progress, 1);
+            let cargo_registry = package
+                .registry
+                .as_deref()
+                .and_then(|name| store.config.cargo_registry(name));
+            let delta = Delta {
+                from: None,
+                to: package.version.clone(),
+            };
+            cache
+                .fetch_and_diffstat_package(
+                    &cfg.metadata,
+                    network,
+                    cargo_registry,
+                    package.name,
+                    &delta,
+                )
+                .await
+                // The cache logs fetch errors itself, and the packages are
+                // counted in `unknown_lines` below.
+                .ok()
+                .map(|diffstat| diffstat.count())
+        }),
+    ));
+    drop(progress);
+
+    // Packages are bucketed exactly as in the report, unless it failed with a
+    // violation conflict, in which case the other packages were never bucketed.
+    let vetted = match &report.conclusion {
+        Conclusion::Success(success) => Some(success),
+        Conclusion::FailForVet(fail) => Some(&fail.vetted),
+        Conclusion::FailForViolationConflict(_) => None,
+    };
+
+    let mut coverage = CoverageTally::default();
+    let mut criteria = vec![CoverageTally::default(); report.criteria_mapper.len()];
+    let mut local_audits = Tally::default();
+    let mut imports = vec![Tally::default(); import_names.len()];
+    let mut wildcard_audits = Tally::default();
+    let mut trusted = Tally::default();
+    let mut exemptions = Tally::default();
+    let mut unknown_lines = 0;
+
+    for (&(pkgidx, _), lines) in third_party.iter().zip(lines) {
+        let lines = lines.unwrap_or_else(|| {
+            unknown_lines += 1;
+            0
+        });
+        let required_criteria = &report.requirements[pkgidx];
+
+        let Some(result) = &report.results[pkgidx] else {
+            // Packages with violation conflicts have no results.
+            coverage.add(Bucket::ViolationConflict, lines);
+            for criteria_idx in required_criteria.indices() {
+                criteria[criteria_idx].add(Bucket::ViolationConflict, lines);
+            }
+            continue;
+        };
+
+        let mut paths = Vec::new();
+        let mut unvetted = false;
+        for criteria_idx in required_criteria.indices() {
+            let bucket = match &result.search_results[criteria_idx] {
+                Ok(path) => {
+                    paths.push(&path[..]);
+                    Bucket::Vetted(VettedBucket::for_paths([&path[..]]))
+                }
+                Err(_) => {
+                    unvetted = true;
+                    Bucket::Unvetted
+                }
+            };
+            criteria[criteria_idx].add(bucket, lines);
+        }
+
+        let bucket = match vetted {
+            Some(vetted) => vetted.bucket(pkgidx),
+            None if unvetted => None,
+            None => Some(VettedBucket::for_paths(paths.iter().copied())),
+        };
+        let Some(bucket) = bucket else {
+            coverage.add(Bucket::Unvetted, lines);
+            continue;
+        };
+        coverage.add(Bucket::Vetted(bucket), lines);
+
+        // Count each source once per package, even if it's used for several
+        // criteria or several edges.
+        let mut used_local = false;
+        let mut used_imports = vec![false; import_names.len()];
+        let mut used_wildcard = false;
+        let mut used_trusted = false;
+        let mut used_exemption = false;
+        for origin in paths.into_iter().flatten() {
+            match origin {
+                DeltaEdgeOrigin::StoredLocalAudit { .. } => used_local = true,
+                DeltaEdgeOrigin::ImportedAudit { import_index, .. } => {
+                    used_imports[*import_index] = true
+                }
+                DeltaEdgeOrigin::WildcardAudit { import_index, .. } => {
+                    used_wildcard = true;
+                    if let Some(import_index) = import_index {
+                        used_imports[*import_index] = true;
+                    }
+                }
+                DeltaEdgeOrigin::Trusted { .. } => used_trusted = true,
+                DeltaEdgeOrigin::Exemption { .. } | DeltaEdgeOrigin::FreshExemption { .. } => {
+                    used_exemption = true
+                }
+                DeltaEdgeOrigin::Unpublished { .. } => {}
+            }
+        }
+        for (used, tally) in [
+            (used_local, &mut local_audits),
+            (used_wildcard, &mut wildcard_audits),
+            (used_trusted, &mut trusted),
+            (used_exemption, &mut exemptions),
+        ] {
+            if used {
+                tally.add(lines);
+            }
+        }
+        for (used, tally) in used_imports.into_iter().zip(&mut imports) {
+            if used {
+                tally.add(lines);
+            }
+        }
+    }
+
+    let total = coverage.total;
+    Ok(JsonStats {
+        coverage: coverage.coverage(),
+        criteria: criteria
+            .into_iter()
+            .enumerate()
+            .filter(|(_, tally)| tally.total.packages > 0)
+            .map(|(criteria_idx, tally)| {
+                (
+                    report
+                        .criteria_mapper
+                        .criteria_name(criteria_idx)
+                        .to_owned(),
+                    tally.coverage(),
+                )
+            })
+            .collect(),
+        sources: JsonStatsSources {
+            local_audits: local_audits.count(total),
+            imports: import_names
+                .into_iter()
+                .zip(imports)
+                .filter(|(_, tally)| tally.packages > 0)
+                .map(|(import_name, tally)| (import_name.clone(), tally.count(total)))
+                .collect(),
+            wildcard_audits: wildcard_audits.count(total),
+            trusted: trusted.count(total),
+            exemptions: exemptions.count(total),
+        },
+        unknown_lines,
+    })
+}
+
+/// Print a human-readable summary of the coverage statistics.
+pub fn print_human(out: &Arc<dyn Out>, stats: &JsonStats) -> Result<(), io::Error> {
+    // Line the counts up in a column, making room for long import names.
+    let width = stats
+        .sources
+        .imports
+        .keys()
+        .map(|import_name| format!("imported from {import_name}: ").len())
+        .max()
+        .unwrap_or(0)
+        .max(24);
+
+    let total = &stats.coverage.total;
+    writeln!(
+        out,
+        "{} third-party packages ({} lines):",
+        total.packages, total.lines
+    );
+    print_coverage(out, "  ", width, &stats.coverage);
+
+    if !stats.criteria.is_empty() {
+        writeln!(out);
+        writeln!(out, "By criteria:");
+        for (criteria, coverage) in &stats.criteria {
+            writeln!(
+                out,
+                "  {criteria} (required by {} packages, {} lines):",
+                coverage.total.packages, coverage.total.lines
+            );
+            print_coverage(out, "    ", width, coverage);
+        }
+    }
+
+    writeln!(out);
+    writeln!(out, "By source:");
+    let sources = &stats.sources;
+    print_count(out, "  ", width, "local audits", &sources.local_audits);
+    for (import_name, count) in &sources.imports {
+        print_count(
+            out,
+            "  ",
+            width,
+            &format!("imported from {import_name}"),
+            count,
+        );
+    }
+    print_count(
+        out,
+        "  ",
+        width,
+        "wildcard audits",
+        &sources.wildcard_audits,
+    );
+    print_count(out, "  ", width, "trusted publishers", &sources.trusted);
+    print_count(out, "  ", width, "exemptions", &sources.exemptions);
+
+    if stats.unknown_lines > 0 {
+        writeln!(out);
+        writeln!(
+            out,
+            "The size of {} packages couldn't be determined, so they are counted as having no lines.",
+            stats.unknown_lines
+        );
+    }
+    Ok(())
+}
+
+fn print_coverage(out: &Arc<dyn Out>, indent: &str, width: usize, coverage: &JsonStatsCoverage) {
+    print_count(out, indent, width, "vetted fully", &coverage.vetted_fully);
+    print_count(
+        out,
+        indent,
+        width,
+        "vetted partially",
+        &coverage.vetted_partially,
+    );
+    print_count(
+        out,
+        indent,
+        width,
+        "vetted with exemptions",
+        &coverage.vetted_with_exemptions,
+    );
+    print_count(out, indent, width, "unvetted", &coverage.unvetted);
+    if coverage.violation_conflicts.packages > 0 {
+        print_count(
+            out,
+            indent,
+            width,
+            "violation conflicts",
+            &coverage.violation_conflicts,
+        );
+    }
+}
+
+fn print_count(
+    out: &Arc<dyn Out>,
+    indent: &str,
+    width: usize,
+    label: &str,
+    count: &JsonStatsCount,
+) {
+    writeln!(
+        out,
+        "{indent}{:<width$}{:>5} packages ({:>5.1}%) {:>8} lines ({:>5.1}%)",
+        format!("{label}:"),
+        count.packages,
+        count.packages_percent,
+        count.lines,
+        count.lines_percent
+    );
+}
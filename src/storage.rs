@@ -1,5 +1,6 @@
 use std::{
     ffi::{OsStr, OsString},
+    fmt,
     fs::{self, File, OpenOptions},
     io::{self, BufReader, Read, Seek, Write},
     mem,
@@ -13,6 +14,7 @@ use flate2::read::GzDecoder;
 use futures_util::future::{join_all, try_join_all};
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use similar::{udiff::unified_diff, Algorithm};
 use tar::Archive;
 use tracing::{error, info, log::warn, trace};
@@ -21,28 +23,34 @@ use crate::{
     cli::FetchMode,
     criteria::CriteriaMapper,
     errors::{
-        AggregateError, BadFormatError, BadWildcardEndDateError, CacheAcquireError,
-        CacheCommitError, CertifyError, CommandError, CrateInfoError, CriteriaChangeError,
-        CriteriaChangeErrors, DiffError, DownloadError, FetchAndDiffError,
-        FetchAuditAggregateError, FetchAuditError, FetchError, FetchRegistryError, FlockError,
-        InvalidCriteriaError, JsonParseError, LoadJsonError, LoadTomlError, SourceFile,
-        StoreAcquireError, StoreCommitError, StoreCreateError, StoreJsonError, StoreTomlError,
-        StoreValidateError, StoreValidateErrors, TomlParseError, UnpackCheckoutError, UnpackError,
+        AdvisoryDbError, AggregateCriteriaDescription, AggregateCriteriaDescriptionMismatchError,
+        AggregateCriteriaImplies, AggregateError, AggregateErrors, AggregateImpliesMismatchError,
+        BadFormatError, BadWildcardEndDateError, BundleError, CacheAcquireError, CacheCommitError,
+        CertifyError, CommandError, CrateInfoError, CriteriaChangeError, CriteriaChangeErrors,
+        DiffError, DownloadError, FetchAndDiffError, FetchAuditAggregateError, FetchAuditError,
+        FetchError, FetchRegistryError, FlockError, GitImportError, ImportChangedError,
+        ImportsChangedError, InvalidCriteriaError, JsonParseError, LoadJsonError, LoadTomlError,
+        SourceFile, StoreAcquireError, StoreCommitError, StoreCreateError, StoreJsonError,
+        StoreTomlError, StoreValidateError, StoreValidateErrors, TomlParseError,
+        UnpackCheckoutError, UnpackError, VendorError, VendorErrors,
     },
     flock::{FileLock, Filesystem},
     format::{
-        self, AuditEntry, AuditedDependencies, AuditsFile, CommandHistory, ConfigFile,
-        CratesAPICrate, CratesCache, CratesCacheEntry, CratesCacheUser, CratesCacheVersionDetails,
-        CratesPublisher, CratesPublisherSource, CratesSourceId, CratesUserId, CriteriaEntry,
-        CriteriaMap, CriteriaName, CriteriaStr, Delta, DiffCache, DiffStat, FastMap, FastSet,
-        FetchCommand, ForeignAuditsFile, ImportName, ImportsFile, MetaConfig, PackageName,
-        PackageStr, RegistryEntry, RegistryFile, SortedMap, StoreVersion, TrustEntry,
-        TrustedPackages, UnpublishedEntry, VetVersion, WildcardAudits, WildcardEntry,
-        SAFE_TO_DEPLOY, SAFE_TO_RUN,
+        self, Advisory, AdvisoryId, AuditEntry, AuditKind, AuditedDependencies, AuditsFile,
+        CargoRegistry, CommandHistory, ConfigFile, CratesAPICrate, CratesCache, CratesCacheEntry,
+        CratesCacheUser, CratesCacheVersionDetails, CratesPublisher, CratesPublisherSource,
+        CratesSourceId, CratesUserId, CriteriaEntry, CriteriaMap, CriteriaName, CriteriaStr, Delta,
+        DiffCache, DiffStat, FastMap, FastSet, FetchCommand, ForeignAuditsFile, ImportHash,
+        ImportHashes, ImportName, ImportsFile, MetaConfig, PackageName, PackageStr, RegistryEntry,
+        RegistryFile, RemoteImport, SortedMap, StoreVersion, TrustEntry, TrustedPackages,
+        UnpublishedEntry, VersionReq, VetVersion, WildcardAudits, WildcardEntry, SAFE_TO_DEPLOY,
+        SAFE_TO_RUN,
     },
+    git_tool::run_git,
     network::Network,
     out::{progress_bar, IncProgressOnDrop},
     serialization::{parse_from_table, spanned::Spanned, to_formatted_toml, Tidyable},
+    signing::ImportKey,
     Config, PackageExt, PartialConfig, CARGO_ENV,
 };
 
@@ -54,6 +62,7 @@ const CACHE_EMPTY_PACKAGE: &str = "empty";
 const CACHE_REGISTRY_SRC: &str = "src";
 const CACHE_REGISTRY_CACHE: &str = "cache";
 const CACHE_VET_LOCK: &str = ".vet-lock";
+const CACHE_GIT_IMPORTS: &str = "git-imports";
 
 // Files which are allowed to appear in the root of the cache directory, and
 // will not be GC'd
@@ -65,16 +74,20 @@ const CACHE_ALLOWED_FILES: &[&str] = &[
     CACHE_REGISTRY_SRC,
     CACHE_REGISTRY_CACHE,
     CACHE_VET_LOCK,
+    CACHE_GIT_IMPORTS,
 ];
 
 // Various cargo values
 const CARGO_REGISTRY: &str = "registry";
 const CARGO_REGISTRY_SRC: &str = "src";
+const CARGO_REGISTRY_CACHE: &str = "cache";
 const CARGO_REGISTRY_CRATES_IO_GIT: &str = "github.com-1ecc6299db9ec823";
 const CARGO_REGISTRY_CRATES_IO_HTTP: &str = "index.crates.io-6f17d22bba15001f";
 const CARGO_TOML_FILE: &str = "Cargo.toml";
 const CARGO_OK_FILE: &str = ".cargo-ok";
 const CARGO_OK_BODY: &str = "ok";
+const CARGO_CHECKSUM_FILE: &str = ".cargo-checksum.json";
+const CARGO_LOCK_FILE: &str = "Cargo.lock";
 
 pub const DEFAULT_STORE: &str = "supply-chain";
 
@@ -82,8 +95,20 @@ const AUDITS_TOML: &str = "audits.toml";
 const CONFIG_TOML: &str = "config.toml";
 const IMPORTS_LOCK: &str = "imports.lock";
 
+// Entries in the archives written by `cargo vet bundle export`
+const BUNDLE_IMPORTS: &str = "imports.lock";
+const BUNDLE_CRATES_IO_CACHE: &str = "crates-io-cache.json";
+const BUNDLE_DIFF_CACHE: &str = "diff-cache.toml";
+// Directory holding the bundled `.crate` files
+const BUNDLE_CRATES: &str = "crates";
+
 // Files which are skipped when counting changes for diffs.
-const DIFF_SKIP_PATHS: &[&str] = &["Cargo.lock", ".cargo_vcs_info.json", ".cargo-ok"];
+const DIFF_SKIP_PATHS: &[&str] = &[
+    "Cargo.lock",
+    ".cargo_vcs_info.json",
+    ".cargo-ok",
+    ".cargo-checksum.json",
+];
 
 // FIXME: This is a completely arbitrary number, and may be too high or too low.
 const MAX_CONCURRENT_DIFFS: usize = 40;
@@ -148,6 +173,10 @@ pub struct Store {
     // initialized to `None` if `--locked` was passed.
     pub live_imports: Option<ImportsFile>,
 
+    // Synthetic violations for locked crates which are affected by an advisory
+    // in the configured advisory database, along with the advisory's ID.
+    pub advisory_violations: SortedMap<PackageName, Vec<(AdvisoryId, AuditEntry)>>,
+
     pub config_src: SourceFile,
     pub imports_src: SourceFile,
     pub audits_src: SourceFile,
@@ -163,16 +192,11 @@ impl Store {
 
         Ok(Self {
             lock: Some(lock),
-            config: ConfigFile {
-                cargo_vet: Default::default(),
-                default_criteria: format::get_default_criteria(),
-                imports: SortedMap::new(),
-                policy: Default::default(),
-                exemptions: SortedMap::new(),
-            },
+            config: ConfigFile::default(),
             imports: ImportsFile {
                 unpublished: SortedMap::new(),
                 publisher: SortedMap::new(),
+                hashes: SortedMap::new(),
                 audits: SortedMap::new(),
             },
             audits: AuditsFile {
@@ -182,6 +206,7 @@ impl Store {
                 trusted: SortedMap::new(),
             },
             live_imports: None,
+            advisory_violations: SortedMap::new(),
             config_src: SourceFile::new_empty(CONFIG_TOML),
             audits_src: SourceFile::new_empty(AUDITS_TOML),
             imports_src: SourceFile::new_empty(IMPORTS_LOCK),
@@ -193,6 +218,66 @@ impl Store {
         metacfg.store_path().as_path_unlocked().exists()
     }
 
+    /// Read the top-level `targets` list from the store's config.toml, without
+    /// acquiring the store.
+    ///
+    /// This is used before the store is acquired, as the targets affect how
+    /// `cargo metadata` is invoked.
+    pub fn configured_targets(metacfg: &MetaConfig) -> Result<Vec<String>, StoreAcquireError> {
+        let path = metacfg.store_path().as_path_unlocked().join(CONFIG_TOML);
+        let (_, config): (_, ConfigFile) = load_toml(CONFIG_TOML, File::open(path)?)?;
+        Ok(config.targets)
+    }
+
+    /// Load a read-only copy of a store from an arbitrary directory, such as a
+    /// checkout of an older revision of the repository.
+    ///
+    /// Unlike `Store::acquire_offline`, this does not take the store lock, and
+    /// the resulting store cannot be committed to disk.
+    pub fn acquire_snapshot(
+        root: &Path,
+        metadata: &cargo_metadata::Metadata,
+        today: chrono::NaiveDate,
+    ) -> Result<Self, StoreAcquireError> {
+        let mut store = Self::load_snapshot(root)?;
+
+        if let Some(advisory_db) = &store.config.advisory_db {
+            let advisories = load_advisory_db(&root.join(&advisory_db.path))?;
+            store.add_advisories(metadata, advisories);
+        }
+
+        store.validate(today, false)?;
+
+        Ok(store)
+    }
+
+    /// Read the files of a store from an arbitrary directory, without loading
+    /// advisories or validating it. This is enough to compare the contents of
+    /// two stores, but not to resolve with.
+    pub fn load_snapshot(root: &Path) -> Result<Self, StoreAcquireError> {
+        let (config_src, config): (_, ConfigFile) =
+            load_toml(CONFIG_TOML, File::open(root.join(CONFIG_TOML))?)?;
+        if config.cargo_vet.version > StoreVersion::current() {
+            return Err(StoreAcquireError::NewerStore(config.cargo_vet.version));
+        }
+        let (audits_src, audits): (_, AuditsFile) =
+            load_toml(AUDITS_TOML, File::open(root.join(AUDITS_TOML))?)?;
+        let (imports_src, imports): (_, ImportsFile) =
+            load_toml(IMPORTS_LOCK, File::open(root.join(IMPORTS_LOCK))?)?;
+
+        Ok(Self {
+            lock: None,
+            config,
+            audits,
+            imports,
+            live_imports: None,
+            advisory_violations: SortedMap::new(),
+            config_src,
+            audits_src,
+            imports_src,
+        })
+    }
+
     pub fn acquire_offline(cfg: &Config) -> Result<Self, StoreAcquireError> {
         let root = cfg.metacfg.store_path();
 
@@ -220,17 +305,23 @@ impl Store {
         let (imports_src, imports): (_, ImportsFile) =
             load_toml(IMPORTS_LOCK, lock.read_imports()?)?;
 
-        let store = Self {
+        let mut store = Self {
             lock: Some(lock),
             config,
             audits,
             imports,
             live_imports: None,
+            advisory_violations: SortedMap::new(),
             config_src,
             audits_src,
             imports_src,
         };
 
+        if let Some(advisory_db) = &store.config.advisory_db {
+            let advisories = load_advisory_db(&root.as_path_unlocked().join(&advisory_db.path))?;
+            store.add_advisories(&cfg.metadata, advisories);
+        }
+
         // Check that the store isn't corrupt
         store.validate(cfg.today(), cfg.cli.locked)?;
 
@@ -240,7 +331,8 @@ impl Store {
     /// Acquire an existing store
     ///
     /// If `network` is passed and `!cfg.cli.locked`, this will fetch remote
-    /// imports to use for comparison purposes.
+    /// imports to use for comparison purposes. If `network` isn't passed and
+    /// every import is local, they're re-read from disk instead.
     pub fn acquire(
         cfg: &Config,
         network: Option<&Network>,
@@ -256,11 +348,75 @@ impl Store {
                 allow_criteria_changes,
             ))?;
 
+            this.validate(cfg.today(), cfg.cli.locked)?;
+        } else if !this.config.imports.is_empty()
+            && this
+                .config
+                .imports
+                .iter()
+                .all(|(import_name, import)| is_local_import(cfg, import_name, import))
+        {
+            let cache = Cache::acquire(cfg).map_err(Box::new)?;
+            tokio::runtime::Handle::current().block_on(this.refresh_local_imports(
+                cfg,
+                &cache,
+                allow_criteria_changes,
+            ))?;
+
             this.validate(cfg.today(), cfg.cli.locked)?;
         }
         Ok(this)
     }
 
+    /// Refresh imported audits without network access, when every import is
+    /// read from a local file or git checkout.
+    ///
+    /// If any `git+` commit hasn't been fetched yet, the imports from
+    /// `imports.lock` are used as-is. When locked, the refreshed imports are
+    /// checked against the hashes in `imports.lock` like
+    /// [`Store::verify_import_hashes`]. Publisher information and unpublished
+    /// entries can't be updated without the network, so those from
+    /// `imports.lock` are kept as-is.
+    async fn refresh_local_imports(
+        &mut self,
+        cfg: &Config,
+        cache: &Cache,
+        allow_criteria_changes: bool,
+    ) -> Result<(), StoreAcquireError> {
+        for (import_name, import) in &self.config.imports {
+            for url in &import.url {
+                if let Ok(ImportSource::Git { repo, rev, .. }) =
+                    ImportSource::parse(cfg, import_name, url)
+                {
+                    if !cache.has_git_rev(&repo, &rev).await {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+
+        let local_criteria_mapper = CriteriaMapper::new(&self.audits.criteria);
+        let fetcher = ImportFetcher {
+            cfg,
+            network: None,
+            cache,
+        };
+        let fetched_audits =
+            fetch_imported_audits(&fetcher, &local_criteria_mapper, &self.config.imports).await?;
+        if cfg.cli.locked {
+            let changed = self.changed_imports(&fetched_audits);
+            if !changed.is_empty() {
+                return Err(Box::new(ImportsChangedError::Changed { changed }).into());
+            }
+        }
+        let mut live_imports =
+            process_imported_audits(fetched_audits, &self.imports, allow_criteria_changes)?;
+        live_imports.unpublished = self.imports.unpublished.clone();
+        live_imports.publisher = self.imports.publisher.clone();
+        self.live_imports = Some(live_imports);
+        Ok(())
+    }
+
     pub async fn go_online(
         &mut self,
         cfg: &Config,
@@ -275,8 +431,13 @@ impl Store {
         // If this command isn't locked, and the network is available, fetch the
         // live state of imported audits.
         let local_criteria_mapper = CriteriaMapper::new(&self.audits.criteria);
+        let fetcher = ImportFetcher {
+            cfg,
+            network: Some(network),
+            cache,
+        };
         let fetched_audits =
-            fetch_imported_audits(network, &local_criteria_mapper, &self.config).await?;
+            fetch_imported_audits(&fetcher, &local_criteria_mapper, &self.config.imports).await?;
         let mut live_imports =
             process_imported_audits(fetched_audits, &self.imports, allow_criteria_changes)?;
         import_unpublished_entries(
@@ -314,6 +475,7 @@ impl Store {
             imports,
             audits,
             live_imports: None,
+            advisory_violations: SortedMap::new(),
             config_src: SourceFile::new_empty(CONFIG_TOML),
             audits_src: SourceFile::new_empty(AUDITS_TOML),
             imports_src: SourceFile::new_empty(IMPORTS_LOCK),
@@ -335,14 +497,19 @@ impl Store {
         allow_criteria_changes: bool,
     ) -> Result<Self, StoreAcquireError> {
         let local_criteria_mapper = CriteriaMapper::new(&audits.criteria);
+        let cache = Cache::acquire(cfg).map_err(Box::new)?;
+        let fetcher = ImportFetcher {
+            cfg,
+            network: Some(network),
+            cache: &cache,
+        };
         let fetched_audits = tokio::runtime::Handle::current().block_on(fetch_imported_audits(
-            network,
+            &fetcher,
             &local_criteria_mapper,
-            &config,
+            &config.imports,
         ))?;
         let mut live_imports =
             process_imported_audits(fetched_audits, &imports, allow_criteria_changes)?;
-        let cache = Cache::acquire(cfg).map_err(Box::new)?;
         tokio::runtime::Handle::current()
             .block_on(import_unpublished_entries(
                 &cfg.metadata,
@@ -372,6 +539,7 @@ impl Store {
             imports,
             audits,
             live_imports: Some(live_imports),
+            advisory_violations: SortedMap::new(),
             config_src: SourceFile::new_empty(CONFIG_TOML),
             audits_src: SourceFile::new_empty(AUDITS_TOML),
             imports_src: SourceFile::new_empty(IMPORTS_LOCK),
@@ -402,6 +570,7 @@ impl Store {
             imports,
             audits,
             live_imports: None,
+            advisory_violations: SortedMap::new(),
             config_src,
             audits_src,
             imports_src,
@@ -429,6 +598,7 @@ impl Store {
             imports: self.imports.clone(),
             audits: self.audits.clone(),
             live_imports: self.live_imports.clone(),
+            advisory_violations: self.advisory_violations.clone(),
             config_src: self.config_src.clone(),
             audits_src: self.audits_src.clone(),
             imports_src: self.imports_src.clone(),
@@ -486,6 +656,223 @@ impl Store {
         }
     }
 
+    /// Returns the hashes of each imported audits file.
+    ///
+    /// If the store was acquired unlocked, these are the hashes of the files
+    /// which were just fetched, otherwise they are the hashes stored in
+    /// imports.lock.
+    pub fn import_hashes(&self) -> &SortedMap<ImportName, ImportHashes> {
+        match &self.live_imports {
+            Some(live_imports) => &live_imports.hashes,
+            None => &self.imports.hashes,
+        }
+    }
+
+    /// Summarize how each import has changed upstream since imports.lock was
+    /// generated, based on the live imports fetched from the network.
+    ///
+    /// Only imports whose hash has changed (or which have no recorded hash)
+    /// are compared, and imports without any changes are omitted.
+    pub fn upstream_import_changes(&self) -> Vec<ImportChanges> {
+        let Some(live_imports) = &self.live_imports else {
+            return Vec::new();
+        };
+        live_imports
+            .audits
+            .iter()
+            .filter(|(import_name, _)| {
+                self.imports.hashes.get(*import_name) != live_imports.hashes.get(*import_name)
+            })
+            .filter_map(|(import_name, live_audits)| {
+                let existing_audits = self.imports.audits.get(import_name)?;
+                let exclude = self
+                    .config
+                    .imports
+                    .get(import_name)
+                    .map(|import| &import.exclude[..])
+                    .unwrap_or(&[]);
+                Some(ImportChanges::between(
+                    import_name,
+                    existing_audits,
+                    live_audits,
+                    exclude,
+                ))
+            })
+            .filter(|changes| !changes.is_empty())
+            .collect()
+    }
+
+    /// When locked, re-read every import with a hash recorded in imports.lock,
+    /// and check that none of its audits in imports.lock were removed or
+    /// modified upstream, and that its criteria descriptions haven't changed. Imports which
+    /// only had audits added upstream are still accepted.
+    ///
+    /// If `network` is `None`, only imports which can be read without the
+    /// network (local paths, and git commits which are already cached) are
+    /// checked.
+    pub async fn verify_import_hashes(
+        &self,
+        cfg: &Config,
+        network: Option<&Network>,
+        cache: &Cache,
+    ) -> Result<(), ImportsChangedError> {
+        let imports: SortedMap<_, _> = self
+            .config
+            .imports
+            .iter()
+            .filter(|(import_name, import)| {
+                self.imports.hashes.contains_key(*import_name)
+                    && (network.is_some() || is_local_import(cfg, import_name, import))
+            })
+            .map(|(import_name, import)| (import_name.clone(), import.clone()))
+            .collect();
+        if imports.is_empty() {
+            return Ok(());
+        }
+
+        let local_criteria_mapper = CriteriaMapper::new(&self.audits.criteria);
+        let fetcher = ImportFetcher {
+            cfg,
+            network,
+            cache,
+        };
+        let fetched_audits = fetch_imported_audits(&fetcher, &local_criteria_mapper, &imports)
+            .await
+            .map_err(|error| ImportsChangedError::Fetch(*error))?;
+
+        let changed = self.changed_imports(&fetched_audits);
+        if changed.is_empty() {
+            Ok(())
+        } else {
+            Err(ImportsChangedError::Changed { changed })
+        }
+    }
+
+    /// Compare freshly fetched imports against the hashes in imports.lock,
+    /// returning those which had audits in imports.lock removed or modified, or
+    /// their criteria changed, upstream.
+    fn changed_imports(
+        &self,
+        fetched_audits: &[(ImportName, AuditsFile, ImportHashes)],
+    ) -> Vec<ImportChangedError> {
+        let mut changed = Vec::new();
+        for (import_name, live_audits, live_hashes) in fetched_audits {
+            let Some(locked_hashes) = self.imports.hashes.get(import_name) else {
+                continue;
+            };
+            let mismatched_urls: Vec<_> = locked_hashes
+                .iter()
+                .filter(|(url, hash)| live_hashes.get(*url).is_some_and(|live| live != *hash))
+                .map(|(url, _)| url.clone())
+                .collect();
+            if mismatched_urls.is_empty() {
+                continue;
+            }
+
+            let exclude = self
+                .config
+                .imports
+                .get(import_name)
+                .map(|import| &import.exclude[..])
+                .unwrap_or(&[]);
+            let empty = AuditsFile::default();
+            let existing_audits = self.imports.audits.get(import_name).unwrap_or(&empty);
+            let changes =
+                ImportChanges::between(import_name, existing_audits, live_audits, exclude);
+            if changes.is_empty() {
+                info!(
+                    "{import_name} has changed upstream, but no audits in imports.lock were removed or modified; run `cargo vet regenerate imports` to record the new contents"
+                );
+                continue;
+            }
+            changed.push(ImportChangedError {
+                urls: mismatched_urls,
+                changes,
+            });
+        }
+
+        changed
+    }
+
+    /// Record a synthetic violation for each crates.io package in the
+    /// dependency graph whose locked version is affected by one of the given
+    /// advisories, unless the advisory is ignored in config.toml.
+    ///
+    /// The violations apply to every criteria which doesn't imply any others, so
+    /// any audit or exemption of an affected version conflicts with them.
+    pub fn add_advisories(
+        &mut self,
+        metadata: &cargo_metadata::Metadata,
+        advisories: impl IntoIterator<Item = Advisory>,
+    ) {
+        let ignored: &[AdvisoryId] = self
+            .config
+            .advisory_db
+            .as_ref()
+            .map_or(&[], |advisory_db| &advisory_db.ignore);
+
+        let mut by_package: SortedMap<PackageName, Vec<Advisory>> = SortedMap::new();
+        for advisory in advisories {
+            if !ignored.contains(&advisory.id) {
+                by_package
+                    .entry(advisory.package.clone())
+                    .or_default()
+                    .push(advisory);
+            }
+        }
+
+        let criteria: Vec<Spanned<CriteriaName>> = std::iter::once(SAFE_TO_RUN.to_owned())
+            .chain(
+                self.audits
+                    .criteria
+                    .iter()
+                    .filter(|(_, entry)| entry.implies.is_empty())
+                    .map(|(name, _)| name.clone()),
+            )
+            .map(Spanned::from)
+            .collect();
+
+        for package in &metadata.packages {
+            if !package.is_crates_io() {
+                continue;
+            }
+            let Some(advisories) = by_package.get(&package.name[..]) else {
+                continue;
+            };
+            let version = package.vet_version();
+            for advisory in advisories {
+                if !advisory.affects(&version) {
+                    continue;
+                }
+                let violation = AuditEntry {
+                    who: vec![],
+                    criteria: criteria.clone(),
+                    kind: AuditKind::Violation {
+                        violation: VersionReq::parse(&format!("={}", version.semver))
+                            .expect("exact version requirements are always valid"),
+                    },
+                    expires: None,
+                    registry: None,
+                    checksum: None,
+                    importable: false,
+                    notes: Some(format!("{}: {}", advisory.id, advisory.title)),
+                    aggregated_from: vec![],
+                    is_fresh_import: false,
+                };
+                let violations = self
+                    .advisory_violations
+                    .entry(package.name.to_string())
+                    .or_default();
+                if !violations
+                    .iter()
+                    .any(|(id, existing)| *id == advisory.id && existing.kind == violation.kind)
+                {
+                    violations.push((advisory.id.clone(), violation));
+                }
+            }
+        }
+    }
+
     /// Commit the store's contents back to disk
     pub fn commit(self) -> Result<(), StoreCommitError> {
         // TODO: make this truly transactional?
@@ -607,6 +994,12 @@ impl Store {
                 &mut errors,
                 policy.dev_criteria.as_ref().unwrap_or(&no_criteria),
             );
+            check_criteria(
+                &self.config_src,
+                &valid_criteria,
+                &mut errors,
+                policy.build_criteria.as_ref().unwrap_or(&no_criteria),
+            );
             for (_dep_package, dep_criteria) in &policy.dependency_criteria {
                 check_criteria(&self.config_src, &valid_criteria, &mut errors, dep_criteria);
             }
@@ -620,7 +1013,7 @@ impl Store {
                 &entry.implies,
             );
         }
-        for (_package, entries) in &self.audits.audits {
+        for (package, entries) in &self.audits.audits {
             for entry in entries {
                 // TODO: check that new_criteria isn't shadowing a builtin criteria
                 check_criteria(
@@ -629,6 +1022,15 @@ impl Store {
                     &mut errors,
                     &entry.criteria,
                 );
+
+                if let Some(registry) = &entry.registry {
+                    if !self.config.registries.contains_key(registry) {
+                        errors.push(StoreValidateError::UnknownRegistry {
+                            package: package.clone(),
+                            registry: registry.clone(),
+                        });
+                    }
+                }
             }
         }
         for (_package, entries) in &self.audits.wildcard_audits {
@@ -783,6 +1185,11 @@ impl Store {
                 registry_file.registry.len() as u64,
             );
             let local_criteria_mapper = CriteriaMapper::new(&self.audits.criteria);
+            let fetcher = ImportFetcher {
+                cfg,
+                network: Some(network),
+                cache,
+            };
             join_all(
                 registry_file
                     .registry
@@ -792,10 +1199,11 @@ impl Store {
                         let _guard = IncProgressOnDrop(&progress_bar, 1);
                         let existing_entry = self.config.imports.get(&name);
                         fetch_imported_audit(
-                            network,
+                            &fetcher,
                             &local_criteria_mapper,
                             &name,
                             &entry.url,
+                            existing_entry.and_then(|e| e.public_key.as_deref()),
                             existing_entry.map(|e| &e.exclude[..]).unwrap_or(&[]),
                             existing_entry
                                 .map(|e| &e.criteria_map)
@@ -805,7 +1213,7 @@ impl Store {
                         .map_err(|error| {
                             error!("Error fetching registry audits for '{name}': {error:?}")
                         })
-                        .map(|audit_file| (name, entry, audit_file))
+                        .map(|(audit_file, _)| (name, entry, audit_file))
                         .ok()
                     }),
             )
@@ -816,27 +1224,31 @@ impl Store {
         };
 
         // Re-run import_publisher_versions to ensure that we have all publisher
-        // information for any potential wildcard audit imports.
+        // information for any potential wildcard audit imports. If the store
+        // was acquired with `--locked` there are no live imports to add the
+        // publishers to, so only the audits are returned.
         // Note: This is the only reason we need mutable access to the store.
         // XXX: Consider limiting further to only packages which are currently
         // failing to vet?
         // XXX: Consider making this fetch async?
-        let wildcard_packages = registry_entries
-            .iter()
-            .flat_map(|(_, _, audits_file)| audits_file.wildcard_audits.keys())
-            .cloned()
-            .collect::<FastSet<_>>();
-        import_publisher_versions(
-            &cfg.metadata,
-            network,
-            cache,
-            &wildcard_packages,
-            false,
-            &self.config,
-            &self.imports,
-            self.live_imports.as_mut().unwrap(),
-        )
-        .await?;
+        if let Some(live_imports) = self.live_imports.as_mut() {
+            let wildcard_packages = registry_entries
+                .iter()
+                .flat_map(|(_, _, audits_file)| audits_file.wildcard_audits.keys())
+                .cloned()
+                .collect::<FastSet<_>>();
+            import_publisher_versions(
+                &cfg.metadata,
+                network,
+                cache,
+                &wildcard_packages,
+                false,
+                &self.config,
+                &self.imports,
+                live_imports,
+            )
+            .await?;
+        }
 
         Ok(registry_entries)
     }
@@ -845,18 +1257,19 @@ impl Store {
 /// Process imported audits from the network, generating a `LiveImports`
 /// description of the live state of imported audits.
 fn process_imported_audits(
-    fetched_audits: Vec<(ImportName, AuditsFile)>,
+    fetched_audits: Vec<(ImportName, AuditsFile, ImportHashes)>,
     imports_lock: &ImportsFile,
     allow_criteria_changes: bool,
 ) -> Result<ImportsFile, CriteriaChangeErrors> {
     let mut new_imports = ImportsFile {
         unpublished: SortedMap::new(),
         publisher: SortedMap::new(),
+        hashes: SortedMap::new(),
         audits: SortedMap::new(),
     };
     let mut changed_criteria = Vec::new();
 
-    for (import_name, mut audits_file) in fetched_audits {
+    for (import_name, mut audits_file, hashes) in fetched_audits {
         if let Some(existing_audits_file) = imports_lock.audits.get(&import_name) {
             update_import_freshness(
                 &mut audits_file,
@@ -883,6 +1296,7 @@ fn process_imported_audits(
         }
 
         // Now add the new import
+        new_imports.hashes.insert(import_name.clone(), hashes);
         new_imports.audits.insert(import_name, audits_file);
     }
 
@@ -949,103 +1363,605 @@ fn update_import_freshness(
     }
 }
 
-/// Fetch all declared imports from the network, mapping criteria to the local
-/// namespace, and filling in any criteria descriptions.
-async fn fetch_imported_audits(
-    network: &Network,
-    local_criteria_mapper: &CriteriaMapper,
-    config: &ConfigFile,
-) -> Result<Vec<(ImportName, AuditsFile)>, Box<FetchAuditError>> {
-    let progress_bar = progress_bar("Fetching", "imported audits", config.imports.len() as u64);
-    try_join_all(config.imports.iter().map(|(name, import)| async {
-        let _guard = IncProgressOnDrop(&progress_bar, 1);
-        let audit_file = fetch_imported_audit(
-            network,
-            local_criteria_mapper,
-            name,
-            &import.url,
-            &import.exclude,
-            &import.criteria_map,
-        )
-        .await
-        .map_err(Box::new)?;
-        Ok::<_, Box<FetchAuditError>>((name.clone(), audit_file))
-    }))
-    .await
+/// Compute the hash which is recorded in imports.lock for an imported audits
+/// file.
+fn import_hash(contents: &[u8]) -> ImportHash {
+    format!("sha256:{:x}", Sha256::digest(contents))
 }
 
-async fn fetch_imported_audit(
-    network: &Network,
-    local_criteria_mapper: &CriteriaMapper,
-    name: &str,
-    urls: &[String],
-    exclude: &[PackageName],
-    criteria_map: &CriteriaMap,
-) -> Result<AuditsFile, FetchAuditError> {
-    // Fetch all imported URLs, and then aggregate them.
-    let sources = try_join_all(urls.iter().map(|url| async {
-        fetch_single_imported_audit(
-            network,
-            local_criteria_mapper,
-            name,
-            url,
-            exclude,
-            criteria_map,
-        )
-        .await
-        .map(|audits_file| (url.clone(), audits_file))
-    }))
-    .await?;
-
-    // If we only have a single source, don't aggregate so that we don't add
-    // unnecessary `aggregated-from` members.
-    if sources.len() == 1 {
-        Ok(sources.into_iter().next().unwrap().1)
-    } else {
-        crate::do_aggregate_audits(sources).map_err(|error| FetchAuditError::Aggregate {
-            import_name: name.to_owned(),
-            errors: error
-                .errors
-                .into_iter()
-                .map(|err| match err {
-                    AggregateError::CriteriaDescriptionMismatch(mismatch) => {
-                        FetchAuditAggregateError {
-                            mapped_to: criteria_map
-                                .get(&mismatch.criteria_name)
-                                .cloned()
-                                .unwrap_or_default(),
-                            criteria_name: mismatch.criteria_name,
-                            first: mismatch.first,
-                            second: mismatch.second,
-                        }
-                    }
-                    AggregateError::ImpliesMismatch(_) => {
-                        unreachable!("implies is stripped by fetch_single_imported_audit")
-                    }
-                })
-                .collect(),
-        })
-    }
+/// A summary of how an imported audits file has changed upstream since
+/// imports.lock was generated.
+#[derive(Debug, Clone, Default)]
+pub struct ImportChanges {
+    pub import_name: ImportName,
+    /// Audits which were newly added to imports.lock.
+    pub new_audits: Vec<String>,
+    /// Audits in imports.lock which are no longer present upstream.
+    pub removed_audits: Vec<String>,
+    /// Criteria whose description has changed upstream.
+    pub changed_criteria: Vec<CriteriaName>,
 }
 
-/// Fetch a single AuditsFile from the network, filling in any criteria
-/// descriptions.
-async fn fetch_single_imported_audit(
-    network: &Network,
-    local_criteria_mapper: &CriteriaMapper,
-    name: &str,
-    url: &str,
-    exclude: &[PackageName],
-    criteria_map: &CriteriaMap,
-) -> Result<AuditsFile, FetchAuditError> {
-    let parsed_url = Url::parse(url).map_err(|error| FetchAuditError::InvalidUrl {
-        import_url: url.to_owned(),
-        import_name: name.to_owned(),
-        error,
-    })?;
-    let audit_source = network.download_source_file_cached(parsed_url).await?;
+impl ImportChanges {
+    pub fn new(import_name: &str) -> Self {
+        ImportChanges {
+            import_name: import_name.to_owned(),
+            ..Default::default()
+        }
+    }
 
-    let mut audit_file = foreign_audit_source_to_local_warn(name, audit_source)?;
+    /// Compare the audits for an import recorded in imports.lock against the
+    /// live audits fetched from upstream. Audits for crates in `exclude` were
+    /// removed when fetching, so aren't reported as removed.
+    ///
+    /// `new_audits` is left empty, as most new audits upstream won't be
+    /// imported.
+    fn between(
+        import_name: &str,
+        existing: &AuditsFile,
+        live: &AuditsFile,
+        exclude: &[PackageName],
+    ) -> Self {
+        let mut changes = ImportChanges::new(import_name);
+
+        for (criteria_name, old_entry) in &existing.criteria {
+            if let Some(new_entry) = live.criteria.get(criteria_name) {
+                if old_entry.description != new_entry.description {
+                    changes.changed_criteria.push(criteria_name.clone());
+                }
+            }
+        }
+
+        for (package, existing_audits) in &existing.audits {
+            if exclude.contains(package) {
+                continue;
+            }
+            let live_audits = live.audits.get(package).map(|v| &v[..]).unwrap_or(&[]);
+            for audit in existing_audits {
+                if !live_audits.iter().any(|live| live.same_audit_as(audit)) {
+                    changes.removed_audits.push(describe_audit(package, audit));
+                }
+            }
+        }
+        for (package, existing_audits) in &existing.wildcard_audits {
+            if exclude.contains(package) {
+                continue;
+            }
+            let live_audits = live
+                .wildcard_audits
+                .get(package)
+                .map(|v| &v[..])
+                .unwrap_or(&[]);
+            for audit in existing_audits {
+                if !live_audits.iter().any(|live| live.same_audit_as(audit)) {
+                    changes
+                        .removed_audits
+                        .push(describe_wildcard_audit(package, audit));
+                }
+            }
+        }
+
+        changes
+    }
+
+    /// Record the audits which were added to imports.lock for this import,
+    /// going from `old` to `new`.
+    pub fn add_new_audits(&mut self, old: Option<&AuditsFile>, new: &AuditsFile) {
+        let empty = AuditsFile::default();
+        let old = old.unwrap_or(&empty);
+        for (package, new_audits) in &new.audits {
+            let old_audits = old.audits.get(package).map(|v| &v[..]).unwrap_or(&[]);
+            for audit in new_audits {
+                if !old_audits.iter().any(|old| old.same_audit_as(audit)) {
+                    self.new_audits.push(describe_audit(package, audit));
+                }
+            }
+        }
+        for (package, new_audits) in &new.wildcard_audits {
+            let old_audits = old
+                .wildcard_audits
+                .get(package)
+                .map(|v| &v[..])
+                .unwrap_or(&[]);
+            for audit in new_audits {
+                if !old_audits.iter().any(|old| old.same_audit_as(audit)) {
+                    self.new_audits
+                        .push(describe_wildcard_audit(package, audit));
+                }
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.new_audits.is_empty()
+            && self.removed_audits.is_empty()
+            && self.changed_criteria.is_empty()
+    }
+}
+
+impl fmt::Display for ImportChanges {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.import_name)?;
+        for (heading, entries) in [
+            ("new audits", &self.new_audits),
+            ("removed audits", &self.removed_audits),
+            ("changed criteria descriptions", &self.changed_criteria),
+        ] {
+            if entries.is_empty() {
+                continue;
+            }
+            write!(f, "\n  {heading}:")?;
+            for entry in entries {
+                write!(f, "\n    {entry}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn describe_audit(package: PackageStr<'_>, audit: &AuditEntry) -> String {
+    let criteria = audit
+        .criteria
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    match &audit.kind {
+        AuditKind::Full { version } => format!("{package} {version} ({criteria})"),
+        AuditKind::Delta { from, to } => format!("{package} {from} -> {to} ({criteria})"),
+        AuditKind::Violation { violation } => {
+            format!("{package} {violation} violation ({criteria})")
+        }
+    }
+}
+
+fn describe_wildcard_audit(package: PackageStr<'_>, audit: &WildcardEntry) -> String {
+    let criteria = audit
+        .criteria
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "{package} wildcard {} to {} ({criteria})",
+        *audit.start, *audit.end
+    )
+}
+
+/// Where an import's audits are read from, as specified by its `url`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ImportSource {
+    /// An `https://` URL, downloaded from the network.
+    Url(Url),
+    /// A `file:` path, relative to the store.
+    File(PathBuf),
+    /// A file within a `git+` repository, pinned to a commit.
+    Git {
+        repo: String,
+        rev: String,
+        path: String,
+    },
+}
+
+/// The default path to the audits within a `git+` import.
+const GIT_IMPORT_DEFAULT_PATH: &str = "supply-chain/audits.toml";
+
+/// Whether every source of `import` can be read without network access.
+fn is_local_import(cfg: &Config, import_name: &str, import: &RemoteImport) -> bool {
+    import.url.iter().all(|url| {
+        matches!(
+            ImportSource::parse(cfg, import_name, url),
+            Ok(ImportSource::File(_) | ImportSource::Git { .. })
+        )
+    })
+}
+
+impl ImportSource {
+    fn parse(cfg: &Config, name: &str, url: &str) -> Result<Self, FetchAuditError> {
+        if let Some(path) = url.strip_prefix("file:") {
+            // `file:///absolute/path` URLs are also accepted.
+            let path = match path.strip_prefix("//") {
+                Some(absolute) => PathBuf::from(absolute),
+                None => cfg.metacfg.store_path().as_path_unlocked().join(path),
+            };
+            return Ok(ImportSource::File(path));
+        }
+
+        if let Some(repo) = url.strip_prefix("git+") {
+            let invalid = |reason| FetchAuditError::InvalidGitSource {
+                import_name: name.to_owned(),
+                import_url: url.to_owned(),
+                reason,
+            };
+            let mut repo = Url::parse(repo).map_err(|_| invalid("invalid repository URL"))?;
+            let mut rev = None;
+            let mut path = None;
+            for (key, value) in repo.query_pairs() {
+                match &key[..] {
+                    "rev" => rev = Some(value.into_owned()),
+                    "path" => path = Some(value.into_owned()),
+                    _ => return Err(invalid("unknown query parameter")),
+                }
+            }
+            let rev = rev.ok_or_else(|| invalid("missing `rev`"))?;
+            if !matches!(rev.len(), 40 | 64) || !rev.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(invalid("`rev` must be a full commit hash"));
+            }
+            repo.set_query(None);
+            repo.set_fragment(None);
+            return Ok(ImportSource::Git {
+                repo: repo.to_string(),
+                rev,
+                path: path.unwrap_or_else(|| GIT_IMPORT_DEFAULT_PATH.to_owned()),
+            });
+        }
+
+        Url::parse(url)
+            .map(ImportSource::Url)
+            .map_err(|error| FetchAuditError::InvalidUrl {
+                import_url: url.to_owned(),
+                import_name: name.to_owned(),
+                error,
+            })
+    }
+
+    /// The location of a file next to this one, with `extension` added, such
+    /// as a detached signature.
+    fn with_extension(&self, extension: &str) -> Self {
+        match self {
+            ImportSource::Url(url) => {
+                let mut url = url.clone();
+                url.set_path(&format!("{}.{extension}", url.path()));
+                ImportSource::Url(url)
+            }
+            ImportSource::File(path) => {
+                let mut path = path.clone().into_os_string();
+                path.push(format!(".{extension}"));
+                ImportSource::File(path.into())
+            }
+            ImportSource::Git { repo, rev, path } => ImportSource::Git {
+                repo: repo.clone(),
+                rev: rev.clone(),
+                path: format!("{path}.{extension}"),
+            },
+        }
+    }
+}
+
+impl fmt::Display for ImportSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportSource::Url(url) => write!(f, "{url}"),
+            ImportSource::File(path) => write!(f, "file:{}", path.display()),
+            ImportSource::Git { repo, rev, path } => {
+                write!(f, "git+{repo}?rev={rev}&path={path}")
+            }
+        }
+    }
+}
+
+/// Reads imported audits from wherever their sources are located. Only
+/// `https://` imports (and criteria descriptions) require the network, so
+/// local imports can still be read while frozen.
+struct ImportFetcher<'a> {
+    cfg: &'a Config,
+    network: Option<&'a Network>,
+    cache: &'a Cache,
+}
+
+impl ImportFetcher<'_> {
+    async fn read(&self, name: &str, source: &ImportSource) -> Result<SourceFile, FetchAuditError> {
+        let frozen = || FetchAuditError::Frozen {
+            import_name: name.to_owned(),
+            import_url: source.to_string(),
+        };
+        match source {
+            ImportSource::Url(url) => {
+                let network = self.network.ok_or_else(frozen)?;
+                Ok(network.download_source_file_cached(url.clone()).await?)
+            }
+            ImportSource::File(path) => {
+                let contents = tokio::fs::read_to_string(path).await.map_err(|error| {
+                    FetchAuditError::ReadLocal {
+                        import_name: name.to_owned(),
+                        path: path.clone(),
+                        error,
+                    }
+                })?;
+                Ok(SourceFile::new(&source.to_string(), contents))
+            }
+            ImportSource::Git { repo, rev, path } => {
+                let contents = self
+                    .cache
+                    .read_git_file(repo, rev, path, self.network.is_none())
+                    .await
+                    .map_err(|error| FetchAuditError::Git {
+                        import_name: name.to_owned(),
+                        import_url: source.to_string(),
+                        error,
+                    })?;
+                Ok(SourceFile::new(&source.to_string(), contents))
+            }
+        }
+    }
+}
+
+/// Fetch all of the given imports, mapping criteria to the local namespace,
+/// and filling in any criteria descriptions.
+async fn fetch_imported_audits(
+    fetcher: &ImportFetcher<'_>,
+    local_criteria_mapper: &CriteriaMapper,
+    imports: &SortedMap<ImportName, RemoteImport>,
+) -> Result<Vec<(ImportName, AuditsFile, ImportHashes)>, Box<FetchAuditError>> {
+    let progress_bar = progress_bar("Fetching", "imported audits", imports.len() as u64);
+    try_join_all(imports.iter().map(|(name, import)| async {
+        let _guard = IncProgressOnDrop(&progress_bar, 1);
+        let (audit_file, hashes) = fetch_imported_audit(
+            fetcher,
+            local_criteria_mapper,
+            name,
+            &import.url,
+            import.public_key.as_deref(),
+            &import.exclude,
+            &import.criteria_map,
+        )
+        .await
+        .map_err(Box::new)?;
+        Ok::<_, Box<FetchAuditError>>((name.clone(), audit_file, hashes))
+    }))
+    .await
+}
+
+async fn fetch_imported_audit(
+    fetcher: &ImportFetcher<'_>,
+    local_criteria_mapper: &CriteriaMapper,
+    name: &str,
+    urls: &[String],
+    public_key: Option<&str>,
+    exclude: &[PackageName],
+    criteria_map: &CriteriaMap,
+) -> Result<(AuditsFile, ImportHashes), FetchAuditError> {
+    let public_key = public_key
+        .map(ImportKey::parse)
+        .transpose()
+        .map_err(|error| FetchAuditError::InvalidPublicKey {
+            import_name: name.to_owned(),
+            error,
+        })?;
+
+    // Fetch all imported URLs, and then aggregate them.
+    let sources = try_join_all(urls.iter().map(|url| async {
+        fetch_single_imported_audit(
+            fetcher,
+            local_criteria_mapper,
+            name,
+            url,
+            public_key.as_ref(),
+            exclude,
+            criteria_map,
+        )
+        .await
+        .map(|(audits_file, hash)| (url.clone(), audits_file, hash))
+    }))
+    .await?;
+
+    let hashes = sources
+        .iter()
+        .map(|(url, _, hash)| (url.clone(), hash.clone()))
+        .collect();
+    let sources: Vec<_> = sources
+        .into_iter()
+        .map(|(url, audits_file, _)| (url, audits_file))
+        .collect();
+
+    // If we only have a single source, don't aggregate so that we don't add
+    // unnecessary `aggregated-from` members.
+    let mut audits_file = if sources.len() == 1 {
+        sources.into_iter().next().unwrap().1
+    } else {
+        do_aggregate_audits(sources).map_err(|error| FetchAuditError::Aggregate {
+            import_name: name.to_owned(),
+            errors: error
+                .errors
+                .into_iter()
+                .map(|err| match err {
+                    AggregateError::CriteriaDescriptionMismatch(mismatch) => {
+                        FetchAuditAggregateError::Description {
+                            mapped_to: criteria_map
+                                .get(&mismatch.criteria_name)
+                                .cloned()
+                                .unwrap_or_default(),
+                            criteria_name: mismatch.criteria_name,
+                            first: mismatch.first,
+                            second: mismatch.second,
+                        }
+                    }
+                    AggregateError::ImpliesMismatch(mismatch) => {
+                        FetchAuditAggregateError::Implies {
+                            mapped_to: criteria_map
+                                .get(&mismatch.criteria_name)
+                                .cloned()
+                                .unwrap_or_default(),
+                            criteria_name: mismatch.criteria_name,
+                            first: mismatch.first,
+                            second: mismatch.second,
+                        }
+                    }
+                })
+                .collect(),
+        })?
+    };
+
+    // Clear out implies once the sources have been checked for agreement, as
+    // imported criteria are only used through the import's `criteria-map`, so
+    // it will never be used locally and isn't recorded in imports.lock.
+    for criteria_entry in audits_file.criteria.values_mut() {
+        criteria_entry.implies = Vec::new();
+    }
+    Ok((audits_file, hashes))
+}
+
+pub(crate) fn do_aggregate_audits(
+    sources: Vec<(String, AuditsFile)>,
+) -> Result<AuditsFile, AggregateErrors> {
+    let mut errors = Vec::new();
+    let mut aggregate = AuditsFile {
+        criteria: SortedMap::new(),
+        wildcard_audits: SortedMap::new(),
+        audits: SortedMap::new(),
+        // FIXME: How should we handle aggregating trusted entries? Should we do
+        // any form of de-duplication?
+        trusted: SortedMap::new(),
+    };
+
+    for (source, audit_file) in sources {
+        // Add each criteria from the original source, managing duplicates by
+        // ensuring that their descriptions map 1:1.
+        for (criteria_name, mut criteria_entry) in audit_file.criteria {
+            match aggregate.criteria.entry(criteria_name) {
+                std::collections::btree_map::Entry::Vacant(vacant) => {
+                    criteria_entry.aggregated_from.push(source.clone().into());
+                    vacant.insert(criteria_entry);
+                }
+                std::collections::btree_map::Entry::Occupied(occupied) => {
+                    let prev_source = occupied
+                        .get()
+                        .aggregated_from
+                        .last()
+                        .map(|s| s.to_string())
+                        .unwrap_or_default();
+                    // NOTE: We don't record the new `aggregated_from` chain in
+                    // this case, as we already have a chain for the existing
+                    // entry which we don't want to clobber. This means that
+                    // source order in the `sources.list` file can impact where
+                    // your criteria are credited to originate from.
+                    if occupied.get().description != criteria_entry.description
+                        || occupied.get().description_url != criteria_entry.description_url
+                    {
+                        errors.push(AggregateError::CriteriaDescriptionMismatch(
+                            AggregateCriteriaDescriptionMismatchError {
+                                criteria_name: occupied.key().to_owned(),
+                                first: AggregateCriteriaDescription {
+                                    source: prev_source.clone(),
+                                    description: occupied.get().description.clone(),
+                                    description_url: occupied.get().description_url.clone(),
+                                },
+                                second: AggregateCriteriaDescription {
+                                    source: source.clone(),
+                                    description: criteria_entry.description.clone(),
+                                    description_url: criteria_entry.description_url.clone(),
+                                },
+                            },
+                        ))
+                    }
+                    if occupied.get().implies != criteria_entry.implies {
+                        errors.push(AggregateError::ImpliesMismatch(
+                            AggregateImpliesMismatchError {
+                                criteria_name: occupied.key().to_owned(),
+                                first: AggregateCriteriaImplies {
+                                    source: prev_source.clone(),
+                                    implies: occupied
+                                        .get()
+                                        .implies
+                                        .iter()
+                                        .map(|c| c.to_string())
+                                        .collect(),
+                                },
+                                second: AggregateCriteriaImplies {
+                                    source: source.clone(),
+                                    implies: criteria_entry
+                                        .implies
+                                        .iter()
+                                        .map(|c| c.to_string())
+                                        .collect(),
+                                },
+                            },
+                        ));
+                    }
+                }
+            }
+        }
+        for (package_name, entries) in audit_file.audits {
+            aggregate.audits.entry(package_name).or_default().extend(
+                entries
+                    .into_iter()
+                    .filter(|audit_entry| audit_entry.importable)
+                    .map(|mut audit_entry| {
+                        audit_entry.aggregated_from.push(source.clone().into());
+                        audit_entry
+                    }),
+            );
+        }
+        for (package_name, entries) in audit_file.wildcard_audits {
+            aggregate
+                .wildcard_audits
+                .entry(package_name)
+                .or_default()
+                .extend(entries.into_iter().map(|mut wildcard_entry| {
+                    wildcard_entry.aggregated_from.push(source.clone().into());
+                    wildcard_entry
+                }));
+        }
+        for (package_name, entries) in audit_file.trusted {
+            aggregate
+                .trusted
+                .entry(package_name)
+                .or_default()
+                .extend(entries.into_iter().map(|mut trusted_entry| {
+                    trusted_entry.aggregated_from.push(source.clone().into());
+                    trusted_entry
+                }));
+        }
+    }
+
+    aggregate.tidy();
+
+    if errors.is_empty() {
+        Ok(aggregate)
+    } else {
+        Err(AggregateErrors { errors })
+    }
+}
+
+/// Fetch a single AuditsFile from its source, filling in any criteria
+/// descriptions.
+///
+/// If the import has a public key, the detached signature next to `url` is
+/// verified before the file is parsed. The hash of the file is returned
+/// alongside it, to be recorded in imports.lock.
+async fn fetch_single_imported_audit(
+    fetcher: &ImportFetcher<'_>,
+    local_criteria_mapper: &CriteriaMapper,
+    name: &str,
+    url: &str,
+    public_key: Option<&ImportKey>,
+    exclude: &[PackageName],
+    criteria_map: &CriteriaMap,
+) -> Result<(AuditsFile, ImportHash), FetchAuditError> {
+    let source = ImportSource::parse(fetcher.cfg, name, url)?;
+    let audit_source = fetcher.read(name, &source).await?;
+    let hash = import_hash(audit_source.source().as_bytes());
+
+    if let Some(public_key) = public_key {
+        let signature_source = source.with_extension(public_key.signature_extension());
+        let signature = fetcher
+            .read(name, &signature_source)
+            .await
+            .map_err(|error| FetchAuditError::MissingSignature {
+                import_name: name.to_owned(),
+                signature_url: signature_source.to_string(),
+                error: Box::new(error),
+            })?;
+        public_key
+            .verify(audit_source.source().as_bytes(), signature.source())
+            .map_err(|error| FetchAuditError::BadSignature {
+                import_name: name.to_owned(),
+                import_url: url.to_owned(),
+                error,
+            })?;
+    }
+
+    let mut audit_file = foreign_audit_source_to_local_warn(name, audit_source)?;
 
     // Remove any excluded audits from the live copy. We'll effectively
     // pretend they don't exist upstream.
@@ -1141,6 +2057,10 @@ async fn fetch_single_imported_audit(
                         error,
                     }
                 })?;
+                let network = fetcher.network.ok_or_else(|| FetchAuditError::Frozen {
+                    import_name: name.to_owned(),
+                    import_url: url_string.clone(),
+                })?;
                 let bytes = network.download(url.clone()).await?;
                 let description =
                     String::from_utf8(bytes).map_err(|error| DownloadError::InvalidText {
@@ -1154,14 +2074,12 @@ async fn fetch_single_imported_audit(
     )
     .await?;
 
-    // Clear out the description URL and implies, as those will never be used
-    // locally.
+    // Clear out the description URL, as it will never be used locally.
     for criteria_entry in audit_file.criteria.values_mut() {
         criteria_entry.description_url = None;
-        criteria_entry.implies = Vec::new();
     }
 
-    Ok(audit_file)
+    Ok((audit_file, hash))
 }
 
 pub(crate) struct ForeignAuditFileToLocalResult {
@@ -1417,13 +2335,15 @@ async fn import_unpublished_entries(
     // they are actually published. We also skip git versions, as those can
     // always be audit-as-crates-io.
     let audit_as_packages = crate::first_party_packages_strict(metadata, config_file)
-        .filter(|package| package.is_third_party(&config_file.policy))
+        .filter(|package| package.is_third_party(config_file))
         .filter(|package| package.git_rev().is_none());
     for package in audit_as_packages {
         // If we have no versions for the crate, it cannot be
         // audit-as-crates-io, so treat it as an error.
         // FIXME: better errors here?
-        let versions = cache.published_versions(network, &package.name).await?;
+        let versions = cache
+            .published_versions(network, None, &package.name)
+            .await?;
 
         // Pick which verison of the crate we'd audit as. We prefer the exact
         // version of the crate, followed by the largest version below, and then
@@ -1518,7 +2438,10 @@ async fn import_publisher_versions(
             .packages
             .iter()
             .filter(|pkg| {
-                relevant_packages.contains(&*pkg.name) && pkg.is_third_party(&config_file.policy)
+                // Publisher information is only available for crates.io.
+                relevant_packages.contains(&*pkg.name)
+                    && pkg.is_third_party(config_file)
+                    && pkg.registry(config_file).is_none()
             })
             .map(|pkg| &pkg.name[..])
             .collect()
@@ -1592,36 +2515,136 @@ pub async fn fetch_registry(network: &Network) -> Result<RegistryFile, FetchRegi
     Ok(registry_file)
 }
 
-pub fn user_info_map(imports: &ImportsFile) -> FastMap<CratesUserId, CratesCacheUser> {
-    let mut user_info = FastMap::new();
-    for publisher in imports.publisher.values().flatten() {
-        if let CratesPublisherSource::User {
-            user_id,
-            user_login,
-            user_name,
-        } = &publisher.source
-        {
-            user_info
-                .entry(*user_id)
-                .or_insert_with(|| CratesCacheUser {
-                    login: user_login.clone(),
-                    name: user_name.clone(),
-                });
-        }
+/// Load every advisory from a local clone of the RustSec advisory database.
+///
+/// Withdrawn and informational advisories (e.g. for unmaintained crates) are
+/// skipped, as they don't describe a vulnerability.
+pub fn load_advisory_db(path: &Path) -> Result<Vec<Advisory>, AdvisoryDbError> {
+    #[derive(Deserialize)]
+    struct RustsecAdvisory {
+        advisory: RustsecAdvisoryMetadata,
+        #[serde(default)]
+        versions: RustsecVersions,
     }
-    user_info
-}
 
-type PublishedVersions = Arc<SortedMap<semver::Version, crates_index::Version>>;
+    #[derive(Deserialize)]
+    struct RustsecAdvisoryMetadata {
+        id: AdvisoryId,
+        package: PackageName,
+        title: Option<String>,
+        informational: Option<String>,
+        withdrawn: Option<toml::Value>,
+    }
 
-struct CacheState {
-    /// The loaded DiffCache, will be persisted between invocations
-    diff_cache: DiffCache,
-    /// Command history, will be persisted between invocations
-    command_history: CommandHistory,
-    /// Cache of fetched info from crates.io, will be persisted between invocations
-    crates_cache: CratesCache,
-    /// Package versions and sha256 checksums from index.crates.io
+    #[derive(Deserialize, Default)]
+    struct RustsecVersions {
+        #[serde(default)]
+        patched: Vec<VersionReq>,
+        #[serde(default)]
+        unaffected: Vec<VersionReq>,
+    }
+
+    let read_dir = |path: &Path| {
+        let mut entries = fs::read_dir(path)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| entry.map(|entry| entry.path()))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .map_err(|error| AdvisoryDbError::Read {
+                path: path.to_owned(),
+                error,
+            })?;
+        entries.sort();
+        Ok(entries)
+    };
+
+    let mut advisories = Vec::new();
+    for crate_dir in read_dir(&path.join("crates"))? {
+        if !crate_dir.is_dir() {
+            continue;
+        }
+        for file in read_dir(&crate_dir)? {
+            let extension = file.extension().and_then(OsStr::to_str);
+            if !matches!(extension, Some("md" | "toml")) {
+                continue;
+            }
+            let contents = fs::read_to_string(&file).map_err(|error| AdvisoryDbError::Read {
+                path: file.clone(),
+                error,
+            })?;
+
+            // Markdown advisories start with a fenced block of TOML front
+            // matter, followed by the title as the first heading. Older
+            // advisories are plain TOML files with a `title` key.
+            let (front_matter, heading) = if extension == Some("md") {
+                let Some((front_matter, body)) = contents
+                    .trim_start()
+                    .strip_prefix("```toml")
+                    .and_then(|rest| rest.split_once("\n```"))
+                else {
+                    return Err(AdvisoryDbError::MissingFrontMatter { path: file });
+                };
+                let heading = body
+                    .lines()
+                    .find_map(|line| line.strip_prefix("# "))
+                    .map(|title| title.trim().to_owned());
+                (front_matter, heading)
+            } else {
+                (&contents[..], None)
+            };
+
+            let parsed: RustsecAdvisory =
+                toml::from_str(front_matter).map_err(|error| AdvisoryDbError::Parse {
+                    path: file.clone(),
+                    error,
+                })?;
+            let metadata = parsed.advisory;
+            if metadata.withdrawn.is_some() || metadata.informational.is_some() {
+                continue;
+            }
+            advisories.push(Advisory {
+                title: heading.or(metadata.title).unwrap_or_default(),
+                id: metadata.id,
+                package: metadata.package,
+                patched: parsed.versions.patched,
+                unaffected: parsed.versions.unaffected,
+            });
+        }
+    }
+    Ok(advisories)
+}
+
+pub fn user_info_map(imports: &ImportsFile) -> FastMap<CratesUserId, CratesCacheUser> {
+    let mut user_info = FastMap::new();
+    for publisher in imports.publisher.values().flatten() {
+        if let CratesPublisherSource::User {
+            user_id,
+            user_login,
+            user_name,
+        } = &publisher.source
+        {
+            user_info
+                .entry(*user_id)
+                .or_insert_with(|| CratesCacheUser {
+                    login: user_login.clone(),
+                    name: user_name.clone(),
+                });
+        }
+    }
+    user_info
+}
+
+type PublishedVersions = Arc<SortedMap<semver::Version, crates_index::Version>>;
+
+struct CacheState {
+    /// The loaded DiffCache, will be persisted between invocations
+    diff_cache: DiffCache,
+    /// Command history, will be persisted between invocations
+    command_history: CommandHistory,
+    /// Cache of fetched info from crates.io, will be persisted between invocations
+    crates_cache: CratesCache,
+    /// Package versions and sha256 checksums from index.crates.io
     published_versions: FastMap<PackageName, Arc<tokio::sync::OnceCell<PublishedVersions>>>,
     /// Used to avoid multiple requests to the crates.io API for crate metadata.
     /// Results are populated into crates_cache.
@@ -1646,6 +2669,8 @@ pub struct Cache {
     command_history_path: Option<PathBuf>,
     /// Path to the CratesCache (for when we want to save it back)
     publisher_cache_path: Option<PathBuf>,
+    /// Vendored sources which crates.io has been replaced with, if any.
+    vendored_sources: Option<VendoredSources>,
     /// Semaphore preventing exceeding the maximum number of concurrent diffs.
     diff_semaphore: tokio::sync::Semaphore,
     /// The time to use as `now` when considering cache expiry.
@@ -1704,6 +2729,7 @@ impl Cache {
                 diff_cache_path: None,
                 command_history_path: None,
                 publisher_cache_path: None,
+                vendored_sources: None,
                 diff_semaphore: tokio::sync::Semaphore::new(MAX_CONCURRENT_DIFFS),
                 now: cfg.now,
                 state: Mutex::new(CacheState {
@@ -1772,6 +2798,7 @@ impl Cache {
             diff_cache_path: Some(diff_cache_path),
             command_history_path: Some(command_history_path),
             publisher_cache_path: Some(publisher_cache_path),
+            vendored_sources: VendoredSources::find(),
             diff_semaphore: tokio::sync::Semaphore::new(MAX_CONCURRENT_DIFFS),
             now: cfg.now,
             state: Mutex::new(CacheState {
@@ -1791,6 +2818,7 @@ impl Cache {
         &self,
         metadata: &cargo_metadata::Metadata,
         network: Option<&Network>,
+        registry: Option<CargoRegistry<'_>>,
         package: PackageStr<'_>,
         version: &VetVersion,
     ) -> Result<PathBuf, FetchError> {
@@ -1802,7 +2830,7 @@ impl Cache {
             let mut guard = self.state.lock().unwrap();
             guard
                 .fetched_packages
-                .entry((package.to_owned(), version.clone()))
+                .entry((registry_cache_key(registry, package), version.clone()))
                 .or_default()
                 .clone()
         };
@@ -1846,10 +2874,34 @@ impl Cache {
 
                 let version = &version.semver;
 
-                let dir_name = format!("{package}-{version}");
+                // Crates from alternative registries are namespaced by the
+                // registry's name, as they may share names with crates.io crates.
+                let dir_name = match registry {
+                    Some(registry) => format!("{package}-{version}@{}", registry.name),
+                    None => format!("{package}-{version}"),
+                };
 
-                // First try to get a cached copy from cargo's registry.
-                if let Ok(cargo_home) = home::cargo_home() {
+                // Vendored sources are what will actually be built, so prefer
+                // them over any other copy, after making sure that they haven't
+                // been modified.
+                if let (None, Some(vendored_sources)) = (registry, &self.vendored_sources) {
+                    if let Some(vendored_src) = vendored_sources.package_dir(package, version) {
+                        let package = package.to_owned();
+                        let version = version.clone();
+                        let vendored_src_ = vendored_src.clone();
+                        tokio::task::spawn_blocking(move || {
+                            verify_vendored_package(&vendored_src_, &package, &version, None, None)
+                        })
+                        .await
+                        .expect("failed to join")?;
+                        return Ok(vendored_src);
+                    }
+                }
+
+                // Next try to get a cached copy from cargo's registry. We can
+                // only locate cargo's copies of crates.io crates, as the cache
+                // directories for other registries are named using a hash.
+                if let (None, Ok(cargo_home)) = (registry, home::cargo_home()) {
                     // Check both the sparse and git registry caches.
                     for registry in [CARGO_REGISTRY_CRATES_IO_HTTP, CARGO_REGISTRY_CRATES_IO_GIT] {
                         let fetched_src = cargo_home
@@ -1900,8 +2952,16 @@ impl Cache {
                         })?;
 
                         // We don't have it, so download it
-                        let url =
-                            format!("https://crates.io/api/v1/crates/{package}/{version}/download");
+                        let url = match registry {
+                            Some(registry) => {
+                                registry_download_url(&registry.entry.download, package, version)
+                            }
+                            None => registry_download_url(
+                                &network.crates_io().await?.download,
+                                package,
+                                version,
+                            ),
+                        };
                         let url = Url::parse(&url).map_err(|error| FetchError::InvalidUrl {
                             url: url.clone(),
                             error,
@@ -2054,9 +3114,12 @@ impl Cache {
         &self,
         metadata: &cargo_metadata::Metadata,
         network: Option<&Network>,
+        registry: Option<CargoRegistry<'_>>,
         package: PackageStr<'_>,
         delta: &Delta,
     ) -> Result<DiffStat, FetchAndDiffError> {
+        let cache_key = registry_cache_key(registry, package);
+
         // Lock the mutex to extract a reference to the OnceCell which we'll use
         // to asynchronously synchronize on and diff the package only once in a
         // single execution.
@@ -2071,7 +3134,7 @@ impl Cache {
             if let Some(cached) = guard
                 .diff_cache
                 .diffs
-                .get(package)
+                .get(&cache_key)
                 .and_then(|cache| cache.get(delta))
                 .cloned()
             {
@@ -2099,7 +3162,7 @@ impl Cache {
 
             guard
                 .diffed
-                .entry((package.to_owned(), delta.clone()))
+                .entry((cache_key.clone(), delta.clone()))
                 .or_default()
                 .clone()
         };
@@ -2107,11 +3170,14 @@ impl Cache {
         let diffstat = once_cell
             .get_or_try_init(|| async {
                 let from = match &delta.from {
-                    Some(from) => self.fetch_package(metadata, network, package, from).await?,
+                    Some(from) => {
+                        self.fetch_package(metadata, network, registry, package, from)
+                            .await?
+                    }
                     None => self.root.as_ref().unwrap().join(CACHE_EMPTY_PACKAGE),
                 };
                 let to = self
-                    .fetch_package(metadata, network, package, &delta.to)
+                    .fetch_package(metadata, network, registry, package, &delta.to)
                     .await?;
 
                 // Have fetches, do a real diffstat
@@ -2127,7 +3193,7 @@ impl Cache {
                     guard
                         .diff_cache
                         .diffs
-                        .entry(package.to_string())
+                        .entry(cache_key.clone())
                         .or_default()
                         .insert(delta.clone(), diffstat.clone());
                 }
@@ -2138,6 +3204,278 @@ impl Cache {
         Ok(diffstat.clone())
     }
 
+    /// The bare repository in the cache which commits from `repo` are fetched
+    /// into.
+    fn git_repo_dir(&self, repo: &str) -> Option<PathBuf> {
+        let root = self.root.as_ref()?;
+        Some(
+            root.join(CACHE_GIT_IMPORTS)
+                .join(&format!("{:x}", Sha256::digest(repo.as_bytes()))[..16]),
+        )
+    }
+
+    /// Whether the commit `rev` from `repo` has already been fetched into the
+    /// cache, so that it can be read while frozen.
+    pub async fn has_git_rev(&self, repo: &str, rev: &str) -> bool {
+        let Some(repo_dir) = self.git_repo_dir(repo) else {
+            return false;
+        };
+        repo_dir.exists()
+            && run_git(&repo_dir, &["cat-file", "-e", &format!("{rev}^{{commit}}")])
+                .await
+                .is_ok()
+    }
+
+    /// Read the file at `path` in the given commit of a git repository, used
+    /// for `git+` imports.
+    ///
+    /// Commits are fetched into a bare repository in the cache, and are only
+    /// fetched once, as they can't change. If `frozen`, commits which haven't
+    /// been fetched yet can't be read.
+    pub async fn read_git_file(
+        &self,
+        repo: &str,
+        rev: &str,
+        path: &str,
+        frozen: bool,
+    ) -> Result<String, GitImportError> {
+        let repo_dir = self.git_repo_dir(repo).ok_or(GitImportError::NoCache)?;
+
+        if !repo_dir.exists() {
+            if frozen {
+                return Err(GitImportError::NotCached {
+                    rev: rev.to_owned(),
+                });
+            }
+            tokio::fs::create_dir_all(&repo_dir)
+                .await
+                .map_err(|error| GitImportError::CreateDir {
+                    target: repo_dir.clone(),
+                    error,
+                })?;
+            run_git(&repo_dir, &["init", "--bare", "--quiet"]).await?;
+        }
+
+        let commit = format!("{rev}^{{commit}}");
+        if run_git(&repo_dir, &["cat-file", "-e", &commit])
+            .await
+            .is_err()
+        {
+            if frozen {
+                return Err(GitImportError::NotCached {
+                    rev: rev.to_owned(),
+                });
+            }
+            run_git(&repo_dir, &["fetch", "--quiet", "--depth=1", repo, rev]).await?;
+        }
+
+        let contents = run_git(&repo_dir, &["show", &format!("{rev}:{path}")]).await?;
+        Ok(String::from_utf8(contents).map_err(CommandError::BadOutput)?)
+    }
+
+    /// Find the sha256 checksum of the `.crate` file for a package, in the same
+    /// format as `Cargo.lock`.
+    ///
+    /// The checksum of a previously fetched `.crate` file is preferred, as it's
+    /// the file which was reviewed. Otherwise the checksum published in the
+    /// registry's index is used. Returns `None` for git versions, or if the
+    /// checksum can't be found.
+    pub async fn package_checksum(
+        &self,
+        network: Option<&Network>,
+        registry: Option<CargoRegistry<'_>>,
+        package: PackageStr<'_>,
+        version: &VetVersion,
+    ) -> Option<String> {
+        if version.git_rev.is_some() {
+            return None;
+        }
+        let version = &version.semver;
+
+        let dir_name = match registry {
+            Some(registry) => format!("{package}-{version}@{}", registry.name),
+            None => format!("{package}-{version}"),
+        };
+        let mut candidates = vec![];
+        if let Some(root) = &self.root {
+            candidates.push(
+                root.join(CACHE_REGISTRY_CACHE)
+                    .join(format!("{dir_name}.crate")),
+            );
+        }
+        if let (None, Ok(cargo_home)) = (registry, home::cargo_home()) {
+            for registry in [CARGO_REGISTRY_CRATES_IO_HTTP, CARGO_REGISTRY_CRATES_IO_GIT] {
+                candidates.push(
+                    cargo_home
+                        .join(CARGO_REGISTRY)
+                        .join(CARGO_REGISTRY_CACHE)
+                        .join(registry)
+                        .join(format!("{dir_name}.crate")),
+                );
+            }
+        }
+        for candidate in candidates {
+            if let Ok(contents) = tokio::fs::read(&candidate).await {
+                return Some(format!("{:x}", Sha256::digest(contents)));
+            }
+        }
+
+        let versions = self
+            .published_versions(network?, registry, package)
+            .await
+            .ok()?;
+        let checksum = versions.get(version)?.checksum();
+        Some(checksum.iter().map(|byte| format!("{byte:02x}")).collect())
+    }
+
+    /// Make sure the `.crate` file for the given crates.io package is in the
+    /// cache, downloading it if necessary, and return its path.
+    ///
+    /// Unlike [`Cache::fetch_package`], this never uses cargo's own copy of the
+    /// package, as the archive itself is needed for bundles.
+    pub async fn fetch_package_archive(
+        &self,
+        network: &Network,
+        package: PackageStr<'_>,
+        version: &semver::Version,
+    ) -> Result<PathBuf, FetchError> {
+        let root = self.root.as_ref().unwrap();
+        let fetched_package = root
+            .join(CACHE_REGISTRY_CACHE)
+            .join(format!("{package}-{version}.crate"));
+        if !fetched_package.exists() {
+            let url = registry_download_url(&network.crates_io().await?.download, package, version);
+            let url = Url::parse(&url).map_err(|error| FetchError::InvalidUrl {
+                url: url.clone(),
+                error,
+            })?;
+            info!(
+                "downloading package {}:{} from {} to {}",
+                package,
+                version,
+                url,
+                fetched_package.display()
+            );
+            network.download_and_persist(url, &fetched_package).await?;
+        }
+        Ok(fetched_package)
+    }
+
+    /// Read the `.crate` file for a crates.io package, preferring a copy in the
+    /// cache or cargo's registry cache whose sha256 checksum is `checksum`, and
+    /// otherwise downloading it into the cache.
+    ///
+    /// The checksum of a downloaded file isn't checked. Returns `None` if no
+    /// matching copy is cached and `network` is `None`, or the cache is mocked.
+    pub async fn package_archive(
+        &self,
+        network: Option<&Network>,
+        package: PackageStr<'_>,
+        version: &semver::Version,
+        checksum: &str,
+    ) -> Result<Option<Vec<u8>>, FetchError> {
+        let Some(root) = &self.root else {
+            return Ok(None);
+        };
+        let file_name = format!("{package}-{version}.crate");
+        let mut candidates = vec![root.join(CACHE_REGISTRY_CACHE).join(&file_name)];
+        if let Ok(cargo_home) = home::cargo_home() {
+            for registry in [CARGO_REGISTRY_CRATES_IO_HTTP, CARGO_REGISTRY_CRATES_IO_GIT] {
+                candidates.push(
+                    cargo_home
+                        .join(CARGO_REGISTRY)
+                        .join(CARGO_REGISTRY_CACHE)
+                        .join(registry)
+                        .join(&file_name),
+                );
+            }
+        }
+        for candidate in candidates {
+            if let Ok(contents) = tokio::fs::read(&candidate).await {
+                if format!("{:x}", Sha256::digest(&contents)) == checksum {
+                    return Ok(Some(contents));
+                }
+            }
+        }
+
+        let Some(network) = network else {
+            return Ok(None);
+        };
+        let fetched_package = root.join(CACHE_REGISTRY_CACHE).join(&file_name);
+        let url = registry_download_url(&network.crates_io().await?.download, package, version);
+        let url = Url::parse(&url).map_err(|error| FetchError::InvalidUrl {
+            url: url.clone(),
+            error,
+        })?;
+        info!(
+            "downloading package {}:{} from {} to {}",
+            package,
+            version,
+            url,
+            fetched_package.display()
+        );
+        network.download_and_persist(url, &fetched_package).await?;
+        let contents =
+            tokio::fs::read(&fetched_package)
+                .await
+                .map_err(|error| FetchError::OpenCached {
+                    target: fetched_package.clone(),
+                    error,
+                })?;
+        Ok(Some(contents))
+    }
+
+    /// Copy verified `.crate` files from a bundle into the cache. Does nothing
+    /// if the cache is mocked.
+    pub fn add_package_archives(&self, crate_files: &[PathBuf]) -> Result<(), io::Error> {
+        let Some(root) = &self.root else {
+            return Ok(());
+        };
+        for crate_file in crate_files {
+            fs::copy(
+                crate_file,
+                root.join(CACHE_REGISTRY_CACHE)
+                    .join(crate_file.file_name().unwrap()),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Copies of the crates.io metadata and diffstats in the cache, to be
+    /// included in a bundle.
+    pub fn bundle_caches(&self) -> (CratesCache, DiffCache) {
+        let guard = self.state.lock().unwrap();
+        (guard.crates_cache.clone(), guard.diff_cache.clone())
+    }
+
+    /// Merge the crates.io metadata and diffstats from a bundle into the
+    /// cache. Crates.io metadata from the bundle replaces any cached metadata
+    /// which knows about fewer versions of the crate.
+    pub fn merge_bundle_caches(&self, crates_cache: CratesCache, diff_cache: DiffCache) {
+        let mut guard = self.state.lock().unwrap();
+        for (package, entry) in crates_cache.crates {
+            match guard.crates_cache.crates.entry(package) {
+                std::collections::btree_map::Entry::Vacant(vacant) => {
+                    vacant.insert(entry);
+                }
+                std::collections::btree_map::Entry::Occupied(mut occupied) => {
+                    if entry.versions.len() >= occupied.get().versions.len() {
+                        occupied.insert(entry);
+                    }
+                }
+            }
+        }
+        guard.crates_cache.users.extend(crates_cache.users);
+        for (package, diffs) in diff_cache.diffs {
+            guard
+                .diff_cache
+                .diffs
+                .entry(package)
+                .or_default()
+                .extend(diffs);
+        }
+    }
+
     /// Run a garbage-collection pass over the cache, removing any files which
     /// aren't supposed to be there, or which haven't been touched for an
     /// extended period of time.
@@ -2322,15 +3660,37 @@ impl Cache {
     }
 
     /// For a given package, fetch the list of versions published on crates.io,
-    /// along with the corresponding index entry.
+    /// or the given alternative registry, along with the corresponding index
+    /// entry.
     ///
-    /// This information will be pulled live from index.crates.io each time
-    /// cargo-vet is run, but is cached in-memory.
+    /// This information will be pulled live from index.crates.io (or the
+    /// registry's sparse index) each time cargo-vet is run, but is cached
+    /// in-memory.
     pub async fn published_versions(
         &self,
         network: &Network,
+        registry: Option<CargoRegistry<'_>>,
         package: PackageStr<'_>,
     ) -> Result<PublishedVersions, CrateInfoError> {
+        let index_url = match registry {
+            Some(registry) => {
+                let index = registry
+                    .entry
+                    .index
+                    .strip_prefix("sparse+")
+                    .ok_or_else(|| CrateInfoError::UnsupportedIndex {
+                        registry: registry.name.to_owned(),
+                    })?;
+                let url = format!("{}/", index.trim_end_matches('/'));
+                Url::parse(&url).map_err(|error| CrateInfoError::InvalidIndexUrl {
+                    registry: registry.name.to_owned(),
+                    url,
+                    error,
+                })?
+            }
+            None => network.crates_io().await?.index.clone(),
+        };
+
         // Lock the mutex to extract a reference to the OnceCell which we'll use
         // to asynchronously synchronize on and fetch the index metadata only
         // once in a single execution.
@@ -2339,7 +3699,7 @@ impl Cache {
             let mut guard = self.state.lock().unwrap();
             guard
                 .published_versions
-                .entry(package.to_owned())
+                .entry(registry_cache_key(registry, package))
                 .or_default()
                 .clone()
         };
@@ -2349,22 +3709,11 @@ impl Cache {
         // have some limited support for interacting with this cache.
         let res: Result<_, CrateInfoError> = once_cell
             .get_or_try_init(|| async {
-                // Crate names can only be a subset of ascii (valid rust
-                // identifier characters and `-`), so using `len()` and indexing
-                // will result in valid counts/characters.
-                let mut url = String::from("https://index.crates.io/");
-                use std::fmt::Write;
-                match package.len() {
-                    1 => write!(url, "1/{package}"),
-                    2 => write!(url, "2/{package}"),
-                    3 => write!(url, "3/{}/{package}", &package[0..1]),
-                    _ => write!(url, "{}/{}/{package}", &package[0..2], &package[2..4]),
-                }
-                .expect("writing to a String should not fail");
                 // Crate index always use lowercases, but crate name may contain
                 // uppercase characters.
-                url.make_ascii_lowercase();
-                let url = Url::parse(&url).expect("invalid crate name");
+                let mut path = format!("{}/{package}", index_prefix(package));
+                path.make_ascii_lowercase();
+                let url = index_url.join(&path).expect("invalid crate name");
 
                 // Fetch the crate's entry from the index
                 let response = match network.download(url).await {
@@ -2439,7 +3788,7 @@ impl Cache {
         // we do, we don't need to update anything.
         // This will also check if the crate does not exist, in which case we'll
         // early-return with an error.
-        let published_versions = self.published_versions(network, package).await?;
+        let published_versions = self.published_versions(network, None, package).await?;
 
         // Lock the mutex. We'll either detect that we already have metadata for
         // all published versions, or we'll extract a reference to the OnceCell
@@ -2468,7 +3817,11 @@ impl Cache {
         once_cell
             .get_or_try_init(|| async {
                 info!("fetching crate metadata for crate {}", package);
-                let url = Url::parse(&format!("https://crates.io/api/v1/crates/{}", package))
+                let url = network
+                    .crates_io()
+                    .await?
+                    .api
+                    .join(&format!("api/v1/crates/{}", package))
                     .expect("invalid crate name");
                 let response = network.download(url).await?;
                 let result = load_json::<CratesAPICrate>(&response[..])?;
@@ -2573,6 +3926,443 @@ impl Cache {
     }
 }
 
+/// Key used to identify a package in the cache, which is namespaced by the
+/// registry's name for crates from alternative registries.
+fn registry_cache_key(registry: Option<CargoRegistry<'_>>, package: PackageStr<'_>) -> PackageName {
+    match registry {
+        Some(registry) => format!("{package}@{}", registry.name),
+        None => package.to_owned(),
+    }
+}
+
+/// The directory prefix used for a crate within a registry index.
+fn index_prefix(package: PackageStr<'_>) -> String {
+    // Crate names can only be a subset of ascii (valid rust identifier
+    // characters and `-`), so using `len()` and indexing will result in valid
+    // counts/characters.
+    match package.len() {
+        1 => "1".to_owned(),
+        2 => "2".to_owned(),
+        3 => format!("3/{}", &package[0..1]),
+        _ => format!("{}/{}", &package[0..2], &package[2..4]),
+    }
+}
+
+/// Expand the download URL template of a registry (the `dl` key of its
+/// `config.json`) for a specific version of a crate, following the same rules
+/// as cargo.
+pub(crate) fn registry_download_url(
+    template: &str,
+    package: PackageStr<'_>,
+    version: &semver::Version,
+) -> String {
+    const MARKERS: [&str; 4] = ["{crate}", "{version}", "{prefix}", "{lowerprefix}"];
+    if !MARKERS.iter().any(|marker| template.contains(marker)) {
+        return format!(
+            "{}/{package}/{version}/download",
+            template.trim_end_matches('/')
+        );
+    }
+    let prefix = index_prefix(package);
+    template
+        .replace("{crate}", package)
+        .replace("{version}", &version.to_string())
+        .replace("{prefix}", &prefix)
+        .replace("{lowerprefix}", &prefix.to_ascii_lowercase())
+}
+
+/// The source crates.io has been replaced with by cargo's source replacement
+/// (`[source.crates-io] replace-with = "..."`).
+pub enum CratesIoReplacement {
+    /// A remote registry, with its index URL as written in the cargo config.
+    Registry { name: String, index: String },
+    /// A directory of vendored crates, such as one created by `cargo vendor`.
+    Directory(PathBuf),
+    /// Any other kind of source, such as a local registry.
+    Other { name: String },
+}
+
+/// Find what crates.io has been replaced with by the cargo config files which
+/// apply to the current directory, if anything.
+pub fn crates_io_replacement() -> Option<CratesIoReplacement> {
+    let cwd = std::env::current_dir().ok()?;
+    // Config files closer to the current directory take precedence, so merge
+    // the `[source]` tables from the furthest file first. Relative paths are
+    // relative to the directory containing the config's `.cargo` directory.
+    let config_paths: Vec<_> = cargo_config2::Walk::new(&cwd).collect();
+    let mut sources: SortedMap<String, (toml::Value, PathBuf)> = SortedMap::new();
+    for path in config_paths.iter().rev() {
+        let Some(config) = fs::read_to_string(path)
+            .ok()
+            .and_then(|config| config.parse::<toml::Table>().ok())
+        else {
+            continue;
+        };
+        let base = path
+            .parent()
+            .and_then(Path::parent)
+            .unwrap_or(Path::new(""))
+            .to_owned();
+        if let Some(toml::Value::Table(config_sources)) = config.get("source") {
+            for (name, source) in config_sources {
+                sources.insert(name.clone(), (source.clone(), base.clone()));
+            }
+        }
+    }
+
+    // Follow the chain of replacements, bounded in case of cycles.
+    let mut name = "crates-io";
+    for _ in 0..sources.len() {
+        let (source, base) = sources.get(name)?;
+        if let Some(replacement) = source.get("replace-with").and_then(|r| r.as_str()) {
+            name = replacement;
+            continue;
+        }
+        if name == "crates-io" {
+            return None;
+        }
+        if let Some(index) = source.get("registry").and_then(|r| r.as_str()) {
+            return Some(CratesIoReplacement::Registry {
+                name: name.to_owned(),
+                index: index.to_owned(),
+            });
+        }
+        if let Some(directory) = source.get("directory").and_then(|d| d.as_str()) {
+            return Some(CratesIoReplacement::Directory(base.join(directory)));
+        }
+        return Some(CratesIoReplacement::Other {
+            name: name.to_owned(),
+        });
+    }
+    None
+}
+
+/// A directory of vendored crates.io crates, as created by `cargo vendor`.
+#[derive(Debug, Clone)]
+pub struct VendoredSources {
+    dir: PathBuf,
+}
+
+/// The contents of the `.cargo-checksum.json` file in each vendored crate.
+#[derive(Deserialize)]
+struct CargoChecksums {
+    files: SortedMap<String, String>,
+    package: Option<String>,
+}
+
+impl VendoredSources {
+    pub fn new(dir: PathBuf) -> Self {
+        VendoredSources { dir }
+    }
+
+    /// Find the vendored sources crates.io has been replaced with, if any.
+    pub fn find() -> Option<Self> {
+        match crates_io_replacement()? {
+            CratesIoReplacement::Directory(dir) => Some(VendoredSources::new(dir)),
+            _ => None,
+        }
+    }
+
+    /// Locate the vendored copy of a crate. `cargo vendor` only includes the
+    /// version in the directory name when vendoring multiple versions of a
+    /// crate, or when passed `--versioned-dirs`.
+    pub fn package_dir(
+        &self,
+        package: PackageStr<'_>,
+        version: &semver::Version,
+    ) -> Option<PathBuf> {
+        let versioned = self.dir.join(format!("{package}-{version}"));
+        if versioned.join(CARGO_CHECKSUM_FILE).is_file() {
+            return Some(versioned);
+        }
+        let unversioned = self.dir.join(package);
+        let manifest = fs::read_to_string(unversioned.join(CARGO_TOML_FILE)).ok()?;
+        let manifest: toml::Table = manifest.parse().ok()?;
+        let vendored_version = manifest.get("package")?.get("version")?.as_str()?;
+        (vendored_version == version.to_string()).then_some(unversioned)
+    }
+
+    /// Check that every crates.io crate in the build graph is vendored without
+    /// modifications, and matches the checksum recorded in `Cargo.lock`.
+    ///
+    /// Each vendored file is also compared against the `.crate` file with the
+    /// checksum from `Cargo.lock`, which is fetched if it isn't cached. While
+    /// frozen, crates whose `.crate` file isn't cached are only checked
+    /// against their `.cargo-checksum.json`.
+    pub async fn verify(
+        &self,
+        metadata: &cargo_metadata::Metadata,
+        network: Option<&Network>,
+        cache: &Cache,
+    ) -> Result<(), VendorErrors> {
+        let lock_checksums = cargo_lock_checksums(metadata);
+        let mut errors = Vec::new();
+        let mut unavailable = 0;
+        for package in &metadata.packages {
+            if !package.is_crates_io() {
+                continue;
+            }
+            let Some(dir) = self.package_dir(&package.name, &package.version) else {
+                errors.push(VendorError::MissingPackage {
+                    package: package.name.to_string(),
+                    version: package.version.clone(),
+                    dir: self.dir.clone(),
+                });
+                continue;
+            };
+            let lock_checksum = lock_checksums
+                .get(&cargo_lock_key(package))
+                .map(|checksum| &checksum[..]);
+
+            let mut crate_files = None;
+            if let Some(lock_checksum) = lock_checksum {
+                match cache
+                    .package_archive(network, &package.name, &package.version, lock_checksum)
+                    .await
+                {
+                    Ok(Some(contents)) => {
+                        let found = format!("{:x}", Sha256::digest(&contents));
+                        if found != lock_checksum {
+                            errors.push(VendorError::CrateChecksum {
+                                package: package.name.to_string(),
+                                version: package.version.clone(),
+                                expected: lock_checksum.to_owned(),
+                                found,
+                            });
+                            continue;
+                        }
+                        match crate_file_checksums(&contents, &package.name, &package.version) {
+                            Ok(files) => crate_files = Some(files),
+                            Err(error) => {
+                                errors.push(VendorError::InvalidCrate {
+                                    package: package.name.to_string(),
+                                    version: package.version.clone(),
+                                    error,
+                                });
+                                continue;
+                            }
+                        }
+                    }
+                    Ok(None) => unavailable += 1,
+                    Err(error) => {
+                        errors.push(VendorError::FetchCrate {
+                            package: package.name.to_string(),
+                            version: package.version.clone(),
+                            error: Box::new(error),
+                        });
+                        continue;
+                    }
+                }
+            }
+
+            if let Err(error) = verify_vendored_package(
+                &dir,
+                &package.name,
+                &package.version,
+                lock_checksum,
+                crate_files.as_ref(),
+            ) {
+                errors.push(error);
+            }
+        }
+        if unavailable > 0 {
+            warn!(
+                "{unavailable} vendored crates were only checked against their .cargo-checksum.json, as their .crate files haven't been fetched and can't be while frozen"
+            );
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(VendorErrors { errors })
+        }
+    }
+}
+
+/// The key for a package in the result of [`cargo_lock_checksums`]. Sources
+/// are written the same way in `Cargo.lock` and `cargo metadata`.
+pub type CargoLockKey = (PackageName, semver::Version, String);
+
+pub fn cargo_lock_key(package: &cargo_metadata::Package) -> CargoLockKey {
+    (
+        package.name.to_string(),
+        package.version.clone(),
+        package
+            .source
+            .as_ref()
+            .map(|source| source.repr.clone())
+            .unwrap_or_default(),
+    )
+}
+
+/// Read the checksums of the `.crate` files of registry packages from the
+/// workspace's `Cargo.lock`. Returns no checksums if the lockfile can't be
+/// read.
+pub fn cargo_lock_checksums(
+    metadata: &cargo_metadata::Metadata,
+) -> SortedMap<CargoLockKey, String> {
+    #[derive(Deserialize)]
+    struct CargoLock {
+        #[serde(default)]
+        package: Vec<CargoLockPackage>,
+    }
+    #[derive(Deserialize)]
+    struct CargoLockPackage {
+        name: PackageName,
+        version: semver::Version,
+        source: Option<String>,
+        checksum: Option<String>,
+    }
+
+    let lock_path = metadata.workspace_root.join(CARGO_LOCK_FILE);
+    let Some(lock) = fs::read_to_string(lock_path)
+        .ok()
+        .and_then(|lock| toml::from_str::<CargoLock>(&lock).ok())
+    else {
+        return SortedMap::new();
+    };
+    lock.package
+        .into_iter()
+        .filter_map(|package| {
+            Some((
+                (package.name, package.version, package.source?),
+                package.checksum?,
+            ))
+        })
+        .collect()
+}
+
+/// The sha256 checksum of each file in a `.crate` file, keyed by its path
+/// within the package, as in `.cargo-checksum.json`.
+fn crate_file_checksums(
+    contents: &[u8],
+    package: PackageStr<'_>,
+    version: &semver::Version,
+) -> Result<SortedMap<String, String>, io::Error> {
+    let prefix = format!("{package}-{version}/");
+    let mut archive = Archive::new(GzDecoder::new(contents));
+    let mut files = SortedMap::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path()?.to_string_lossy().into_owned();
+        let Some(rel_path) = path.strip_prefix(&prefix) else {
+            continue;
+        };
+        let rel_path = rel_path.to_owned();
+        let mut hasher = Sha256::new();
+        io::copy(&mut entry, &mut hasher)?;
+        files.insert(rel_path, format!("{:x}", hasher.finalize()));
+    }
+    Ok(files)
+}
+
+/// Verify the files of a vendored crate against its `.cargo-checksum.json`,
+/// and optionally the checksum of the `.crate` file it was vendored from, and
+/// the checksums of the files within that `.crate` file.
+///
+/// Unlike cargo, files which aren't listed in the checksums are also rejected,
+/// as they could still be used by the build.
+fn verify_vendored_package(
+    dir: &Path,
+    package: PackageStr<'_>,
+    version: &semver::Version,
+    lock_checksum: Option<&str>,
+    crate_files: Option<&SortedMap<String, String>>,
+) -> Result<(), VendorError> {
+    let read_error = |path: &Path, error| VendorError::Read {
+        package: package.to_owned(),
+        version: version.clone(),
+        path: path.to_owned(),
+        error,
+    };
+
+    let checksums_path = dir.join(CARGO_CHECKSUM_FILE);
+    let checksums =
+        fs::read(&checksums_path).map_err(|error| read_error(&checksums_path, error))?;
+    let checksums: CargoChecksums =
+        serde_json::from_slice(&checksums).map_err(|error| VendorError::InvalidChecksums {
+            package: package.to_owned(),
+            version: version.clone(),
+            error,
+        })?;
+
+    if let Some(expected) = lock_checksum {
+        let Some(found) = &checksums.package else {
+            return Err(VendorError::MissingPackageChecksum {
+                package: package.to_owned(),
+                version: version.clone(),
+                expected: expected.to_owned(),
+            });
+        };
+        if expected != found {
+            return Err(VendorError::PackageChecksum {
+                package: package.to_owned(),
+                version: version.clone(),
+                expected: expected.to_owned(),
+                found: found.clone(),
+            });
+        }
+    }
+
+    // Collect the relative paths of every file in the vendored crate.
+    let mut files = FastSet::new();
+    let mut dirs = vec![dir.to_owned()];
+    while let Some(current) = dirs.pop() {
+        for entry in fs::read_dir(&current).map_err(|error| read_error(&current, error))? {
+            let entry = entry.map_err(|error| read_error(&current, error))?;
+            let file_type = entry
+                .file_type()
+                .map_err(|error| read_error(&entry.path(), error))?;
+            if file_type.is_dir() {
+                dirs.push(entry.path());
+                continue;
+            }
+            let path = entry.path();
+            let rel_path = path.strip_prefix(dir).unwrap();
+            let rel_path = rel_path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            if rel_path != CARGO_CHECKSUM_FILE {
+                files.insert(rel_path);
+            }
+        }
+    }
+
+    for (path, expected) in &checksums.files {
+        if !files.remove(path) {
+            return Err(VendorError::MissingFile {
+                package: package.to_owned(),
+                version: version.clone(),
+                path: path.clone(),
+            });
+        }
+        let full_path = dir.join(path);
+        let contents = fs::read(&full_path).map_err(|error| read_error(&full_path, error))?;
+        let found = format!("{:x}", Sha256::digest(contents));
+        if found != *expected
+            || crate_files.is_some_and(|crate_files| crate_files.get(path) != Some(&found))
+        {
+            return Err(VendorError::ModifiedFile {
+                package: package.to_owned(),
+                version: version.clone(),
+                path: path.clone(),
+            });
+        }
+    }
+    if let Some(path) = files.into_iter().min() {
+        return Err(VendorError::AddedFile {
+            package: package.to_owned(),
+            version: version.clone(),
+            path,
+        });
+    }
+    Ok(())
+}
+
 /// Queries a package in the crates.io registry for a specific published version
 pub fn exact_version<'a>(
     this: &'a crates_index::Crate,
@@ -2675,6 +4465,177 @@ fn create_unpack_lock(unpack_dir: &Path) -> Result<(), io::Error> {
     Ok(())
 }
 
+/// Everything `check` and `suggest` need from the network, collected by
+/// `cargo vet bundle export` so it can be moved to an offline machine.
+///
+/// Bundles are gzipped tarballs, containing the live imports in `imports.lock`
+/// format, the crates.io metadata and diffstat caches, and optionally some
+/// `.crate` files under `crates/`.
+pub struct Bundle {
+    pub imports: ImportsFile,
+    pub crates_cache: CratesCache,
+    pub diff_cache: DiffCache,
+}
+
+impl Bundle {
+    /// Write the bundle to `path`, including the given `.crate` files.
+    pub fn write(&self, path: &Path, crate_files: &[PathBuf]) -> Result<(), BundleError> {
+        let imports = store_imports(self.imports.clone(), &FastMap::new())?;
+        let crates_cache = store_publisher_cache(self.crates_cache.clone())?;
+        let diff_cache = store_diff_cache(self.diff_cache.clone())?;
+
+        let write_error = |error| BundleError::Write {
+            path: path.to_owned(),
+            error,
+        };
+        let file = File::create(path).map_err(write_error)?;
+        let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(
+            file,
+            flate2::Compression::default(),
+        ));
+        for (name, contents) in [
+            (BUNDLE_IMPORTS, imports),
+            (BUNDLE_CRATES_IO_CACHE, crates_cache),
+            (BUNDLE_DIFF_CACHE, diff_cache),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            tar.append_data(&mut header, name, contents.as_bytes())
+                .map_err(write_error)?;
+        }
+        for crate_file in crate_files {
+            let name = Path::new(BUNDLE_CRATES).join(crate_file.file_name().unwrap());
+            tar.append_path_with_name(crate_file, name)
+                .map_err(write_error)?;
+        }
+        tar.into_inner()
+            .and_then(|gz| gz.finish())
+            .map_err(write_error)?;
+        Ok(())
+    }
+
+    /// Read a bundle from `path`. Any `.crate` files in the bundle are written
+    /// into `crates_dir`, or skipped if it is `None`.
+    ///
+    /// The `.crate` files haven't been verified, so `crates_dir` should be a
+    /// temporary directory, and only the files returned by
+    /// [`verify_bundle_crates`] should be moved into the cache.
+    pub fn read(path: &Path, crates_dir: Option<&Path>) -> Result<Self, BundleError> {
+        let read_error = |error| BundleError::Read {
+            path: path.to_owned(),
+            error,
+        };
+        let file = File::open(path).map_err(read_error)?;
+        let mut tar = Archive::new(GzDecoder::new(file));
+
+        let mut imports = None;
+        let mut crates_cache = None;
+        let mut diff_cache = None;
+        for entry in tar.entries().map_err(read_error)? {
+            let mut entry = entry.map_err(read_error)?;
+            let entry_path = entry.path().map_err(read_error)?.into_owned();
+            match entry_path.to_str() {
+                Some(BUNDLE_IMPORTS) => imports = Some(load_toml(BUNDLE_IMPORTS, entry)?.1),
+                Some(BUNDLE_CRATES_IO_CACHE) => crates_cache = Some(load_json(entry)?),
+                Some(BUNDLE_DIFF_CACHE) => {
+                    diff_cache = Some(load_toml(BUNDLE_DIFF_CACHE, entry)?.1)
+                }
+                _ => {
+                    // Only plain `.crate` files directly within `crates/` are
+                    // expected, so the bundle can't write anywhere else.
+                    let file_name = entry_path
+                        .strip_prefix(BUNDLE_CRATES)
+                        .ok()
+                        .filter(|name| {
+                            name.components().count() == 1
+                                && name.extension() == Some(OsStr::new("crate"))
+                        })
+                        .ok_or_else(|| BundleError::UnexpectedEntry {
+                            entry_path: entry_path.clone(),
+                        })?;
+                    if let Some(crates_dir) = crates_dir {
+                        entry
+                            .unpack(crates_dir.join(file_name))
+                            .map_err(read_error)?;
+                    }
+                }
+            }
+        }
+
+        Ok(Bundle {
+            imports: imports.ok_or(BundleError::MissingEntry {
+                name: BUNDLE_IMPORTS,
+            })?,
+            crates_cache: crates_cache.ok_or(BundleError::MissingEntry {
+                name: BUNDLE_CRATES_IO_CACHE,
+            })?,
+            diff_cache: diff_cache.ok_or(BundleError::MissingEntry {
+                name: BUNDLE_DIFF_CACHE,
+            })?,
+        })
+    }
+}
+
+/// Check the `.crate` files unpacked from a bundle into `dir` against the
+/// checksums in `Cargo.lock`, returning the paths of those which match.
+///
+/// Crates which aren't in `Cargo.lock` (such as older versions which are only
+/// needed for diffs) can't be verified, so are skipped with a warning.
+pub fn verify_bundle_crates(
+    metadata: &cargo_metadata::Metadata,
+    dir: &Path,
+) -> Result<Vec<PathBuf>, BundleError> {
+    let lock_checksums = cargo_lock_checksums(metadata);
+    let expected_checksums: FastMap<String, &String> = metadata
+        .packages
+        .iter()
+        .filter(|package| package.is_crates_io())
+        .filter_map(|package| {
+            let checksum = lock_checksums.get(&cargo_lock_key(package))?;
+            Some((
+                format!("{}-{}.crate", package.name, package.version),
+                checksum,
+            ))
+        })
+        .collect();
+
+    let read_error = |error| BundleError::Read {
+        path: dir.to_owned(),
+        error,
+    };
+    let mut verified = Vec::new();
+    let mut skipped = Vec::new();
+    for entry in fs::read_dir(dir).map_err(read_error)? {
+        let path = entry.map_err(read_error)?.path();
+        let file_name = path.file_name().unwrap().to_string_lossy().into_owned();
+        let Some(expected) = expected_checksums.get(&file_name) else {
+            skipped.push(file_name);
+            continue;
+        };
+        let contents = fs::read(&path).map_err(read_error)?;
+        let found = format!("{:x}", Sha256::digest(contents));
+        if found != **expected {
+            return Err(BundleError::CrateChecksum {
+                file_name,
+                expected: (*expected).clone(),
+                found,
+            });
+        }
+        verified.push(path);
+    }
+    if !skipped.is_empty() {
+        skipped.sort();
+        warn!(
+            "skipped crates in the bundle which aren't in Cargo.lock, as they can't be verified: {}",
+            skipped.join(", ")
+        );
+    }
+    verified.sort();
+    Ok(verified)
+}
+
 /// Unpack a non-crates.io package checkout in a format similar to what would be
 /// unpacked from a .crate file published on crates.io.
 ///
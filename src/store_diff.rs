@@ -0,0 +1,350 @@
+//! Semantic comparison of two versions of the supply-chain store, as printed
+//! by `cargo vet store-diff`.
+//!
+//! Rather than diffing the TOML files line-by-line, the entries of each table
+//! are compared, so that reordering or reformatting the files isn't reported
+//! as a change. Audits, wildcard audits and trusted publishers from the
+//! imports cached in imports.lock are compared as well, so that imported
+//! audits which newly come into effect show up in the diff.
+
+use std::io;
+use std::sync::Arc;
+
+use crate::format::{
+    AuditEntry, AuditKind, AuditsFile, CratesSourceId, CriteriaEntry, ExemptedDependency,
+    ImportName, JsonStoreChange, JsonStoreDiff, PackagePolicyEntry, Policy, PolicyEntry,
+    RemoteImport, SortedMap, SortedSet, TrustEntry, VetVersion, WildcardEntry,
+};
+use crate::out::Out;
+use crate::serialization::spanned::Spanned;
+use crate::storage::Store;
+
+/// Compare the `old` and `new` stores.
+pub fn diff_stores(old: &Store, new: &Store) -> JsonStoreDiff {
+    let mut diff = JsonStoreDiff::default();
+
+    diff_audits_file(&mut diff, None, &old.audits, &new.audits);
+    diff_maps(
+        &mut diff.exemptions,
+        None,
+        &old.config.exemptions,
+        &new.config.exemptions,
+    );
+
+    let old_policy = flatten_policy(&old.config.policy);
+    let new_policy = flatten_policy(&new.config.policy);
+    let keys: SortedSet<_> = old_policy.keys().chain(new_policy.keys()).collect();
+    for key @ (name, version) in keys {
+        let (old_entry, new_entry) = (old_policy.get(key), new_policy.get(key));
+        if old_entry != new_entry {
+            diff.policy.push(JsonStoreChange {
+                name: (*name).clone(),
+                version: version.clone(),
+                import: None,
+                old: old_entry.map(|entry| (*entry).clone()),
+                new: new_entry.map(|entry| (*entry).clone()),
+            });
+        }
+    }
+
+    diff_entries(
+        &mut diff.criteria,
+        &old.audits.criteria,
+        &new.audits.criteria,
+    );
+    diff_entries(&mut diff.imports, &old.config.imports, &new.config.imports);
+
+    let empty = AuditsFile::default();
+    let import_names: SortedSet<&ImportName> = old
+        .imports
+        .audits
+        .keys()
+        .chain(new.imports.audits.keys())
+        .collect();
+    for import_name in import_names {
+        diff_audits_file(
+            &mut diff,
+            Some(import_name),
+            old.imports.audits.get(import_name).unwrap_or(&empty),
+            new.imports.audits.get(import_name).unwrap_or(&empty),
+        );
+    }
+
+    diff
+}
+
+/// Whether there are no changes at all between the two stores.
+pub fn is_empty(diff: &JsonStoreDiff) -> bool {
+    diff.audits.is_empty()
+        && diff.wildcard_audits.is_empty()
+        && diff.exemptions.is_empty()
+        && diff.policy.is_empty()
+        && diff.criteria.is_empty()
+        && diff.imports.is_empty()
+        && diff.trusted.is_empty()
+}
+
+/// Compare the audits, wildcard audits and trusted publishers of a local or
+/// imported audits file.
+fn diff_audits_file(
+    diff: &mut JsonStoreDiff,
+    import: Option<&ImportName>,
+    old: &AuditsFile,
+    new: &AuditsFile,
+) {
+    diff_maps(&mut diff.audits, import, &old.audits, &new.audits);
+    diff_maps(
+        &mut diff.wildcard_audits,
+        import,
+        &old.wildcard_audits,
+        &new.wildcard_audits,
+    );
+    diff_maps(&mut diff.trusted, import, &old.trusted, &new.trusted);
+}
+
+/// Compare tables which map each package to a list of entries, recording the
+/// entries which are only present in one of the two versions.
+fn diff_maps<T: PartialEq + Clone>(
+    changes: &mut Vec<JsonStoreChange<T>>,
+    import: Option<&ImportName>,
+    old: &SortedMap<String, Vec<T>>,
+    new: &SortedMap<String, Vec<T>>,
+) {
+    let names: SortedSet<&String> = old.keys().chain(new.keys()).collect();
+    for name in names {
+        let old_entries = old.get(name).map_or(&[][..], Vec::as_slice);
+        let new_entries = new.get(name).map_or(&[][..], Vec::as_slice);
+        let change = |old: Option<&T>, new: Option<&T>| JsonStoreChange {
+            name: name.clone(),
+            version: None,
+            import: import.cloned(),
+            old: old.cloned(),
+            new: new.cloned(),
+        };
+        for entry in old_entries {
+            if !new_entries.contains(entry) {
+                changes.push(change(Some(entry), None));
+            }
+        }
+        for entry in new_entries {
+            if !old_entries.contains(entry) {
+                changes.push(change(None, Some(entry)));
+            }
+        }
+    }
+}
+
+/// Compare tables with a single entry per name, recording the entries which
+/// were added, removed or changed.
+fn diff_entries<T: PartialEq + Clone>(
+    changes: &mut Vec<JsonStoreChange<T>>,
+    old: &SortedMap<String, T>,
+    new: &SortedMap<String, T>,
+) {
+    let names: SortedSet<&String> = old.keys().chain(new.keys()).collect();
+    for name in names {
+        let (old_entry, new_entry) = (old.get(name), new.get(name));
+        if old_entry != new_entry {
+            changes.push(JsonStoreChange {
+                name: name.clone(),
+                version: None,
+                import: None,
+                old: old_entry.cloned(),
+                new: new_entry.cloned(),
+            });
+        }
+    }
+}
+
+/// Split versioned policies into an entry per version, so they can be
+/// compared individually.
+fn flatten_policy(policy: &Policy) -> SortedMap<(&String, Option<VetVersion>), &PolicyEntry> {
+    let mut entries = SortedMap::new();
+    for (name, package_policy) in &policy.package {
+        match package_policy {
+            PackagePolicyEntry::Unversioned(entry) => {
+                entries.insert((name, None), entry);
+            }
+            PackagePolicyEntry::Versioned { version } => {
+                for (version, entry) in version {
+                    entries.insert((name, Some(version.clone())), entry);
+                }
+            }
+        }
+    }
+    entries
+}
+
+/// Print a human-readable summary of the changes between two stores.
+pub fn print_human(out: &Arc<dyn Out>, diff: &JsonStoreDiff) -> Result<(), io::Error> {
+    if is_empty(diff) {
+        writeln!(out, "No changes to the supply-chain store");
+        return Ok(());
+    }
+
+    print_section(out, "audits", &diff.audits, describe_audit);
+    print_section(
+        out,
+        "wildcard audits",
+        &diff.wildcard_audits,
+        describe_wildcard_audit,
+    );
+    print_section(out, "exemptions", &diff.exemptions, describe_exemption);
+    print_section(out, "policy", &diff.policy, describe_policy);
+    print_section(out, "criteria", &diff.criteria, describe_criteria);
+    print_section(out, "imports", &diff.imports, describe_import);
+    print_section(out, "trusted publishers", &diff.trusted, describe_trusted);
+    Ok(())
+}
+
+fn print_section<T>(
+    out: &Arc<dyn Out>,
+    title: &str,
+    changes: &[JsonStoreChange<T>],
+    describe: impl Fn(&T) -> String,
+) {
+    if changes.is_empty() {
+        return;
+    }
+
+    writeln!(out, "{title}:");
+    for change in changes {
+        let mut name = change.name.clone();
+        if let Some(version) = &change.version {
+            name = format!("{name}:{version}");
+        }
+        if let Some(import) = &change.import {
+            name = format!("{name} (imported from {import})");
+        }
+        match (&change.old, &change.new) {
+            (None, Some(new)) => writeln!(out, "  + {name}: {}", describe(new)),
+            (Some(old), None) => writeln!(out, "  - {name}: {}", describe(old)),
+            (Some(old), Some(new)) => {
+                writeln!(out, "  ~ {name}");
+                writeln!(out, "      was: {}", describe(old));
+                writeln!(out, "      now: {}", describe(new));
+            }
+            (None, None) => unreachable!("changes always have an old or new entry"),
+        }
+    }
+    writeln!(out);
+}
+
+fn join(items: &[Spanned<String>]) -> String {
+    items
+        .iter()
+        .map(|item| item.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn describe_source(source: &CratesSourceId) -> String {
+    match source {
+        CratesSourceId::User { user_id } => format!("user {user_id}"),
+        CratesSourceId::TrustedPublisher { trusted_publisher } => trusted_publisher.clone(),
+    }
+}
+
+pub fn describe_audit(entry: &AuditEntry) -> String {
+    let mut description = match &entry.kind {
+        AuditKind::Full { version } => format!("{version} {}", join(&entry.criteria)),
+        AuditKind::Delta { from, to } => format!("{from} -> {to} {}", join(&entry.criteria)),
+        AuditKind::Violation { violation } => {
+            format!("violation of {} for {violation}", join(&entry.criteria))
+        }
+    };
+    if !entry.who.is_empty() {
+        description.push_str(&format!(" by {}", join(&entry.who)));
+    }
+    if let Some(expires) = entry.expires {
+        description.push_str(&format!(" (expires {expires})"));
+    }
+    description
+}
+
+pub fn describe_wildcard_audit(entry: &WildcardEntry) -> String {
+    let mut description = format!(
+        "{} for versions published by {} from {} to {}",
+        join(&entry.criteria),
+        describe_source(&entry.source),
+        entry.start,
+        entry.end
+    );
+    if !entry.who.is_empty() {
+        description.push_str(&format!(" by {}", join(&entry.who)));
+    }
+    description
+}
+
+pub fn describe_trusted(entry: &TrustEntry) -> String {
+    format!(
+        "{} for versions published by {} from {} to {}",
+        join(&entry.criteria),
+        describe_source(&entry.source),
+        entry.start,
+        entry.end
+    )
+}
+
+fn describe_exemption(entry: &ExemptedDependency) -> String {
+    let mut description = format!("{} {}", entry.version, join(&entry.criteria));
+    if !entry.suggest {
+        description.push_str(" (suggest = false)");
+    }
+    if let Some(expires) = entry.expires {
+        description.push_str(&format!(" (expires {expires})"));
+    }
+    description
+}
+
+fn describe_policy(entry: &PolicyEntry) -> String {
+    let mut parts = Vec::new();
+    if let Some(audit_as_crates_io) = entry.audit_as_crates_io {
+        parts.push(format!("audit-as-crates-io = {audit_as_crates_io}"));
+    }
+    for (key, criteria) in [
+        ("criteria", &entry.criteria),
+        ("dev-criteria", &entry.dev_criteria),
+        ("build-criteria", &entry.build_criteria),
+    ] {
+        if let Some(criteria) = criteria {
+            parts.push(format!("{key} = [{}]", join(criteria)));
+        }
+    }
+    for (dependency, criteria) in &entry.dependency_criteria {
+        parts.push(format!(
+            "dependency-criteria.{dependency} = [{}]",
+            join(criteria)
+        ));
+    }
+    if let Some(min_auditors) = entry.min_auditors {
+        parts.push(format!("min-auditors = {min_auditors}"));
+    }
+    if parts.is_empty() {
+        "default policy".to_owned()
+    } else {
+        parts.join(", ")
+    }
+}
+
+fn describe_criteria(entry: &CriteriaEntry) -> String {
+    let mut parts = Vec::new();
+    if let Some(description) = &entry.description {
+        parts.push(format!("{:?}", description.lines().next().unwrap_or("")));
+    }
+    if let Some(description_url) = &entry.description_url {
+        parts.push(description_url.clone());
+    }
+    if !entry.implies.is_empty() {
+        parts.push(format!("implies [{}]", join(&entry.implies)));
+    }
+    parts.join(", ")
+}
+
+fn describe_import(entry: &RemoteImport) -> String {
+    let mut description = entry.url.join(", ");
+    if !entry.exclude.is_empty() {
+        description.push_str(&format!(" (excluding {})", entry.exclude.join(", ")));
+    }
+    description
+}
@@ -0,0 +1,222 @@
+use super::*;
+
+fn advisory(id: &str, package: &str, patched: &[&str]) -> Advisory {
+    Advisory {
+        id: id.to_owned(),
+        package: package.to_owned(),
+        title: "Memory corruption in parser".to_owned(),
+        patched: patched
+            .iter()
+            .map(|req| VersionReq::parse(req).unwrap())
+            .collect(),
+        unaffected: vec![],
+    }
+}
+
+#[test]
+fn advisory_full_audit() {
+    // (Fail) The locked version is fully audited, but affected by an advisory.
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+
+    let metadata = mock.metadata();
+    let (config, audits, imports) = files_full_audited(&metadata);
+
+    let mut store = Store::mock(config, audits, imports);
+    store.add_advisories(
+        &metadata,
+        [advisory(
+            "RUSTSEC-2023-0001",
+            "third-party1",
+            &[">= 11.0.0"],
+        )],
+    );
+
+    assert_report_snapshot!("advisory-full-audit", metadata, store);
+}
+
+#[test]
+fn advisory_exemption() {
+    // (Fail) The locked version is exempted, but affected by an advisory.
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+
+    let metadata = mock.metadata();
+    let (config, audits, imports) = files_inited(&metadata);
+
+    let mut store = Store::mock(config, audits, imports);
+    store.add_advisories(
+        &metadata,
+        [advisory(
+            "RUSTSEC-2023-0001",
+            "third-party1",
+            &[">= 11.0.0"],
+        )],
+    );
+
+    assert_report_snapshot!("advisory-exemption", metadata, store);
+}
+
+#[test]
+fn advisory_unvetted() {
+    // (Fail) The locked version isn't vetted by anything, so the advisory is
+    // reported directly instead of asking for an audit.
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+
+    let metadata = mock.metadata();
+    let (config, audits, imports) = files_no_exemptions(&metadata);
+
+    let mut store = Store::mock(config, audits, imports);
+    store.add_advisories(
+        &metadata,
+        [advisory(
+            "RUSTSEC-2023-0001",
+            "third-party1",
+            &[">= 11.0.0"],
+        )],
+    );
+
+    assert_report_snapshot!("advisory-unvetted", metadata, store);
+}
+
+#[test]
+fn advisory_patched() {
+    // (Pass) The advisory only affects versions before the locked one, even
+    // though older audited versions were vulnerable.
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+
+    let metadata = mock.metadata();
+    let (config, mut audits, imports) = files_full_audited(&metadata);
+
+    audits.audits.insert(
+        "third-party1".to_owned(),
+        vec![
+            full_audit(ver(5), DEFAULT_CRIT),
+            delta_audit(ver(5), ver(DEFAULT_VER), DEFAULT_CRIT),
+        ],
+    );
+
+    let mut store = Store::mock(config, audits, imports);
+    store.add_advisories(
+        &metadata,
+        [advisory(
+            "RUSTSEC-2023-0001",
+            "third-party1",
+            &[&format!(">= {DEFAULT_VER}.0.0")],
+        )],
+    );
+
+    assert_report_snapshot!("advisory-patched", metadata, store);
+}
+
+#[test]
+fn advisory_ignored() {
+    // (Pass) The advisory is listed in `ignore`.
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+
+    let metadata = mock.metadata();
+    let (mut config, audits, imports) = files_full_audited(&metadata);
+
+    config.advisory_db = Some(AdvisoryDbConfig {
+        path: "../advisory-db".to_owned(),
+        ignore: vec!["RUSTSEC-2023-0001".to_owned()],
+    });
+
+    let mut store = Store::mock(config, audits, imports);
+    store.add_advisories(
+        &metadata,
+        [advisory(
+            "RUSTSEC-2023-0001",
+            "third-party1",
+            &[">= 11.0.0"],
+        )],
+    );
+
+    assert_report_snapshot!("advisory-ignored", metadata, store);
+}
+
+#[test]
+fn advisory_db_parsing() {
+    let dir = tempfile::tempdir().unwrap();
+    let crates = dir.path().join("crates");
+
+    fs::create_dir_all(crates.join("third-party1")).unwrap();
+    fs::write(
+        crates.join("third-party1").join("RUSTSEC-2023-0001.md"),
+        r#"```toml
+[advisory]
+id = "RUSTSEC-2023-0001"
+package = "third-party1"
+date = "2023-01-01"
+categories = ["memory-corruption"]
+
+[versions]
+patched = [">= 1.2.3, < 2.0.0", ">= 2.0.1"]
+unaffected = ["< 1.0.0"]
+```
+
+# Memory corruption in parser
+
+Details about the vulnerability.
+"#,
+    )
+    .unwrap();
+    fs::write(
+        crates.join("third-party1").join("RUSTSEC-2023-0002.md"),
+        r#"```toml
+[advisory]
+id = "RUSTSEC-2023-0002"
+package = "third-party1"
+date = "2023-01-01"
+informational = "unmaintained"
+
+[versions]
+patched = []
+```
+
+# third-party1 is unmaintained
+"#,
+    )
+    .unwrap();
+
+    fs::create_dir_all(crates.join("third-party2")).unwrap();
+    fs::write(
+        crates.join("third-party2").join("RUSTSEC-2018-0001.toml"),
+        r#"[advisory]
+id = "RUSTSEC-2018-0001"
+package = "third-party2"
+title = "Use-after-free in iterator"
+date = "2018-01-01"
+
+[versions]
+patched = ["^0.4.1"]
+"#,
+    )
+    .unwrap();
+
+    let advisories = crate::storage::load_advisory_db(dir.path()).unwrap();
+    let summary = advisories
+        .iter()
+        .map(|advisory| {
+            let affected = ["0.9.0", "1.0.0", "1.2.3", "2.0.0", "2.0.1"]
+                .into_iter()
+                .filter(|v| advisory.affects(&v.parse().unwrap()))
+                .collect::<Vec<_>>();
+            format!(
+                "{} {} {:?} affected={affected:?}",
+                advisory.id, advisory.package, advisory.title
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    insta::assert_snapshot!(summary);
+}
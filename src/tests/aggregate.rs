@@ -3,7 +3,7 @@ use insta::assert_snapshot;
 use super::*;
 
 fn mock_aggregate(sources: Vec<(String, AuditsFile)>) -> String {
-    match crate::do_aggregate_audits(sources) {
+    match crate::storage::do_aggregate_audits(sources) {
         Ok(merged) => crate::serialization::to_formatted_toml(merged, None)
             .unwrap()
             .to_string(),
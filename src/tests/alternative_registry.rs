@@ -0,0 +1,196 @@
+use super::*;
+
+const INTERNAL: &str = "internal";
+const INTERNAL_INDEX: &str = "sparse+https://cargo.internal.example.com/index/";
+
+fn internal_registry() -> CargoRegistryEntry {
+    CargoRegistryEntry {
+        index: INTERNAL_INDEX.to_owned(),
+        download: "https://cargo.internal.example.com/api/v1/crates".to_owned(),
+    }
+}
+
+fn internal_metadata() -> Metadata {
+    MockMetadata::new(vec![
+        MockPackage {
+            name: "root",
+            is_workspace: true,
+            is_first_party: true,
+            deps: vec![dep("internal-crate"), dep("third-party")],
+            ..Default::default()
+        },
+        MockPackage {
+            name: "internal-crate",
+            registry: Some(INTERNAL_INDEX),
+            ..Default::default()
+        },
+        MockPackage {
+            name: "third-party",
+            ..Default::default()
+        },
+    ])
+    .metadata()
+}
+
+fn internal_files(
+    metadata: &Metadata,
+    internal_audit_registry: Option<&str>,
+) -> (ConfigFile, AuditsFile, ImportsFile) {
+    let (mut config, mut audits, imports) = builtin_files_no_exemptions(metadata);
+
+    config
+        .registries
+        .insert(INTERNAL.to_owned(), internal_registry());
+    audits.audits.insert(
+        "third-party".to_owned(),
+        vec![full_audit(ver(DEFAULT_VER), SAFE_TO_DEPLOY)],
+    );
+    audits.audits.insert(
+        "internal-crate".to_owned(),
+        vec![AuditEntry {
+            registry: internal_audit_registry.map(str::to_owned),
+            ..full_audit(ver(DEFAULT_VER), SAFE_TO_DEPLOY)
+        }],
+    );
+
+    (config, audits, imports)
+}
+
+#[test]
+fn alternative_registry_audited() {
+    // (Pass) Crates from a declared registry are third-party, and are vetted by
+    // audits tagged with that registry.
+    let _enter = TEST_RUNTIME.enter();
+
+    let metadata = internal_metadata();
+    let (config, audits, imports) = internal_files(&metadata, Some(INTERNAL));
+
+    let store = Store::mock(config, audits, imports);
+    assert_report_snapshot!("alternative-registry-audited", metadata, store);
+}
+
+#[test]
+fn alternative_registry_untagged_audit() {
+    // (Fail) Audits which aren't tagged with the registry are for the crates.io
+    // crate of the same name, so don't apply.
+    let _enter = TEST_RUNTIME.enter();
+
+    let metadata = internal_metadata();
+    let (config, audits, imports) = internal_files(&metadata, None);
+
+    let store = Store::mock(config, audits, imports);
+    assert_report_snapshot!("alternative-registry-untagged-audit", metadata, store);
+}
+
+#[test]
+fn alternative_registry_undeclared() {
+    // (Pass) Crates from registries which aren't declared are still treated as
+    // first-party, and don't need audits.
+    let _enter = TEST_RUNTIME.enter();
+
+    let metadata = internal_metadata();
+    let (mut config, audits, imports) = internal_files(&metadata, None);
+    config.registries.clear();
+
+    let store = Store::mock(config, audits, imports);
+    assert_report_snapshot!("alternative-registry-undeclared", metadata, store);
+}
+
+#[test]
+fn alternative_registry_published_versions() {
+    // Published versions are looked up in the registry's sparse index.
+    let _enter = TEST_RUNTIME.enter();
+
+    let metadata = internal_metadata();
+    let cfg = mock_cfg(&metadata);
+    let cache = crate::storage::Cache::acquire(&cfg).unwrap();
+
+    let mut network = Network::new_mock();
+    network.mock_serve(
+        "https://cargo.internal.example.com/index/in/te/internal-crate",
+        ["1.0.0", "2.0.0"]
+            .iter()
+            .map(|version| {
+                serde_json::to_string(&json!({
+                    "name": "internal-crate",
+                    "vers": version,
+                    "deps": [],
+                    "cksum": "90527ab4abff2f0608cdb1a78e2349180e1d92059f59b5a65ce2a1a15a499b73",
+                    "features": {},
+                    "yanked": false
+                }))
+                .unwrap()
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    );
+
+    let entry = internal_registry();
+    let registry = CargoRegistry {
+        name: INTERNAL,
+        entry: &entry,
+    };
+    let versions = tokio::runtime::Handle::current()
+        .block_on(cache.published_versions(&network, Some(registry), "internal-crate"))
+        .unwrap();
+    assert_eq!(
+        versions.keys().map(|v| v.to_string()).collect::<Vec<_>>(),
+        ["1.0.0", "2.0.0"]
+    );
+
+    // The crate isn't on crates.io.
+    assert!(tokio::runtime::Handle::current()
+        .block_on(cache.published_versions(&network, None, "internal-crate"))
+        .is_err());
+}
+
+#[test]
+fn crates_io_mirror() {
+    // When crates.io is mirrored, its index, API and downloads are all located
+    // using the mirror's config.json.
+    let _enter = TEST_RUNTIME.enter();
+
+    let metadata = internal_metadata();
+    let cfg = mock_cfg(&metadata);
+    let cache = crate::storage::Cache::acquire(&cfg).unwrap();
+
+    let mut network = Network::new_mock();
+    network.mock_crates_io_mirror("sparse+https://mirror.example.com/crates-io");
+    network.mock_serve_json(
+        "https://mirror.example.com/crates-io/config.json",
+        &json!({
+            "dl": "https://mirror.example.com/dl/{crate}/{version}",
+            "api": "https://mirror.example.com/api",
+        }),
+    );
+    network.mock_serve(
+        "https://mirror.example.com/crates-io/3/s/sha",
+        serde_json::to_string(&json!({
+            "name": "sha",
+            "vers": "1.0.0",
+            "deps": [],
+            "cksum": "90527ab4abff2f0608cdb1a78e2349180e1d92059f59b5a65ce2a1a15a499b73",
+            "features": {},
+            "yanked": false
+        }))
+        .unwrap(),
+    );
+
+    let urls = tokio::runtime::Handle::current()
+        .block_on(network.crates_io())
+        .unwrap()
+        .clone();
+    assert_eq!(urls.api.as_str(), "https://mirror.example.com/api/");
+    assert_eq!(
+        urls.download,
+        "https://mirror.example.com/dl/{crate}/{version}"
+    );
+
+    let versions = tokio::runtime::Handle::current()
+        .block_on(cache.published_versions(&network, None, "sha"))
+        .unwrap();
+    assert_eq!(
+        versions.keys().map(|v| v.to_string()).collect::<Vec<_>>(),
+        ["1.0.0"]
+    );
+}
@@ -46,12 +46,9 @@ fn get_audit_as_crates_io(cfg: &Config, store: &Store, add_packages_to_index: bo
             &[]
         },
     );
-    let res = tokio::runtime::Handle::current().block_on(crate::check_audit_as_crates_io(
-        cfg,
-        store,
-        Some(&network),
-        &mut cache,
-    ));
+    let res = tokio::runtime::Handle::current().block_on(
+        crate::commands::check_audit_as_crates_io(cfg, store, Some(&network), &mut cache),
+    );
     match res {
         Ok(()) => String::new(),
         Err(e) => format!("{:?}", miette::Report::new(e)),
@@ -60,9 +57,9 @@ fn get_audit_as_crates_io(cfg: &Config, store: &Store, add_packages_to_index: bo
 
 fn get_audit_as_crates_io_no_network(cfg: &Config, store: &Store) -> String {
     let mut cache = crate::storage::Cache::acquire(cfg).unwrap();
-    let res = tokio::runtime::Handle::current().block_on(crate::check_audit_as_crates_io(
-        cfg, store, None, &mut cache,
-    ));
+    let res = tokio::runtime::Handle::current().block_on(
+        crate::commands::check_audit_as_crates_io(cfg, store, None, &mut cache),
+    );
     match res {
         Ok(()) => String::new(),
         Err(e) => format!("{:?}", miette::Report::new(e)),
@@ -73,12 +70,9 @@ fn get_audit_as_crates_io_json(cfg: &Config, store: &Store) -> String {
     let mut cache = crate::storage::Cache::acquire(cfg).unwrap();
     let mut network = crate::network::Network::new_mock();
     build_registry(&mut network, &[]);
-    let res = tokio::runtime::Handle::current().block_on(crate::check_audit_as_crates_io(
-        cfg,
-        store,
-        Some(&network),
-        &mut cache,
-    ));
+    let res = tokio::runtime::Handle::current().block_on(
+        crate::commands::check_audit_as_crates_io(cfg, store, Some(&network), &mut cache),
+    );
     match res {
         Ok(()) => String::new(),
         Err(e) => {
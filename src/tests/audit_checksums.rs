@@ -0,0 +1,100 @@
+use crate::errors::AuditChecksumErrors;
+
+use super::*;
+
+const LOCKED_CHECKSUM: &str = "90527ab4abff2f0608cdb1a78e2349180e1d92059f59b5a65ce2a1a15a499b73";
+const OTHER_CHECKSUM: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Run `check_audit_checksums` with a `Cargo.lock` recording `LOCKED_CHECKSUM`
+/// for every third-party crate in `MockMetadata::simple()`.
+fn check_audit_checksums(
+    alter_store: impl FnOnce(&mut AuditsFile, &mut ImportsFile),
+) -> Result<(), AuditChecksumErrors> {
+    let mock = MockMetadata::simple();
+    let mut metadata = mock.metadata();
+    let dir = tempfile::tempdir().unwrap();
+    mock_lockfile(&mut metadata, dir.path(), |_| {
+        Some(LOCKED_CHECKSUM.to_owned())
+    });
+
+    let (config, mut audits, mut imports) = builtin_files_full_audited(&metadata);
+    alter_store(&mut audits, &mut imports);
+    let store = Store::mock(config, audits, imports);
+    let cfg = mock_cfg(&metadata);
+
+    crate::commands::check_audit_checksums(&cfg, &store)
+}
+
+fn with_checksum(mut audit: AuditEntry, checksum: &str) -> AuditEntry {
+    audit.checksum = Some(checksum.to_owned());
+    audit
+}
+
+#[test]
+fn audit_checksums_match() {
+    // Audits with matching checksums, and audits without checksums, are fine.
+
+    check_audit_checksums(|audits, _| {
+        audits.audits.insert(
+            "third-party1".to_owned(),
+            vec![with_checksum(
+                full_audit(ver(DEFAULT_VER), SAFE_TO_DEPLOY),
+                LOCKED_CHECKSUM,
+            )],
+        );
+    })
+    .expect("audit checksums should match");
+}
+
+#[test]
+fn audit_checksums_other_version() {
+    // Checksums for versions which aren't in Cargo.lock aren't checked.
+
+    check_audit_checksums(|audits, _| {
+        audits
+            .audits
+            .get_mut("third-party1")
+            .unwrap()
+            .push(with_checksum(
+                delta_audit(ver(DEFAULT_VER), ver(11), SAFE_TO_DEPLOY),
+                OTHER_CHECKSUM,
+            ));
+    })
+    .expect("audit checksums should match");
+}
+
+#[test]
+fn audit_checksums_mismatch() {
+    // (Fail) Both local and imported audits must match Cargo.lock.
+    let _enter = TEST_RUNTIME.enter();
+
+    let errors = check_audit_checksums(|audits, imports| {
+        audits.audits.insert(
+            "third-party1".to_owned(),
+            vec![with_checksum(
+                delta_audit(ver(5), ver(DEFAULT_VER), SAFE_TO_DEPLOY),
+                OTHER_CHECKSUM,
+            )],
+        );
+        imports.audits.insert(
+            FOREIGN.to_owned(),
+            AuditsFile {
+                criteria: SortedMap::new(),
+                wildcard_audits: SortedMap::new(),
+                audits: [(
+                    "third-party2".to_owned(),
+                    vec![with_checksum(
+                        full_audit(ver(DEFAULT_VER), SAFE_TO_DEPLOY),
+                        OTHER_CHECKSUM,
+                    )],
+                )]
+                .into_iter()
+                .collect(),
+                trusted: SortedMap::new(),
+            },
+        );
+    })
+    .expect_err("audit checksums shouldn't match");
+
+    insta::assert_snapshot!(format!("{:?}", miette::Report::new(errors)));
+}
@@ -0,0 +1,228 @@
+use super::*;
+
+/// Resolve `store` and `base_store` against the same metadata, and get the
+/// human and JSON reports for failures which are new relative to the base.
+fn get_base_reports(metadata: &Metadata, store: &Store, base_store: &Store) -> (String, String) {
+    let mut report = crate::resolver::resolve(metadata, None, store, mock_today());
+    let base_report = crate::resolver::resolve(metadata, None, base_store, mock_today());
+    report.retain_new_failures(&base_report);
+    get_reports(metadata, report, store, None)
+}
+
+#[test]
+fn base_new_failure() {
+    // A crate which was already unvetted in the base revision is reported as
+    // pre-existing, while a newly unvetted crate still fails.
+    let _enter = TEST_RUNTIME.enter();
+
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+
+    let (config, mut audits, imports) = builtin_files_full_audited(&metadata);
+    audits.audits.remove("third-party2");
+    let base_store = Store::mock(config.clone(), audits.clone(), imports.clone());
+
+    audits.audits.remove("transitive-third-party1");
+    let store = Store::mock(config, audits, imports);
+
+    let (human, json) = get_base_reports(&metadata, &store, &base_store);
+    insta::assert_snapshot!("base-new-failure", human);
+    insta::assert_snapshot!("base-new-failure.json", json);
+}
+
+#[test]
+fn base_only_preexisting() {
+    // When every failure was already present in the base revision, the check
+    // succeeds and the failures are listed separately.
+    let _enter = TEST_RUNTIME.enter();
+
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+
+    let (config, mut audits, imports) = builtin_files_full_audited(&metadata);
+    audits.audits.remove("third-party2");
+    let store = Store::mock(config, audits, imports);
+
+    let mut report = crate::resolver::resolve(&metadata, None, &store, mock_today());
+    let base_report = crate::resolver::resolve(&metadata, None, &store, mock_today());
+    report.retain_new_failures(&base_report);
+    assert!(!report.has_errors());
+
+    let (human, json) = get_base_reports(&metadata, &store, &store);
+    insta::assert_snapshot!("base-only-preexisting", human);
+    insta::assert_snapshot!("base-only-preexisting.json", json);
+}
+
+#[test]
+fn base_new_criteria() {
+    // A crate which was unvetted in the base revision, but now also requires
+    // a stronger criteria, only fails for the new criteria.
+    let _enter = TEST_RUNTIME.enter();
+
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+
+    let (mut config, mut audits, imports) = builtin_files_full_audited(&metadata);
+    audits.audits.remove("third-party1");
+    config
+        .policy
+        .insert("first-party".to_owned(), self_policy([SAFE_TO_RUN]));
+    let base_store = Store::mock(config.clone(), audits.clone(), imports.clone());
+
+    config
+        .policy
+        .insert("first-party".to_owned(), self_policy([SAFE_TO_DEPLOY]));
+    let store = Store::mock(config, audits, imports);
+
+    let (human, json) = get_base_reports(&metadata, &store, &base_store);
+    insta::assert_snapshot!("base-new-criteria", human);
+    insta::assert_snapshot!("base-new-criteria.json", json);
+}
+
+#[test]
+fn base_violation_conflict() {
+    // If the base revision had violation conflicts, its failures aren't known,
+    // so every failure is still reported.
+    let _enter = TEST_RUNTIME.enter();
+
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+
+    let (config, mut audits, imports) = builtin_files_full_audited(&metadata);
+    audits.audits.remove("third-party2");
+    let store = Store::mock(config.clone(), audits.clone(), imports.clone());
+
+    audits
+        .audits
+        .get_mut("third-party1")
+        .unwrap()
+        .push(violation("*".parse().unwrap(), SAFE_TO_DEPLOY));
+    let base_store = Store::mock(config, audits, imports);
+
+    let mut report = crate::resolver::resolve(&metadata, None, &store, mock_today());
+    let base_report = crate::resolver::resolve(&metadata, None, &base_store, mock_today());
+    report.retain_new_failures(&base_report);
+    let crate::resolver::Conclusion::FailForVet(fail) = &report.conclusion else {
+        panic!("expected vetting to fail");
+    };
+    assert_eq!(fail.failures.len(), 1);
+    assert!(fail.preexisting.is_empty());
+}
+
+/// Create a git repository in `dir` containing `files`, and commit them.
+fn git_repo(dir: &std::path::Path, files: &[(&str, &str)]) {
+    for (path, contents) in files {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+    git(dir, &["init", "--quiet"]);
+    git(dir, &["add", "."]);
+    git(dir, &["commit", "--quiet", "-m", "initial"]);
+}
+
+fn git(dir: &std::path::Path, args: &[&str]) {
+    let output = std::process::Command::new("git")
+        .current_dir(dir)
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .args(["-c", "protocol.file.allow=always"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "git {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn base_revision_with_submodule() {
+    // The base revision is extracted from git, including the contents of its
+    // submodules, and loaded as it was committed.
+    let _enter = TEST_RUNTIME.enter();
+
+    let dep = tempfile::tempdir().unwrap();
+    git_repo(
+        dep.path(),
+        &[
+            (
+                "Cargo.toml",
+                "[package]\nname = \"dep\"\nversion = \"1.0.0\"\nedition = \"2021\"\n",
+            ),
+            ("src/lib.rs", ""),
+        ],
+    );
+
+    let mock = MockMetadata::simple();
+    let mut metadata = mock.metadata();
+    let (config, mut audits, imports) = builtin_files_no_exemptions(&metadata);
+    audits
+        .criteria
+        .insert("base-criteria".to_owned(), criteria("Only in the base"));
+    fn to_toml<T: serde::Serialize>(value: &T) -> String {
+        crate::serialization::to_formatted_toml(value, None)
+            .unwrap()
+            .to_string()
+    }
+    let repo = tempfile::tempdir().unwrap();
+    git_repo(
+        repo.path(),
+        &[
+            (
+                "Cargo.toml",
+                "[package]\nname = \"root\"\nversion = \"1.0.0\"\nedition = \"2021\"\n\n[dependencies]\ndep = { path = \"dep\" }\n",
+            ),
+            ("src/lib.rs", ""),
+            ("supply-chain/config.toml", &to_toml(&config)),
+            ("supply-chain/audits.toml", &to_toml(&audits)),
+            ("supply-chain/imports.lock", &to_toml(&imports)),
+        ],
+    );
+    git(
+        repo.path(),
+        &[
+            "submodule",
+            "add",
+            "--quiet",
+            dep.path().to_str().unwrap(),
+            "dep",
+        ],
+    );
+    let cargo = std::env::var_os("CARGO").unwrap();
+    let status = std::process::Command::new(cargo)
+        .args(["generate-lockfile", "--offline", "--manifest-path"])
+        .arg(repo.path().join("Cargo.toml"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+    git(repo.path(), &["add", "."]);
+    git(repo.path(), &["commit", "--quiet", "-m", "base"]);
+
+    // Change the working tree, which shouldn't affect the base revision.
+    fs::write(
+        repo.path().join("supply-chain/audits.toml"),
+        to_toml(&AuditsFile {
+            criteria: SortedMap::new(),
+            ..audits
+        }),
+    )
+    .unwrap();
+
+    metadata.workspace_root = repo.path().to_str().unwrap().into();
+    let mut cfg = mock_cfg(&metadata);
+    cfg.metacfg = MetaConfig(vec![crate::format::MetaConfigInstance {
+        version: Some(1),
+        store: Some(crate::format::StoreInfo {
+            path: Some(repo.path().join("supply-chain")),
+        }),
+    }]);
+
+    let (_checkout, base_metadata, base_store) =
+        crate::commands::load_base_revision(&cfg, "HEAD").unwrap();
+    assert!(base_metadata
+        .packages
+        .iter()
+        .any(|package| package.name.as_str() == "dep"));
+    assert!(base_store.audits.criteria.contains_key("base-criteria"));
+}
@@ -0,0 +1,174 @@
+use sha2::{Digest, Sha256};
+
+use super::*;
+use crate::errors::BundleError;
+use crate::format::{
+    CratesCache, CratesCacheEntry, CratesCacheVersionDetails, Delta, DiffCache, DiffStat,
+};
+use crate::storage::{verify_bundle_crates, Bundle, Cache};
+
+fn crates_cache_entry(versions: &[u64]) -> Arc<CratesCacheEntry> {
+    Arc::new(CratesCacheEntry {
+        versions: versions
+            .iter()
+            .map(|&v| {
+                (
+                    ver(v).semver,
+                    CratesCacheVersionDetails {
+                        created_at: Default::default(),
+                        source: None,
+                    },
+                )
+            })
+            .collect(),
+        metadata: CratesAPICrateMetadata {
+            description: None,
+            repository: None,
+        },
+    })
+}
+
+fn diff_cache(delta: Delta) -> DiffCache {
+    let mut diff_cache = DiffCache::default();
+    diff_cache
+        .diffs
+        .entry("third-party1".to_owned())
+        .or_default()
+        .insert(
+            delta,
+            DiffStat {
+                insertions: 10,
+                deletions: 2,
+                files_changed: 1,
+            },
+        );
+    diff_cache
+}
+
+#[test]
+fn bundle_round_trip() {
+    // Everything written into a bundle can be read back out, with crates
+    // extracted into the given directory.
+
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+    let (_, _, imports) = builtin_files_full_audited(&metadata);
+
+    let mut crates_cache = CratesCache::default();
+    crates_cache.crates.insert(
+        "third-party1".to_owned(),
+        crates_cache_entry(&[DEFAULT_VER]),
+    );
+    let delta = Delta {
+        from: Some(ver(5)),
+        to: ver(DEFAULT_VER),
+    };
+
+    let dir = tempfile::tempdir().unwrap();
+    let crate_file = dir.path().join("third-party1-10.0.0.crate");
+    std::fs::write(&crate_file, "not really a crate").unwrap();
+    let bundle_path = dir.path().join("bundle.tar.gz");
+    Bundle {
+        imports: imports.clone(),
+        crates_cache,
+        diff_cache: diff_cache(delta.clone()),
+    }
+    .write(&bundle_path, &[crate_file])
+    .unwrap();
+
+    let crates_dir = tempfile::tempdir().unwrap();
+    let bundle = Bundle::read(&bundle_path, Some(crates_dir.path())).unwrap();
+    assert_eq!(bundle.imports, imports);
+    assert!(bundle.crates_cache.crates.contains_key("third-party1"));
+    assert!(bundle.diff_cache.diffs["third-party1"].contains_key(&delta));
+    assert_eq!(
+        std::fs::read_to_string(crates_dir.path().join("third-party1-10.0.0.crate")).unwrap(),
+        "not really a crate"
+    );
+}
+
+#[test]
+fn bundle_unexpected_entry() {
+    // (Fail) Bundles may only contain the expected files, and crates.
+
+    let dir = tempfile::tempdir().unwrap();
+    let bundle_path = dir.path().join("bundle.tar.gz");
+    let file = std::fs::File::create(&bundle_path).unwrap();
+    let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(
+        file,
+        flate2::Compression::default(),
+    ));
+    let mut header = tar::Header::new_gnu();
+    header.set_size(0);
+    header.set_cksum();
+    tar.append_data(&mut header, "crates/nested/evil.crate", &[][..])
+        .unwrap();
+    tar.into_inner().unwrap().finish().unwrap();
+
+    assert!(matches!(
+        Bundle::read(&bundle_path, None),
+        Err(BundleError::UnexpectedEntry { .. })
+    ));
+}
+
+#[test]
+fn bundle_merge_caches() {
+    // Merging a bundle into the cache adds its diffstats, and only replaces
+    // crates.io metadata which knows about fewer versions.
+
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+    let cfg = mock_cfg(&metadata);
+    let cache = Cache::acquire(&cfg).unwrap();
+
+    let mut newer = CratesCache::default();
+    newer.crates.insert(
+        "third-party1".to_owned(),
+        crates_cache_entry(&[5, DEFAULT_VER]),
+    );
+    let delta = Delta {
+        from: None,
+        to: ver(DEFAULT_VER),
+    };
+    cache.merge_bundle_caches(newer, diff_cache(delta.clone()));
+
+    let mut older = CratesCache::default();
+    older
+        .crates
+        .insert("third-party1".to_owned(), crates_cache_entry(&[5]));
+    cache.merge_bundle_caches(older, DiffCache::default());
+
+    let (crates_cache, diff_cache) = cache.bundle_caches();
+    assert_eq!(crates_cache.crates["third-party1"].versions.len(), 2);
+    assert!(diff_cache.diffs["third-party1"].contains_key(&delta));
+}
+
+#[test]
+fn bundle_crates_verified() {
+    // (Fail) Crates unpacked from a bundle must match the checksums in
+    // Cargo.lock, and crates which aren't in Cargo.lock are skipped.
+
+    let dir = tempfile::tempdir().unwrap();
+    let mock = MockMetadata::simple();
+    let mut metadata = mock.metadata();
+    let contents = "not really a crate";
+    mock_lockfile(&mut metadata, dir.path(), |package| {
+        (package.name.as_str() == "third-party1").then(|| format!("{:x}", Sha256::digest(contents)))
+    });
+
+    let crates_dir = dir.path().join("crates");
+    std::fs::create_dir(&crates_dir).unwrap();
+    let crate_file = crates_dir.join("third-party1-10.0.0.crate");
+    std::fs::write(&crate_file, contents).unwrap();
+    std::fs::write(crates_dir.join("third-party1-5.0.0.crate"), contents).unwrap();
+    assert_eq!(
+        verify_bundle_crates(&metadata, &crates_dir).unwrap(),
+        vec![crate_file.clone()]
+    );
+
+    std::fs::write(&crate_file, "tampered").unwrap();
+    assert!(matches!(
+        verify_bundle_crates(&metadata, &crates_dir),
+        Err(BundleError::CrateChecksum { file_name, .. }) if file_name == "third-party1-10.0.0.crate"
+    ));
+}
@@ -38,7 +38,7 @@ fn mock_simple_suggested_criteria() {
     );
 
     let store = Store::mock(config, audits, imports);
-    let report = crate::resolver::resolve(&metadata, None, &store);
+    let report = crate::resolver::resolve(&metadata, None, &store, mock_today());
 
     let mut output = String::new();
     for (from, to, descr) in [
@@ -131,7 +131,7 @@ fn mock_simple_certify_flow() {
         unreachable!();
     };
 
-    crate::do_cmd_certify(
+    crate::commands::do_cmd_certify(
         &output.clone().as_dyn(),
         &cfg,
         sub_args,
@@ -193,7 +193,7 @@ fn mock_delta_certify_flow() {
         unreachable!();
     };
 
-    crate::do_cmd_certify(
+    crate::commands::do_cmd_certify(
         &output.clone().as_dyn(),
         &cfg,
         sub_args,
@@ -259,7 +259,7 @@ fn mock_delta_git_certify_flow() {
         unreachable!();
     };
 
-    crate::do_cmd_certify(
+    crate::commands::do_cmd_certify(
         &output.clone().as_dyn(),
         &cfg,
         sub_args,
@@ -348,7 +348,7 @@ fn mock_prune_non_importable_audit() {
         unreachable!();
     };
 
-    crate::do_cmd_certify(
+    crate::commands::do_cmd_certify(
         &output.clone().as_dyn(),
         &cfg,
         sub_args,
@@ -430,7 +430,7 @@ fn mock_collapse_non_importable_audits() {
         unreachable!();
     };
 
-    crate::do_cmd_certify(
+    crate::commands::do_cmd_certify(
         &output.clone().as_dyn(),
         &cfg,
         sub_args,
@@ -504,7 +504,7 @@ fn mock_collapse_with_full_audit() {
         unreachable!();
     };
 
-    crate::do_cmd_certify(
+    crate::commands::do_cmd_certify(
         &output.clone().as_dyn(),
         &cfg,
         sub_args,
@@ -586,7 +586,7 @@ fn mock_dont_collapse_incompatible_criteria_non_importable_audits() {
         unreachable!();
     };
 
-    crate::do_cmd_certify(
+    crate::commands::do_cmd_certify(
         &output.clone().as_dyn(),
         &cfg,
         sub_args,
@@ -664,7 +664,7 @@ fn mock_wildcard_certify_flow() {
     let mut store = Store::mock_online(&cfg, config, audits, imports, &network, true)
         .expect("store acquisition failed");
 
-    crate::do_cmd_certify(
+    crate::commands::do_cmd_certify(
         &output.clone().as_dyn(),
         &cfg,
         sub_args,
@@ -719,7 +719,7 @@ fn mock_trust_flow_simple() {
     let mut store = Store::mock_online(&cfg, config, audits, imports, &network, true)
         .expect("store acquisition failed");
 
-    crate::do_cmd_trust(
+    crate::commands::do_cmd_trust(
         &output.clone().as_dyn(),
         &cfg,
         sub_args,
@@ -774,8 +774,9 @@ fn mock_trust_flow_ambiguous() {
     let mut store = Store::mock_online(&cfg, config, audits, imports, &network, true)
         .expect("store acquisition failed");
 
-    let error = crate::do_cmd_trust(&output.as_dyn(), &cfg, sub_args, &mut store, Some(&network))
-        .expect_err("do_cmd_trust succeeded");
+    let error =
+        crate::commands::do_cmd_trust(&output.as_dyn(), &cfg, sub_args, &mut store, Some(&network))
+            .expect_err("do_cmd_trust succeeded");
 
     insta::assert_snapshot!(format!("{error:?}"));
 }
@@ -818,7 +819,7 @@ fn mock_trust_flow_explicit() {
     let mut store = Store::mock_online(&cfg, config, audits, imports, &network, true)
         .expect("store acquisition failed");
 
-    crate::do_cmd_trust(
+    crate::commands::do_cmd_trust(
         &output.clone().as_dyn(),
         &cfg,
         sub_args,
@@ -889,7 +890,7 @@ fn mock_trust_flow_all() {
     let mut store = Store::mock_online(&cfg, config, audits, imports, &network, true)
         .expect("store acquisition failed");
 
-    crate::do_cmd_trust(
+    crate::commands::do_cmd_trust(
         &output.clone().as_dyn(),
         &cfg,
         sub_args,
@@ -970,7 +971,7 @@ fn mock_trust_flow_all_allow_multiple() {
     let mut store = Store::mock_online(&cfg, config, audits, imports, &network, true)
         .expect("store acquisition failed");
 
-    crate::do_cmd_trust(
+    crate::commands::do_cmd_trust(
         &output.clone().as_dyn(),
         &cfg,
         sub_args,
@@ -989,3 +990,129 @@ fn mock_trust_flow_all_allow_multiple() {
 
     insta::assert_snapshot!(result);
 }
+
+#[test]
+fn mock_certify_records_checksum() {
+    // Certifying a version records the checksum of its `.crate` file from the
+    // registry index, so that it can be checked against Cargo.lock.
+    let mock = MockMetadata::simple();
+
+    let _enter = TEST_RUNTIME.enter();
+    let metadata = mock.metadata();
+
+    let (config, audits, imports) = files_inited(&metadata);
+
+    let mut store = Store::mock(config, audits, imports);
+
+    let mut network = Network::new_mock();
+    network.mock_serve(
+        "https://index.crates.io/th/ir/third-party1",
+        serde_json::to_string(&json!({
+            "name": "third-party1",
+            "vers": "10.0.0",
+            "deps": [],
+            "cksum": "90527ab4abff2f0608cdb1a78e2349180e1d92059f59b5a65ce2a1a15a499b73",
+            "features": {},
+            "yanked": false
+        }))
+        .unwrap(),
+    );
+
+    let output = BasicTestOutput::new();
+
+    let cfg = mock_cfg_args(
+        &metadata,
+        [
+            "cargo",
+            "vet",
+            "certify",
+            "third-party1",
+            "10.0.0",
+            "--who",
+            "testing",
+            "--criteria",
+            "safe-to-deploy",
+            "--accept-all",
+        ],
+    );
+    let sub_args = if let Some(crate::cli::Commands::Certify(sub_args)) = &cfg.cli.command {
+        sub_args
+    } else {
+        unreachable!();
+    };
+
+    crate::commands::do_cmd_certify(
+        &output.clone().as_dyn(),
+        &cfg,
+        sub_args,
+        &mut store,
+        Some(&network),
+        None,
+    )
+    .expect("do_cmd_certify failed");
+
+    let audit = &store.audits.audits["third-party1"][0];
+    assert_eq!(
+        audit.checksum.as_deref(),
+        Some("90527ab4abff2f0608cdb1a78e2349180e1d92059f59b5a65ce2a1a15a499b73")
+    );
+}
+
+#[test]
+fn mock_certify_frozen_without_checksum() {
+    // When frozen, the registry index can't be fetched, so a version which
+    // isn't cached is certified without a checksum.
+    let mock = MockMetadata::simple();
+
+    let _enter = TEST_RUNTIME.enter();
+    let metadata = mock.metadata();
+
+    let (config, audits, imports) = files_inited(&metadata);
+
+    let mut store = Store::mock(config, audits, imports);
+
+    let output = BasicTestOutput::new();
+
+    let cfg = mock_cfg_args(
+        &metadata,
+        [
+            "cargo",
+            "vet",
+            "certify",
+            "third-party1",
+            "10.0.0",
+            "--locked",
+            "--frozen",
+            "--who",
+            "testing",
+            "--criteria",
+            "safe-to-deploy",
+            "--accept-all",
+        ],
+    );
+    let sub_args = if let Some(crate::cli::Commands::Certify(sub_args)) = &cfg.cli.command {
+        sub_args
+    } else {
+        unreachable!();
+    };
+    assert!(Network::acquire(&cfg).is_none());
+
+    crate::commands::do_cmd_certify(
+        &output.clone().as_dyn(),
+        &cfg,
+        sub_args,
+        &mut store,
+        None,
+        None,
+    )
+    .expect("do_cmd_certify failed");
+
+    let audit = &store.audits.audits["third-party1"][0];
+    assert_eq!(
+        audit.kind,
+        AuditKind::Full {
+            version: ver(DEFAULT_VER)
+        }
+    );
+    assert_eq!(audit.checksum, None);
+}
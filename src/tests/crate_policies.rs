@@ -33,7 +33,7 @@ impl CratePolicyTest {
         let store = Store::mock(config, audits, imports);
         let cfg = mock_cfg(&metadata);
 
-        crate::check_crate_policies(&cfg, &store)
+        crate::commands::check_crate_policies(&cfg, &store)
     }
 }
 
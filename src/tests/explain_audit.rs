@@ -20,12 +20,14 @@ fn test_explain_audit_paths() {
     let store = Store::mock(config, audits, imports);
 
     let output = BasicTestOutput::new();
-    crate::do_cmd_explain_audit(
+    crate::commands::do_cmd_explain_audit(
         &output.clone().as_dyn(),
         &store,
         "third-party",
+        None,
         &ver(10),
         SAFE_TO_DEPLOY,
+        mock_today(),
     )
     .unwrap();
 
@@ -61,12 +63,14 @@ fn test_explain_audit_unpublished() {
     let store = Store::mock(config, audits, imports);
 
     let output = BasicTestOutput::new();
-    crate::do_cmd_explain_audit(
+    crate::commands::do_cmd_explain_audit(
         &output.clone().as_dyn(),
         &store,
         "descriptive",
+        None,
         &ver(10),
         SAFE_TO_DEPLOY,
+        mock_today(),
     )
     .unwrap();
 
@@ -95,12 +99,14 @@ fn test_explain_audit_wildcard() {
     let store = Store::mock(config, audits, imports);
 
     let output = BasicTestOutput::new();
-    crate::do_cmd_explain_audit(
+    crate::commands::do_cmd_explain_audit(
         &output.clone().as_dyn(),
         &store,
         "transitive-third-party1",
+        None,
         &ver(10),
         SAFE_TO_DEPLOY,
+        mock_today(),
     )
     .unwrap();
 
@@ -126,12 +132,14 @@ fn test_explain_audit_incomplete() {
     let store = Store::mock(config, audits, imports);
 
     let output = BasicTestOutput::new();
-    crate::do_cmd_explain_audit(
+    crate::commands::do_cmd_explain_audit(
         &output.clone().as_dyn(),
         &store,
         "third-party",
+        None,
         &ver(10),
         SAFE_TO_DEPLOY,
+        mock_today(),
     )
     .unwrap();
 
@@ -0,0 +1,107 @@
+use super::*;
+
+/// Write an HTML report for the store, and return the contents of the index
+/// and of the page for `package`.
+fn get_html_report(metadata: &Metadata, store: &Store, package: &str) -> (String, String) {
+    let dir = tempfile::tempdir().unwrap();
+    let report = crate::resolver::resolve(metadata, None, store, mock_today());
+    crate::html_report::write_html_report(dir.path(), &report, store).unwrap();
+
+    let index = std::fs::read_to_string(dir.path().join("index.html")).unwrap();
+    let page =
+        std::fs::read_to_string(dir.path().join(format!("crates/{package}/10.0.0.html"))).unwrap();
+    (index, page)
+}
+
+#[test]
+fn html_report() {
+    // Each crate's page shows its audit path, audits, exemptions and reverse
+    // dependencies.
+    let _enter = TEST_RUNTIME.enter();
+
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+
+    let (mut config, mut audits, imports) = builtin_files_full_audited(&metadata);
+    let audit = audits
+        .audits
+        .get_mut("third-party1")
+        .unwrap()
+        .first_mut()
+        .unwrap();
+    audit.who = vec!["Alice <alice@example.com>".to_owned().into()];
+    audit.notes = Some("Checked the unsafe code & build script".to_owned());
+    audits.audits.remove("third-party2");
+    config.exemptions.insert(
+        "third-party2".to_owned(),
+        vec![exemptions(ver(DEFAULT_VER), SAFE_TO_DEPLOY)],
+    );
+    audits.audits.remove("transitive-third-party1");
+    let store = Store::mock(config, audits, imports);
+
+    let (index, page) = get_html_report(&metadata, &store, "third-party1");
+    insta::assert_snapshot!("html_report_index", index);
+    insta::assert_snapshot!("html_report_third_party1", page);
+}
+
+#[test]
+fn html_report_alternative_registry() {
+    // Crates from alternative registries get their own pages, apart from crates
+    // with the same name on crates.io.
+    let _enter = TEST_RUNTIME.enter();
+    const INTERNAL_INDEX: &str = "sparse+https://cargo.internal.example.com/index/";
+    let metadata = MockMetadata::new(vec![
+        MockPackage {
+            name: "root",
+            is_workspace: true,
+            is_first_party: true,
+            deps: vec![dep("shared"), dep("user")],
+            ..Default::default()
+        },
+        MockPackage {
+            name: "shared",
+            ..Default::default()
+        },
+        MockPackage {
+            name: "user",
+            registry: Some(INTERNAL_INDEX),
+            deps: vec![dep_ver("shared", 5)],
+            ..Default::default()
+        },
+        MockPackage {
+            name: "shared",
+            version: ver(5),
+            registry: Some(INTERNAL_INDEX),
+            ..Default::default()
+        },
+    ])
+    .metadata();
+
+    let (mut config, audits, imports) = builtin_files_inited(&metadata);
+    config.registries.insert(
+        "internal".to_owned(),
+        CargoRegistryEntry {
+            index: INTERNAL_INDEX.to_owned(),
+            download: "https://cargo.internal.example.com/api/v1/crates".to_owned(),
+        },
+    );
+    let store = Store::mock(config, audits, imports);
+
+    let dir = tempfile::tempdir().unwrap();
+    let report = crate::resolver::resolve(&metadata, None, &store, mock_today());
+    crate::html_report::write_html_report(dir.path(), &report, &store).unwrap();
+
+    let index = std::fs::read_to_string(dir.path().join("index.html")).unwrap();
+    assert!(index.contains("href=\"crates/shared/10.0.0.html\""));
+    assert!(index.contains("href=\"registries/internal/shared/5.0.0.html\""));
+
+    let crates_io = std::fs::read_to_string(dir.path().join("crates/shared/10.0.0.html")).unwrap();
+    assert!(!crates_io.contains("<th>Registry</th>"));
+    assert!(crates_io.contains("href=\"../../style.css\""));
+
+    let internal =
+        std::fs::read_to_string(dir.path().join("registries/internal/shared/5.0.0.html")).unwrap();
+    assert!(internal.contains("<tr><th>Registry</th><td>internal</td></tr>"));
+    assert!(internal.contains("href=\"../../../style.css\""));
+    assert!(internal.contains("href=\"../../../registries/internal/user/10.0.0.html\""));
+}
@@ -2107,3 +2107,523 @@ fn wildcard_audit_trustpub_import() {
     let output = get_imports_file_changes_noprune(&metadata, &store);
     insta::assert_snapshot!(output);
 }
+
+/// Build an imports.lock which has imported `third-party2` from `FOREIGN`,
+/// along with the `AuditsFile` which was served at the time.
+fn pinned_import(metadata: &Metadata) -> (ConfigFile, AuditsFile, ImportsFile, AuditsFile) {
+    let (mut config, mut audits, mut imports) = builtin_files_full_audited(metadata);
+    audits.audits.remove("third-party2");
+
+    let foreign_audits = AuditsFile {
+        criteria: [("example".to_string(), criteria("Example criteria"))]
+            .into_iter()
+            .collect(),
+        wildcard_audits: SortedMap::new(),
+        audits: [(
+            "third-party2".to_owned(),
+            vec![
+                full_audit(ver(9), SAFE_TO_DEPLOY),
+                delta_audit(ver(9), ver(DEFAULT_VER), SAFE_TO_DEPLOY),
+            ],
+        )]
+        .into_iter()
+        .collect(),
+        trusted: SortedMap::new(),
+    };
+
+    config.imports.insert(
+        FOREIGN.to_owned(),
+        crate::format::RemoteImport {
+            url: vec![FOREIGN_URL.to_owned()],
+            criteria_map: [(
+                "example".to_string().into(),
+                vec![SAFE_TO_DEPLOY.to_string().into()],
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        },
+    );
+
+    let contents = crate::serialization::to_formatted_toml(&foreign_audits, None)
+        .unwrap()
+        .to_string();
+    let mut network = Network::new_mock();
+    network.mock_serve(FOREIGN_URL, &contents);
+    let store = Store::mock_online(
+        &mock_cfg(metadata),
+        config.clone(),
+        audits.clone(),
+        imports.clone(),
+        &network,
+        true,
+    )
+    .unwrap();
+    imports = crate::resolver::get_store_updates(&mock_cfg(metadata), &store, |_| {
+        crate::resolver::UpdateMode {
+            search_mode: crate::resolver::SearchMode::PreferFreshImports,
+            prune_exemptions: true,
+            prune_non_importable_audits: true,
+            prune_imports: true,
+        }
+    })
+    .imports;
+
+    (config, audits, imports, foreign_audits)
+}
+
+fn verify_import_hashes(
+    metadata: &Metadata,
+    store: &Store,
+    network: Option<&Network>,
+) -> Result<(), crate::errors::ImportsChangedError> {
+    let cfg = mock_cfg(metadata);
+    let cache = crate::storage::Cache::acquire(&cfg).unwrap();
+    TEST_RUNTIME.block_on(store.verify_import_hashes(&cfg, network, &cache))
+}
+
+#[test]
+fn locked_import_hash_unchanged() {
+    // (Pass) Re-downloading an import whose contents haven't changed verifies
+    // against the hash in imports.lock.
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+    let (config, audits, imports, foreign_audits) = pinned_import(&metadata);
+    assert!(imports.hashes[FOREIGN][FOREIGN_URL].starts_with("sha256:"));
+
+    let mut network = Network::new_mock();
+    network.mock_serve_toml(FOREIGN_URL, &foreign_audits);
+
+    let store = Store::mock(config, audits, imports);
+    verify_import_hashes(&metadata, &store, Some(&network)).unwrap();
+}
+
+#[test]
+fn locked_import_hash_changed() {
+    // (Fail) If an import's history was rewritten upstream, re-downloading it
+    // while locked fails, and explains what changed.
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+    let (config, audits, imports, mut foreign_audits) = pinned_import(&metadata);
+
+    foreign_audits
+        .audits
+        .get_mut("third-party2")
+        .unwrap()
+        .remove(1);
+    foreign_audits
+        .audits
+        .get_mut("third-party2")
+        .unwrap()
+        .push(full_audit(ver(DEFAULT_VER), SAFE_TO_DEPLOY));
+    foreign_audits.criteria.insert(
+        "example".to_string(),
+        criteria("Example criteria, with a twist"),
+    );
+
+    let mut network = Network::new_mock();
+    network.mock_serve_toml(FOREIGN_URL, &foreign_audits);
+
+    let store = Store::mock(config, audits, imports);
+    let output = match verify_import_hashes(&metadata, &store, Some(&network)) {
+        Ok(()) => panic!("expected the import hash check to fail"),
+        Err(error) => format!("{:?}", miette::Report::new(error)),
+    };
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn locked_import_hash_appended() {
+    // (Pass) If audits were only added to an import upstream, re-downloading it
+    // while locked still succeeds, even though its hash has changed.
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+    let (config, audits, imports, mut foreign_audits) = pinned_import(&metadata);
+
+    foreign_audits.audits.insert(
+        "third-party1".to_owned(),
+        vec![full_audit(ver(DEFAULT_VER), SAFE_TO_DEPLOY)],
+    );
+
+    let mut network = Network::new_mock();
+    network.mock_serve_toml(FOREIGN_URL, &foreign_audits);
+
+    let store = Store::mock(config, audits, imports);
+    verify_import_hashes(&metadata, &store, Some(&network)).unwrap();
+}
+
+#[test]
+fn locked_import_audit_rewritten() {
+    // (Fail) Rewriting the auditors, expiry, registry or checksum of an audit
+    // in imports.lock upstream is reported as a removed audit while locked.
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+
+    let rewrites: [fn(&mut AuditEntry); 4] = [
+        |audit| audit.who = vec!["someone else".to_owned().into()],
+        |audit| audit.expires = Some(mock_today()),
+        |audit| audit.registry = Some("my-registry".to_owned()),
+        |audit| audit.checksum = Some("0".repeat(64)),
+    ];
+    for rewrite in rewrites {
+        let (config, audits, imports, mut foreign_audits) = pinned_import(&metadata);
+        rewrite(&mut foreign_audits.audits.get_mut("third-party2").unwrap()[0]);
+
+        let mut network = Network::new_mock();
+        network.mock_serve_toml(FOREIGN_URL, &foreign_audits);
+
+        let store = Store::mock(config, audits, imports);
+        match verify_import_hashes(&metadata, &store, Some(&network)) {
+            Err(crate::errors::ImportsChangedError::Changed { changed }) => {
+                assert_eq!(changed.len(), 1);
+                assert_eq!(changed[0].changes.removed_audits.len(), 1);
+            }
+            other => panic!("expected the rewritten audit to be reported, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn locked_import_implies_changed() {
+    // (Pass) The implied criteria of imported criteria aren't recorded in
+    // imports.lock, as they're only used through the criteria map, so changing
+    // them upstream is accepted while locked.
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+    let (config, audits, imports, mut foreign_audits) = pinned_import(&metadata);
+    assert!(imports.audits[FOREIGN].criteria["example"]
+        .implies
+        .is_empty());
+
+    foreign_audits.criteria.insert(
+        "example".to_string(),
+        criteria_implies("Example criteria", [SAFE_TO_RUN]),
+    );
+
+    let mut network = Network::new_mock();
+    network.mock_serve_toml(FOREIGN_URL, &foreign_audits);
+
+    let store = Store::mock(config, audits, imports);
+    verify_import_hashes(&metadata, &store, Some(&network)).unwrap();
+}
+
+#[test]
+fn regenerate_import_summary() {
+    // Summarize how an import changed upstream when regenerating imports.
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+    let (config, audits, imports, mut foreign_audits) = pinned_import(&metadata);
+
+    foreign_audits
+        .audits
+        .get_mut("third-party2")
+        .unwrap()
+        .remove(1);
+    foreign_audits
+        .audits
+        .get_mut("third-party2")
+        .unwrap()
+        .push(full_audit(ver(DEFAULT_VER), SAFE_TO_DEPLOY));
+    foreign_audits.criteria.insert(
+        "example".to_string(),
+        criteria("Example criteria, with a twist"),
+    );
+
+    let cfg = mock_cfg(&metadata);
+    let mut network = Network::new_mock();
+    network.mock_serve_toml(FOREIGN_URL, &foreign_audits);
+
+    let mut store = Store::mock_online(&cfg, config, audits, imports, &network, true).unwrap();
+    let mut changes = store.upstream_import_changes();
+    let old_imports = store.imports.audits.clone();
+    crate::resolver::update_store(&cfg, &mut store, |_| crate::resolver::UpdateMode {
+        search_mode: crate::resolver::SearchMode::PreferFreshImports,
+        prune_exemptions: true,
+        prune_non_importable_audits: true,
+        prune_imports: true,
+    });
+    changes[0].add_new_audits(old_imports.get(FOREIGN), &store.imports.audits[FOREIGN]);
+
+    insta::assert_snapshot!(changes[0].to_string());
+}
+
+#[test]
+fn file_import() {
+    // (Pass) Audits can be imported from a local file, without the network.
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+    let (config, audits, imports, foreign_audits) = pinned_import(&metadata);
+
+    let file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(
+        file.path(),
+        crate::serialization::to_formatted_toml(&foreign_audits, None)
+            .unwrap()
+            .to_string(),
+    )
+    .unwrap();
+    let file_url = format!("file://{}", file.path().display());
+
+    let mut config = config;
+    config.imports.get_mut(FOREIGN).unwrap().url = vec![file_url.clone()];
+
+    let cfg = mock_cfg(&metadata);
+    let network = Network::new_mock();
+    let store = Store::mock_online(&cfg, config, audits, imports, &network, true).unwrap();
+    let live_imports = store.imported_audits();
+    assert!(live_imports[FOREIGN].audits.contains_key("third-party2"));
+
+    // Local imports can have their hashes checked while frozen.
+    let imports =
+        crate::resolver::get_store_updates(&cfg, &store, |_| crate::resolver::UpdateMode {
+            search_mode: crate::resolver::SearchMode::PreferFreshImports,
+            prune_exemptions: true,
+            prune_non_importable_audits: true,
+            prune_imports: true,
+        })
+        .imports;
+    assert!(imports.hashes[FOREIGN].contains_key(&file_url));
+    let store = Store::mock(store.config.clone(), store.audits.clone(), imports);
+    verify_import_hashes(&metadata, &store, None).unwrap();
+
+    std::fs::write(file.path(), "[audits]\n").unwrap();
+    assert!(verify_import_hashes(&metadata, &store, None).is_err());
+}
+
+/// Write `store` to `store_dir`, and acquire it again without the network, as
+/// `cargo vet --locked --frozen` would. Git imports need a real `cache_dir`.
+fn acquire_frozen(
+    metadata: &Metadata,
+    store: &Store,
+    store_dir: &std::path::Path,
+    cache_dir: Option<&std::path::Path>,
+) -> Result<Store, crate::errors::StoreAcquireError> {
+    for (name, contents) in store.mock_commit() {
+        std::fs::write(store_dir.join(name), contents).unwrap();
+    }
+
+    let mut cfg = mock_cfg_args(metadata, ["cargo", "vet", "--locked", "--frozen"]);
+    cfg.metacfg = MetaConfig(vec![crate::format::MetaConfigInstance {
+        version: Some(1),
+        store: Some(crate::format::StoreInfo {
+            path: Some(store_dir.to_owned()),
+        }),
+    }]);
+    if let Some(cache_dir) = cache_dir {
+        cfg._rest.cache_dir = cache_dir.to_owned();
+        cfg._rest.mock_cache = false;
+    }
+    Store::acquire(&cfg, None, false)
+}
+
+/// Generate an imports.lock for a local import, as `cargo vet` would.
+fn locked_local_import(
+    metadata: &Metadata,
+    config: ConfigFile,
+    audits: AuditsFile,
+    imports: ImportsFile,
+) -> Store {
+    let cfg = mock_cfg(metadata);
+    let network = Network::new_mock();
+    let mut store = Store::mock_online(&cfg, config, audits, imports, &network, true).unwrap();
+    crate::resolver::update_store(&cfg, &mut store, |_| crate::resolver::UpdateMode {
+        search_mode: crate::resolver::SearchMode::PreferFreshImports,
+        prune_exemptions: true,
+        prune_non_importable_audits: true,
+        prune_imports: true,
+    });
+    store
+}
+
+#[test]
+fn frozen_file_import_refreshed() {
+    // (Pass) `file:` imports are re-read while frozen, accepting audits added
+    // since imports.lock was generated, but not ones which were removed.
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+    let (config, audits, imports, mut foreign_audits) = pinned_import(&metadata);
+
+    let store_dir = tempfile::tempdir().unwrap();
+    let file = store_dir.path().join("foreign.toml");
+    let write_foreign = |foreign_audits: &AuditsFile| {
+        std::fs::write(
+            &file,
+            crate::serialization::to_formatted_toml(foreign_audits, None)
+                .unwrap()
+                .to_string(),
+        )
+        .unwrap()
+    };
+    write_foreign(&foreign_audits);
+
+    let mut config = config;
+    config.imports.get_mut(FOREIGN).unwrap().url = vec![format!("file://{}", file.display())];
+    let store = locked_local_import(&metadata, config, audits, imports);
+
+    foreign_audits.audits.insert(
+        "third-party1".to_owned(),
+        vec![full_audit(ver(DEFAULT_VER), SAFE_TO_DEPLOY)],
+    );
+    write_foreign(&foreign_audits);
+    let acquired = acquire_frozen(&metadata, &store, store_dir.path(), None).unwrap();
+    assert!(acquired.imported_audits()[FOREIGN]
+        .audits
+        .contains_key("third-party1"));
+    drop(acquired);
+
+    foreign_audits.audits.remove("third-party2");
+    write_foreign(&foreign_audits);
+    assert!(matches!(
+        acquire_frozen(&metadata, &store, store_dir.path(), None),
+        Err(crate::errors::StoreAcquireError::ImportsChanged(_))
+    ));
+}
+
+#[test]
+fn frozen_relative_file_import() {
+    // (Pass) Relative `file:` imports are read relative to the store while
+    // frozen.
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+    let (config, audits, imports, foreign_audits) = pinned_import(&metadata);
+
+    let store_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        store_dir.path().join("foreign.toml"),
+        crate::serialization::to_formatted_toml(&foreign_audits, None)
+            .unwrap()
+            .to_string(),
+    )
+    .unwrap();
+
+    let mut config = config;
+    config.imports.get_mut(FOREIGN).unwrap().url = vec!["file:foreign.toml".to_owned()];
+    let mut store = Store::mock(config, audits, imports);
+    store
+        .imports
+        .audits
+        .get_mut(FOREIGN)
+        .unwrap()
+        .audits
+        .clear();
+    store.imports.hashes.clear();
+
+    let acquired = acquire_frozen(&metadata, &store, store_dir.path(), None).unwrap();
+    assert!(acquired.imported_audits()[FOREIGN]
+        .audits
+        .contains_key("third-party2"));
+}
+
+#[test]
+fn frozen_git_import() {
+    // (Pass) `git+` imports are re-read while frozen once their commit has been
+    // fetched, and otherwise imports.lock is used as-is.
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+    let (config, audits, imports, foreign_audits) = pinned_import(&metadata);
+
+    let repo_dir = tempfile::tempdir().unwrap();
+    let git = |args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .current_dir(repo_dir.path())
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+        String::from_utf8(output.stdout).unwrap().trim().to_owned()
+    };
+    git(&["init", "--quiet"]);
+    std::fs::create_dir(repo_dir.path().join("supply-chain")).unwrap();
+    std::fs::write(
+        repo_dir.path().join("supply-chain/audits.toml"),
+        crate::serialization::to_formatted_toml(&foreign_audits, None)
+            .unwrap()
+            .to_string(),
+    )
+    .unwrap();
+    git(&["add", "."]);
+    git(&["commit", "--quiet", "-m", "audits"]);
+    let rev = git(&["rev-parse", "HEAD"]);
+    let repo = format!("file://{}", repo_dir.path().display());
+
+    let mut config = config;
+    config.imports.get_mut(FOREIGN).unwrap().url = vec![format!("git+{repo}?rev={rev}")];
+    let mut store = Store::mock(config, audits, imports);
+    store
+        .imports
+        .audits
+        .get_mut(FOREIGN)
+        .unwrap()
+        .audits
+        .clear();
+    store.imports.hashes.clear();
+
+    // The commit hasn't been fetched yet, so imports.lock is used.
+    let store_dir = tempfile::tempdir().unwrap();
+    let cache_dir = tempfile::tempdir().unwrap();
+    let acquired =
+        acquire_frozen(&metadata, &store, store_dir.path(), Some(cache_dir.path())).unwrap();
+    assert!(acquired.imported_audits()[FOREIGN].audits.is_empty());
+    drop(acquired);
+
+    let mut cfg = mock_cfg(&metadata);
+    cfg._rest.cache_dir = cache_dir.path().to_owned();
+    cfg._rest.mock_cache = false;
+    let cache = crate::storage::Cache::acquire(&cfg).unwrap();
+    TEST_RUNTIME
+        .block_on(cache.read_git_file(&repo, &rev, "supply-chain/audits.toml", false))
+        .unwrap();
+    drop(cache);
+
+    let acquired =
+        acquire_frozen(&metadata, &store, store_dir.path(), Some(cache_dir.path())).unwrap();
+    assert!(acquired.imported_audits()[FOREIGN]
+        .audits
+        .contains_key("third-party2"));
+}
+
+#[test]
+fn git_import_invalid_rev() {
+    // (Fail) `git+` imports must be pinned to a full commit hash.
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+    let (mut config, audits, imports) = builtin_files_full_audited(&metadata);
+    config.imports.insert(
+        FOREIGN.to_owned(),
+        crate::format::RemoteImport {
+            url: vec!["git+https://github.com/peer-company/audits?rev=main".to_owned()],
+            ..Default::default()
+        },
+    );
+
+    let cfg = mock_cfg(&metadata);
+    let network = Network::new_mock();
+    let output = match Store::mock_online(&cfg, config, audits, imports, &network, true) {
+        Ok(_) => panic!("expected the import to fail"),
+        Err(error) => format!("{:?}", miette::Report::new(error)),
+    };
+    insta::assert_snapshot!(output);
+}
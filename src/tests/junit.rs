@@ -0,0 +1,54 @@
+use super::*;
+
+/// Get the JUnit report for the store, including suggestions.
+fn get_junit(metadata: &Metadata, store: &Store) -> String {
+    let cfg = mock_cfg(metadata);
+    let report = crate::resolver::resolve(metadata, None, store, mock_today());
+    let suggest = report.compute_suggest(&cfg, store, None).unwrap();
+
+    let output = BasicTestOutput::new();
+    report
+        .print_junit(&output.clone().as_dyn(), suggest.as_ref())
+        .unwrap();
+    output.to_string()
+}
+
+#[test]
+fn junit_mixed() {
+    // Audited crates pass, exempted crates are skipped and unvetted crates
+    // fail with their suggested audit.
+    let _enter = TEST_RUNTIME.enter();
+
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+
+    let (mut config, mut audits, imports) = builtin_files_full_audited(&metadata);
+    audits.audits.remove("third-party2");
+    config.exemptions.insert(
+        "third-party2".to_owned(),
+        vec![exemptions(ver(DEFAULT_VER), SAFE_TO_DEPLOY)],
+    );
+    audits.audits.remove("transitive-third-party1");
+    let store = Store::mock(config, audits, imports);
+
+    insta::assert_snapshot!(get_junit(&metadata, &store));
+}
+
+#[test]
+fn junit_violation() {
+    // Crates conflicting with a violation fail, and everything else is skipped.
+    let _enter = TEST_RUNTIME.enter();
+
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+
+    let (config, mut audits, imports) = builtin_files_full_audited(&metadata);
+    audits
+        .audits
+        .get_mut("third-party1")
+        .unwrap()
+        .push(violation("*".parse().unwrap(), SAFE_TO_DEPLOY));
+    let store = Store::mock(config, audits, imports);
+
+    insta::assert_snapshot!(get_junit(&metadata, &store));
+}
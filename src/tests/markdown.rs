@@ -0,0 +1,94 @@
+use super::*;
+
+use crate::format::DiffStat;
+use crate::resolver::{DiffRecommendation, RegistrySuggestion, Suggest};
+
+/// Get the Markdown report for the store, after applying `edit_suggest` to the
+/// computed suggestions.
+fn get_markdown(
+    metadata: &Metadata,
+    store: &Store,
+    edit_suggest: impl FnOnce(&mut Suggest),
+) -> String {
+    let cfg = mock_cfg(metadata);
+    let report = crate::resolver::resolve(metadata, None, store, mock_today());
+    let mut suggest = report.compute_suggest(&cfg, store, None).unwrap();
+    if let Some(suggest) = &mut suggest {
+        edit_suggest(suggest);
+    }
+
+    let output = BasicTestOutput::new();
+    report
+        .print_markdown(&output.clone().as_dyn(), suggest.as_ref())
+        .unwrap();
+    output.to_string()
+}
+
+#[test]
+fn markdown_success() {
+    let _enter = TEST_RUNTIME.enter();
+
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+
+    let (config, audits, imports) = builtin_files_full_audited(&metadata);
+    let store = Store::mock(config, audits, imports);
+
+    insta::assert_snapshot!(get_markdown(&metadata, &store, |_| {}));
+}
+
+#[test]
+fn markdown_unvetted() {
+    // Failures and suggested audits are rendered as tables, with registry
+    // import suggestions in a collapsible section.
+    let _enter = TEST_RUNTIME.enter();
+
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+
+    let (config, audits, imports) = builtin_files_no_exemptions(&metadata);
+    let store = Store::mock(config, audits, imports);
+
+    let output = get_markdown(&metadata, &store, |suggest| {
+        let item = &mut suggest
+            .suggestions_by_criteria
+            .get_mut(SAFE_TO_DEPLOY)
+            .unwrap()[0];
+        item.publisher_login = Some("testuser".to_owned());
+        item.registry_suggestion.push(RegistrySuggestion {
+            name: "other-org".to_owned(),
+            url: vec!["https://other-org.example.com/audits.toml".to_owned()],
+            diff: DiffRecommendation {
+                from: Some(ver(5)),
+                to: ver(DEFAULT_VER),
+                diffstat: DiffStat {
+                    insertions: 10,
+                    deletions: 5,
+                    files_changed: 1,
+                },
+            },
+        });
+        suggest
+            .warnings
+            .push("could not fetch | everything".to_owned());
+    });
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn markdown_violation() {
+    let _enter = TEST_RUNTIME.enter();
+
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+
+    let (config, mut audits, imports) = builtin_files_full_audited(&metadata);
+    audits
+        .audits
+        .get_mut("third-party1")
+        .unwrap()
+        .push(violation("*".parse().unwrap(), SAFE_TO_DEPLOY));
+    let store = Store::mock(config, audits, imports);
+
+    insta::assert_snapshot!(get_markdown(&metadata, &store, |_| {}));
+}
@@ -4,7 +4,7 @@ use std::{
     fmt,
     fmt::Write,
     fs, io,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{Arc, Mutex},
 };
 
@@ -14,7 +14,8 @@ use serde_json::{json, Value};
 
 use crate::{
     format::{
-        AuditEntry, AuditKind, AuditsFile, ConfigFile, CratesAPICrate, CratesAPICrateMetadata,
+        Advisory, AdvisoryDbConfig, AuditEntry, AuditKind, AuditsFile, CargoRegistry,
+        CargoRegistryEntry, ConfigFile, CratesAPICrate, CratesAPICrateMetadata,
         CratesAPITrustpubData, CratesAPIUser, CratesAPIVersion, CratesPublisher, CratesSourceId,
         CratesUserId, CriteriaEntry, CriteriaMap, CriteriaName, CriteriaStr, ExemptedDependency,
         FastMap, ImportsFile, MetaConfig, PackageName, PackagePolicyEntry, PackageStr, PolicyEntry,
@@ -41,27 +42,45 @@ macro_rules! assert_report_snapshot {
         assert_report_snapshot!($name, $metadata, $store, None);
     };
     ($name:expr, $metadata:expr, $store:expr, $network:expr) => {{
-        let report = $crate::resolver::resolve(&$metadata, None, &$store);
+        let report =
+            $crate::resolver::resolve(&$metadata, None, &$store, $crate::tests::mock_today());
         let (human, json) = $crate::tests::get_reports(&$metadata, report, &$store, $network);
         insta::assert_snapshot!($name, human);
         insta::assert_snapshot!(concat!($name, ".json"), json);
     }};
 }
 
+mod advisories;
 mod aggregate;
+mod alternative_registry;
 mod audit_as_crates_io;
+mod audit_checksums;
+mod base;
+mod bundle;
 mod certify;
 mod crate_policies;
 mod explain_audit;
+mod html_report;
 mod import;
+mod junit;
+mod markdown;
+mod network;
+mod query;
 mod regenerate_unaudited;
 mod registry;
 mod renew;
+mod sarif;
+mod sbom;
+mod signatures;
+mod stats;
+mod store_diff;
 mod store_parsing;
 mod trusted;
 mod unpublished;
+mod vendor;
 mod vet;
 mod violations;
+mod why;
 mod wildcard;
 
 // Some room above and below
@@ -118,6 +137,8 @@ struct MockPackage {
     targets: Vec<&'static str>,
     is_workspace: bool,
     is_first_party: bool,
+    /// The index of the alternative registry this package is from, if any.
+    registry: Option<&'static str>,
 }
 
 struct MockDependency {
@@ -136,6 +157,7 @@ impl Default for MockPackage {
             targets: vec!["lib"],
             is_workspace: false,
             is_first_party: false,
+            registry: None,
         }
     }
 }
@@ -171,6 +193,7 @@ fn default_exemptions(version: VetVersion, config: &ConfigFile) -> ExemptedDepen
         criteria: vec![config.default_criteria.clone().into()],
         notes: None,
         suggest: true,
+        expires: None,
     }
 }
 fn exemptions(version: VetVersion, criteria: CriteriaStr) -> ExemptedDependency {
@@ -179,6 +202,7 @@ fn exemptions(version: VetVersion, criteria: CriteriaStr) -> ExemptedDependency
         criteria: vec![criteria.to_string().into()],
         notes: None,
         suggest: true,
+        expires: None,
     }
 }
 
@@ -188,6 +212,9 @@ fn delta_audit(from: VetVersion, to: VetVersion, criteria: CriteriaStr) -> Audit
         notes: None,
         criteria: vec![criteria.to_string().into()],
         kind: AuditKind::Delta { from, to },
+        expires: None,
+        registry: None,
+        checksum: None,
         importable: true,
         aggregated_from: vec![],
         is_fresh_import: false,
@@ -200,6 +227,9 @@ fn full_audit(version: VetVersion, criteria: CriteriaStr) -> AuditEntry {
         notes: None,
         criteria: vec![criteria.to_string().into()],
         kind: AuditKind::Full { version },
+        expires: None,
+        registry: None,
+        checksum: None,
         importable: true,
         aggregated_from: vec![],
         is_fresh_import: false,
@@ -215,6 +245,9 @@ fn full_audit_m(
         notes: None,
         criteria: criteria.into_iter().map(|s| s.into().into()).collect(),
         kind: AuditKind::Full { version },
+        expires: None,
+        registry: None,
+        checksum: None,
         importable: true,
         aggregated_from: vec![],
         is_fresh_import: false,
@@ -227,6 +260,9 @@ fn violation_hard(version: VersionReq) -> AuditEntry {
         notes: None,
         criteria: vec![SAFE_TO_RUN.to_string().into()],
         kind: AuditKind::Violation { violation: version },
+        expires: None,
+        registry: None,
+        checksum: None,
         importable: true,
         aggregated_from: vec![],
         is_fresh_import: false,
@@ -239,6 +275,9 @@ fn violation(version: VersionReq, criteria: CriteriaStr) -> AuditEntry {
         notes: None,
         criteria: vec![criteria.to_string().into()],
         kind: AuditKind::Violation { violation: version },
+        expires: None,
+        registry: None,
+        checksum: None,
         importable: true,
         aggregated_from: vec![],
         is_fresh_import: false,
@@ -254,6 +293,9 @@ fn violation_m(
         notes: None,
         criteria: criteria.into_iter().map(|s| s.into().into()).collect(),
         kind: AuditKind::Violation { violation: version },
+        expires: None,
+        registry: None,
+        checksum: None,
         importable: true,
         aggregated_from: vec![],
         is_fresh_import: false,
@@ -365,7 +407,9 @@ fn default_policy() -> PolicyEntry {
         audit_as_crates_io: None,
         criteria: None,
         dev_criteria: None,
+        build_criteria: None,
         dependency_criteria: SortedMap::new(),
+        min_auditors: None,
         notes: None,
     }
 }
@@ -840,6 +884,8 @@ impl MockMetadata {
             json!(null)
         } else if let Some(git_rev) = &package.version.git_rev {
             format!("git+https://github.com/owner/{}#{}", package.name, git_rev).into()
+        } else if let Some(registry) = package.registry {
+            json!(registry)
         } else {
             json!("registry+https://github.com/rust-lang/crates.io-index")
         }
@@ -948,7 +994,10 @@ fn init_files(
     let mut config = ConfigFile {
         cargo_vet: Default::default(),
         default_criteria: default_criteria.to_owned(),
+        targets: Default::default(),
         imports: Default::default(),
+        registries: Default::default(),
+        advisory_db: None,
         policy: Default::default(),
         exemptions: Default::default(),
     };
@@ -961,6 +1010,7 @@ fn init_files(
     let imports = ImportsFile {
         unpublished: SortedMap::new(),
         publisher: SortedMap::new(),
+        hashes: SortedMap::new(),
         audits: SortedMap::new(),
     };
 
@@ -975,7 +1025,9 @@ fn init_files(
                             audit_as_crates_io: None,
                             criteria: Some(vec![default_criteria.to_string().into()]),
                             dev_criteria: Some(vec![default_criteria.to_string().into()]),
+                            build_criteria: None,
                             dependency_criteria: CriteriaMap::new(),
+                            min_auditors: None,
                             notes: None,
                         }),
                     );
@@ -1043,7 +1095,7 @@ fn files_full_audited(metadata: &Metadata) -> (ConfigFile, AuditsFile, ImportsFi
 
     let mut audited = SortedMap::<PackageName, Vec<AuditEntry>>::new();
     for package in &metadata.packages {
-        if package.is_third_party(&config.policy) {
+        if package.is_third_party(&config) {
             audited
                 .entry(package.name.to_string())
                 .or_default()
@@ -1072,7 +1124,7 @@ fn builtin_files_full_audited(metadata: &Metadata) -> (ConfigFile, AuditsFile, I
 
     let mut audited = SortedMap::<PackageName, Vec<AuditEntry>>::new();
     for package in &metadata.packages {
-        if package.is_third_party(&config.policy) {
+        if package.is_third_party(&config) {
             audited
                 .entry(package.name.to_string())
                 .or_default()
@@ -1142,6 +1194,7 @@ where
     Config {
         metacfg: MetaConfig(vec![]),
         metadata: metadata.clone(),
+        targets: vec![],
         _rest: PartialConfig {
             cli,
             now: mock_now(),
@@ -1151,6 +1204,33 @@ where
     }
 }
 
+/// Write a `Cargo.lock` into `dir` with an entry for each package in
+/// `metadata` which isn't first-party, and make `dir` the workspace root.
+/// `checksum` gives the checksum to record for each package, if any.
+fn mock_lockfile(
+    metadata: &mut Metadata,
+    dir: &Path,
+    checksum: impl Fn(&cargo_metadata::Package) -> Option<String>,
+) {
+    metadata.workspace_root = dir.to_str().unwrap().into();
+    let mut lock = String::from("version = 3\n");
+    for package in &metadata.packages {
+        let Some(source) = &package.source else {
+            continue;
+        };
+        write!(
+            lock,
+            "\n[[package]]\nname = \"{}\"\nversion = \"{}\"\nsource = \"{source}\"\n",
+            package.name, package.version
+        )
+        .unwrap();
+        if let Some(checksum) = checksum(package) {
+            writeln!(lock, "checksum = \"{checksum}\"").unwrap();
+        }
+    }
+    fs::write(dir.join("Cargo.lock"), lock).unwrap();
+}
+
 fn get_reports(
     metadata: &Metadata,
     report: ResolveReport,
@@ -1170,7 +1250,7 @@ fn get_reports(
         .unwrap();
     let json_output = BasicTestOutput::new();
     report
-        .print_json(&json_output.clone().as_dyn(), suggest.as_ref())
+        .print_json(&json_output.clone().as_dyn(), &cfg, suggest.as_ref())
         .unwrap();
     (human_output.to_string(), json_output.to_string())
 }
@@ -0,0 +1,316 @@
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use reqwest::Url;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::network::{is_spurious_error, retry_delay, NetworkSettings};
+
+use super::*;
+
+/// Serve every request with the next of `responses`, repeating the last one
+/// once they run out. Returns the server's URL and a count of the requests it
+/// has handled.
+fn serve_responses(responses: Vec<String>) -> (Url, Arc<AtomicUsize>) {
+    let listener = TEST_RUNTIME
+        .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+        .unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap())
+        .parse()
+        .unwrap();
+    let requests = Arc::new(AtomicUsize::new(0));
+    let served = requests.clone();
+    TEST_RUNTIME.spawn(async move {
+        loop {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                return;
+            };
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                match stream.read(&mut buf).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => request.extend_from_slice(&buf[..n]),
+                }
+            }
+            let index = served.fetch_add(1, Ordering::SeqCst);
+            let response = &responses[index.min(responses.len() - 1)];
+            let _ = stream.write_all(response.as_bytes()).await;
+        }
+    });
+    (url, requests)
+}
+
+/// A response with the given status and a body of `ok`.
+fn status_response(status: u16) -> String {
+    format!("HTTP/1.1 {status} Status\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok")
+}
+
+/// A response which claims a longer body than is sent before the connection
+/// is closed, as if it was dropped part way through.
+fn truncated_response() -> String {
+    "HTTP/1.1 200 OK\r\ncontent-length: 10\r\nconnection: close\r\n\r\nok".to_owned()
+}
+
+/// Serve every request with the next status in `statuses`, repeating the last
+/// one once they run out.
+fn serve_statuses(statuses: Vec<u16>) -> (Url, Arc<AtomicUsize>) {
+    serve_responses(statuses.into_iter().map(status_response).collect())
+}
+
+/// Fetch `url` without retrying, returning the error it failed with.
+fn fetch_error(url: &str) -> reqwest::Error {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(200))
+        .build()
+        .unwrap();
+    TEST_RUNTIME
+        .block_on(async {
+            client
+                .get(url)
+                .send()
+                .await
+                .and_then(|res| res.error_for_status())
+        })
+        .unwrap_err()
+}
+
+#[test]
+fn retry_until_success() {
+    let (url, requests) = serve_statuses(vec![503, 503, 200]);
+    let network = Network::new_local(2);
+
+    let body = TEST_RUNTIME.block_on(network.download(url)).unwrap();
+    assert_eq!(body, b"ok");
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn retry_gives_up() {
+    let (url, requests) = serve_statuses(vec![503]);
+    let network = Network::new_local(1);
+
+    let error = TEST_RUNTIME.block_on(network.download(url)).unwrap_err();
+    assert!(matches!(
+        error,
+        crate::errors::DownloadError::FailedToStartDownload { .. }
+    ));
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn no_retry_on_client_error() {
+    let (url, requests) = serve_statuses(vec![404, 200]);
+    let network = Network::new_local(3);
+
+    TEST_RUNTIME.block_on(network.download(url)).unwrap_err();
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn retry_truncated_body() {
+    let (url, requests) = serve_responses(vec![truncated_response(), status_response(200)]);
+    let network = Network::new_local(1);
+
+    let body = TEST_RUNTIME.block_on(network.download(url)).unwrap();
+    assert_eq!(body, b"ok");
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn retry_truncated_body_persisted() {
+    let (url, requests) = serve_responses(vec![truncated_response(), status_response(200)]);
+    let network = Network::new_local(1);
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("download");
+
+    TEST_RUNTIME
+        .block_on(network.download_and_persist(url, &path))
+        .unwrap();
+    assert_eq!(fs::read(&path).unwrap(), b"ok");
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn retry_truncated_body_gives_up() {
+    // Retries of the body and of the request share the same limit.
+    let (url, requests) = serve_responses(vec![
+        status_response(503),
+        truncated_response(),
+        status_response(200),
+    ]);
+    let network = Network::new_local(1);
+
+    let error = TEST_RUNTIME.block_on(network.download(url)).unwrap_err();
+    assert!(matches!(
+        error,
+        crate::errors::DownloadError::FailedToReadDownload { .. }
+    ));
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn retry_delay_capped() {
+    for attempt in 0..64 {
+        let delay = retry_delay(attempt);
+        assert!(delay <= Duration::from_secs(10), "{attempt}: {delay:?}");
+    }
+    // The jitter never takes off more than half of the delay.
+    assert!(retry_delay(0) >= Duration::from_millis(250));
+    assert!(retry_delay(1) >= Duration::from_millis(500));
+    assert!(retry_delay(63) >= Duration::from_secs(5));
+}
+
+#[test]
+fn spurious_errors() {
+    for (status, spurious) in [(500, true), (503, true), (429, true), (404, false)] {
+        let (url, _) = serve_statuses(vec![status]);
+        assert_eq!(
+            is_spurious_error(&fetch_error(url.as_str())),
+            spurious,
+            "status {status}"
+        );
+    }
+
+    // Find a port which nothing is listening on.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+    let error = fetch_error(&format!("http://{addr}/"));
+    assert!(error.is_connect());
+    assert!(is_spurious_error(&error));
+
+    // A server which accepts connections but never responds.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let error = fetch_error(&format!("http://{}/", listener.local_addr().unwrap()));
+    assert!(error.is_timeout());
+    assert!(is_spurious_error(&error));
+}
+
+#[test]
+fn parse_proxy() {
+    // Both with and without a scheme, which defaults to http.
+    for proxy in [
+        "proxy.example.com:8080",
+        "http://proxy.example.com:8080",
+        "https://proxy.example.com:8443",
+    ] {
+        Network::parse_proxy(proxy).unwrap_or_else(|e| panic!("{proxy}: {e}"));
+    }
+    Network::parse_proxy("http://[::1").unwrap_err();
+}
+
+fn mock_cli(args: &[&str]) -> crate::cli::Cli {
+    let crate::cli::FakeCli::Vet(cli) =
+        crate::cli::FakeCli::try_parse_from(["cargo", "vet"].iter().chain(args)).unwrap();
+    cli
+}
+
+/// Load cargo's configuration from a directory containing `config`, with only
+/// the given environment variables set.
+fn mock_cargo_config(config: &str, env: &[(&str, &str)]) -> cargo_config2::Config {
+    let dir = tempfile::tempdir().unwrap();
+    let cargo_dir = dir.path().join(".cargo");
+    fs::create_dir(&cargo_dir).unwrap();
+    fs::write(cargo_dir.join("config.toml"), config).unwrap();
+    let cargo_home = dir.path().join("cargo-home");
+    fs::create_dir(&cargo_home).unwrap();
+    cargo_config2::Config::load_with_options(
+        dir.path(),
+        cargo_config2::ResolveOptions::default()
+            .env(env.iter().copied())
+            .cargo_home(cargo_home),
+    )
+    .unwrap()
+}
+
+const CARGO_CONFIG: &str = r#"
+[http]
+timeout = 30
+proxy = "config.example.com:8080"
+
+[net]
+retry = 5
+"#;
+
+#[test]
+fn network_settings_defaults() {
+    let settings = NetworkSettings::new(&mock_cli(&[]), None);
+    assert_eq!(
+        settings,
+        NetworkSettings {
+            timeout: 60,
+            retries: 3,
+            proxy: None,
+        }
+    );
+
+    let cargo_config = mock_cargo_config("", &[]);
+    let settings = NetworkSettings::new(&mock_cli(&[]), Some(&cargo_config));
+    assert_eq!(settings.timeout, 60);
+    assert_eq!(settings.retries, 3);
+}
+
+#[test]
+fn network_settings_cargo_config() {
+    let cargo_config = mock_cargo_config(CARGO_CONFIG, &[]);
+    let settings = NetworkSettings::new(&mock_cli(&[]), Some(&cargo_config));
+    assert_eq!(
+        settings,
+        NetworkSettings {
+            timeout: 30,
+            retries: 5,
+            proxy: Some("config.example.com:8080".to_owned()),
+        }
+    );
+}
+
+#[test]
+fn network_settings_env() {
+    // Environment variables take precedence over cargo's config files.
+    let cargo_config = mock_cargo_config(
+        CARGO_CONFIG,
+        &[
+            ("CARGO_HTTP_TIMEOUT", "45"),
+            ("CARGO_NET_RETRY", "7"),
+            ("CARGO_HTTP_PROXY", "env.example.com:8080"),
+        ],
+    );
+    let settings = NetworkSettings::new(&mock_cli(&[]), Some(&cargo_config));
+    assert_eq!(
+        settings,
+        NetworkSettings {
+            timeout: 45,
+            retries: 7,
+            proxy: Some("env.example.com:8080".to_owned()),
+        }
+    );
+}
+
+#[test]
+fn network_settings_cli() {
+    // The command line takes precedence over everything else.
+    let cargo_config = mock_cargo_config(
+        CARGO_CONFIG,
+        &[("CARGO_HTTP_TIMEOUT", "45"), ("CARGO_NET_RETRY", "7")],
+    );
+    let cli = mock_cli(&[
+        "--http-timeout",
+        "10",
+        "--net-retry",
+        "0",
+        "--http-proxy",
+        "cli.example.com:8080",
+    ]);
+    let settings = NetworkSettings::new(&cli, Some(&cargo_config));
+    assert_eq!(
+        settings,
+        NetworkSettings {
+            timeout: 10,
+            retries: 0,
+            proxy: Some("cli.example.com:8080".to_owned()),
+        }
+    );
+}
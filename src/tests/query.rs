@@ -0,0 +1,197 @@
+use crate::format::{ImportName, RegistryEntry, RegistryFile};
+
+use super::*;
+
+fn get_query(
+    store: &Store,
+    registry: Option<&[(ImportName, RegistryEntry, AuditsFile)]>,
+    package: &str,
+    version: Option<VetVersion>,
+) -> String {
+    let result = crate::query::query(store, registry, package, version.as_ref());
+    let output = BasicTestOutput::new();
+    crate::query::print_human(&output.clone().as_dyn(), &result).unwrap();
+    output.to_string()
+}
+
+/// A store where third-party1 has a local audit and violation, and an
+/// imported delta audit and trusted publisher.
+fn mock_store(metadata: &Metadata) -> Store {
+    let (mut config, mut audits, mut imports) = builtin_files_full_audited(metadata);
+    audits
+        .audits
+        .get_mut("third-party1")
+        .unwrap()
+        .push(violation(VersionReq::parse("<5").unwrap(), SAFE_TO_RUN));
+    config.imports.insert(
+        FOREIGN.to_owned(),
+        crate::format::RemoteImport {
+            url: vec![FOREIGN_URL.to_owned()],
+            ..Default::default()
+        },
+    );
+    imports.audits.insert(
+        FOREIGN.to_owned(),
+        AuditsFile {
+            audits: [(
+                "third-party1".to_owned(),
+                vec![delta_audit(ver(5), ver(DEFAULT_VER), SAFE_TO_DEPLOY)],
+            )]
+            .into_iter()
+            .collect(),
+            trusted: [(
+                "third-party1".to_owned(),
+                vec![trusted_entry(1, SAFE_TO_DEPLOY)],
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        },
+    );
+    Store::mock(config, audits, imports)
+}
+
+#[test]
+fn query_local_and_imported() {
+    // Entries from the local audits.toml and imports are listed, and the
+    // registry isn't searched without the network.
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+    let store = mock_store(&metadata);
+
+    insta::assert_snapshot!(get_query(&store, None, "third-party1", None));
+}
+
+#[test]
+fn query_version() {
+    // Only audits and violations which cover the version are listed.
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+    let store = mock_store(&metadata);
+
+    insta::assert_snapshot!(get_query(&store, None, "third-party1", Some(ver(3))));
+}
+
+#[test]
+fn query_not_found() {
+    // Crates which aren't in the graph can be searched for.
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+    let store = mock_store(&metadata);
+
+    insta::assert_snapshot!(get_query(&store, None, "new-crate", None));
+}
+
+/// A network serving a registry containing `FOREIGN`, which audits new-crate
+/// 1.0.0, and `OTHER_FOREIGN`, which audits new-crate 2.0.0.
+fn mock_registry_network() -> Network {
+    let mut network = Network::new_mock();
+    network.mock_serve_toml(
+        crate::storage::REGISTRY_URL,
+        &RegistryFile {
+            registry: [
+                (
+                    FOREIGN.to_owned(),
+                    RegistryEntry {
+                        url: vec![FOREIGN_URL.to_owned()],
+                    },
+                ),
+                (
+                    OTHER_FOREIGN.to_owned(),
+                    RegistryEntry {
+                        url: vec![OTHER_FOREIGN_URL.to_owned()],
+                    },
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        },
+    );
+    network.mock_serve_toml(
+        FOREIGN_URL,
+        &AuditsFile {
+            audits: [(
+                "new-crate".to_owned(),
+                vec![full_audit(ver(1), SAFE_TO_DEPLOY)],
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        },
+    );
+    network.mock_serve_toml(
+        OTHER_FOREIGN_URL,
+        &AuditsFile {
+            audits: [(
+                "new-crate".to_owned(),
+                vec![full_audit(ver(2), SAFE_TO_RUN)],
+            )]
+            .into_iter()
+            .collect(),
+            wildcard_audits: [(
+                "new-crate".to_owned(),
+                vec![wildcard_audit(2, SAFE_TO_DEPLOY)],
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        },
+    );
+    network
+}
+
+#[test]
+fn query_registry() {
+    // Sets of audits in the registry are searched, other than those which are
+    // already imported, including audits of crates which aren't in the graph.
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+    let cfg = mock_cfg(&metadata);
+
+    let network = mock_registry_network();
+
+    let (mut config, audits, imports) = builtin_files_full_audited(&metadata);
+    config.imports.insert(
+        FOREIGN.to_owned(),
+        crate::format::RemoteImport {
+            url: vec![FOREIGN_URL.to_owned()],
+            ..Default::default()
+        },
+    );
+    let mut store = Store::mock_online(&cfg, config, audits, imports, &network, true).unwrap();
+    let cache = crate::storage::Cache::acquire(&cfg).unwrap();
+    let registry = tokio::runtime::Handle::current()
+        .block_on(store.fetch_registry_audits(&cfg, &network, &cache))
+        .unwrap();
+
+    insta::assert_snapshot!(get_query(&store, Some(&registry), "new-crate", None));
+}
+
+#[test]
+fn query_registry_locked() {
+    // With --locked, imports aren't fetched and imports.lock only contains the
+    // audits which are used, so the registry's copy of imports is searched too.
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+    let cfg = mock_cfg_args(&metadata, ["cargo", "vet", "--locked"]);
+
+    let network = mock_registry_network();
+
+    let (mut config, audits, imports) = builtin_files_full_audited(&metadata);
+    config.imports.insert(
+        FOREIGN.to_owned(),
+        crate::format::RemoteImport {
+            url: vec![FOREIGN_URL.to_owned()],
+            ..Default::default()
+        },
+    );
+    let mut store = Store::mock(config, audits, imports);
+    let cache = crate::storage::Cache::acquire(&cfg).unwrap();
+    let registry = tokio::runtime::Handle::current()
+        .block_on(store.fetch_registry_audits(&cfg, &network, &cache))
+        .unwrap();
+
+    insta::assert_snapshot!(get_query(&store, Some(&registry), "new-crate", None));
+}
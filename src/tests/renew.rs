@@ -1,6 +1,6 @@
 use super::*;
 
-use crate::{do_cmd_renew, WildcardAuditRenewal};
+use crate::commands::{do_cmd_renew, AuditRenewal};
 
 struct ExpireTest {
     today: chrono::NaiveDate,
@@ -207,7 +207,7 @@ fn renew_expiring_set_false() {
 fn wildcard_audit_renewal_test<'a, Args, Create>(test_name: &str, args: Args, create: Create)
 where
     Args: IntoIterator<Item = &'a str>,
-    Create: for<'s> FnOnce(&Config, &'s mut Store) -> WildcardAuditRenewal<'s>,
+    Create: for<'s> FnOnce(&Config, &'s mut Store) -> AuditRenewal<'s>,
 {
     let _enter = TEST_RUNTIME.enter();
     let metadata = MockMetadata::simple().metadata();
@@ -269,7 +269,7 @@ fn renew_expiring_selection_logic() {
         "renew-expiring-selection-logic",
         ["--expiring"],
         |cfg, store| {
-            let renewal = WildcardAuditRenewal::expiring(cfg, store, false);
+            let renewal = AuditRenewal::expiring(cfg, store, false);
             assert_eq!(renewal.expired_crates(), vec!["foo", "quux"]);
             assert_eq!(renewal.expiring_crates(), vec!["baz", "foo"]);
             renewal
@@ -280,7 +280,7 @@ fn renew_expiring_selection_logic() {
 #[test]
 fn renew_specific_selection_logic() {
     wildcard_audit_renewal_test("renew-specific-selection-logic", ["foo"], |_, store| {
-        WildcardAuditRenewal::single_crate("foo", store).expect("store inconsistent")
+        AuditRenewal::single_crate("foo", store).expect("store inconsistent")
     });
 }
 
@@ -343,7 +343,7 @@ fn expiring_ignore_inactive_test(
     let mut store = Store::mock_online(&cfg, config, audits, imports, &network, false)
         .expect("store acquisition failed");
 
-    let renewal = WildcardAuditRenewal::expiring(&cfg, &mut store, true);
+    let renewal = AuditRenewal::expiring(&cfg, &mut store, true);
     match expected_status {
         ExpiringStatus::None => {
             assert!(renewal.is_empty(), "expected no audits needing renewal");
@@ -352,6 +352,7 @@ fn expiring_ignore_inactive_test(
             assert!(!renewal.is_empty(), "expected an audit needing renewal");
             assert_eq!(renewal.expiring_crates(), vec![package]);
             assert!(renewal.expired_crates().is_empty());
+            assert!(renewal.renewal_hint().contains("renew = false"));
         }
         ExpiringStatus::Expired => {
             assert!(!renewal.is_empty(), "expected an audit needing renewal");
@@ -410,3 +411,101 @@ fn expiring_ignore_inactive_test_expiring_active() {
         ExpiringStatus::Expiring,
     )
 }
+
+fn expiring_entries_renewal_test<'a>(test_name: &str, args: impl IntoIterator<Item = &'a str>) {
+    let _enter = TEST_RUNTIME.enter();
+    let metadata = MockMetadata::simple().metadata();
+    let (mut config, mut audits, imports) = builtin_files_no_exemptions(&metadata);
+
+    let today = mock_today();
+    use chrono::Duration;
+    let expired = today - Duration::weeks(1);
+    let expiring = today + Duration::weeks(1);
+    let not_expiring = today + Duration::weeks(7);
+
+    let audit = |version: u64, expires: Option<chrono::NaiveDate>| AuditEntry {
+        expires,
+        ..full_audit(ver(version), SAFE_TO_DEPLOY)
+    };
+    let exemption = |version: u64, expires: Option<chrono::NaiveDate>| ExemptedDependency {
+        expires,
+        ..exemptions(ver(version), SAFE_TO_DEPLOY)
+    };
+
+    audits.audits.insert(
+        "foo".into(),
+        vec![
+            audit(1, Some(expired)),
+            audit(2, Some(not_expiring)),
+            audit(3, None),
+        ],
+    );
+    audits
+        .audits
+        .insert("bar".into(), vec![audit(1, Some(expiring))]);
+    config.exemptions.insert(
+        "foo".into(),
+        vec![exemption(4, Some(not_expiring)), exemption(5, None)],
+    );
+    config
+        .exemptions
+        .insert("baz".into(), vec![exemption(1, Some(expired))]);
+
+    let mut store = Store::mock(config, audits, imports);
+    let cfg = mock_cfg_args(&metadata, ["cargo", "vet", "renew"].into_iter().chain(args));
+    let sub_args = if let Some(crate::cli::Commands::Renew(sub_args)) = &cfg.cli.command {
+        sub_args
+    } else {
+        unreachable!();
+    };
+
+    let before = store.mock_commit();
+    let output = BasicTestOutput::new();
+    do_cmd_renew(&output.clone().as_dyn(), &cfg, &mut store, sub_args);
+    let after = store.mock_commit();
+    insta::assert_snapshot!(
+        test_name,
+        format!("{output}\n{}", diff_store_commits(&before, &after))
+    );
+}
+
+#[test]
+fn renew_expiring_audits_and_exemptions() {
+    expiring_entries_renewal_test("renew-expiring-audits-and-exemptions", ["--expiring"]);
+}
+
+#[test]
+fn renew_specific_crate_audits_and_exemptions() {
+    expiring_entries_renewal_test("renew-specific-crate-audits-and-exemptions", ["foo"]);
+}
+
+#[test]
+fn expiring_hint_without_wildcard_audits() {
+    // Only wildcard entries can be marked `renew = false`, so expiring
+    // ordinary audits and exemptions shouldn't suggest it.
+    let metadata = MockMetadata::simple().metadata();
+    let (mut config, mut audits, imports) = builtin_files_no_exemptions(&metadata);
+
+    let expiring = mock_today() + chrono::Duration::weeks(1);
+    audits.audits.insert(
+        "foo".into(),
+        vec![AuditEntry {
+            expires: Some(expiring),
+            ..full_audit(ver(1), SAFE_TO_DEPLOY)
+        }],
+    );
+    config.exemptions.insert(
+        "bar".into(),
+        vec![ExemptedDependency {
+            expires: Some(expiring),
+            ..exemptions(ver(1), SAFE_TO_DEPLOY)
+        }],
+    );
+
+    let cfg = mock_cfg(&metadata);
+    let mut store = Store::mock(config, audits, imports);
+    let renewal = AuditRenewal::expiring(&cfg, &mut store, true);
+    assert_eq!(renewal.expiring_crates(), vec!["bar", "foo"]);
+    assert!(!renewal.renewal_hint().contains("renew = false"));
+    assert!(renewal.renewal_hint().contains("cargo vet certify"));
+}
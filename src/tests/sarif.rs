@@ -0,0 +1,75 @@
+use std::path::Path;
+
+use reqwest::Url;
+
+use super::*;
+
+/// Get the SARIF log for the store, with the workspace root redacted.
+fn get_sarif(metadata: &Metadata, store: &Store, dir: &Path) -> String {
+    let cfg = mock_cfg(metadata);
+    let report = crate::resolver::resolve(metadata, None, store, mock_today());
+    let log = report.sarif_log(&cfg, store, &dir.join("supply-chain"));
+    serde_json::to_string_pretty(&log).unwrap().replace(
+        &Url::from_directory_path(dir).unwrap().to_string(),
+        "file:///[ROOT]/",
+    )
+}
+
+#[test]
+fn sarif_unvetted() {
+    // Unvetted crates are located at their entries in Cargo.lock.
+    let _enter = TEST_RUNTIME.enter();
+
+    let dir = tempfile::tempdir().unwrap();
+    let mock = MockMetadata::simple();
+    let mut metadata = mock.metadata();
+    mock_lockfile(&mut metadata, dir.path(), |_| None);
+
+    let (config, audits, imports) = builtin_files_no_exemptions(&metadata);
+    let store = Store::mock(config, audits, imports);
+
+    insta::assert_snapshot!(get_sarif(&metadata, &store, dir.path()));
+}
+
+#[test]
+fn sarif_violation_and_expired_wildcard() {
+    // Local violations and expired wildcard audits, whether or not they're to
+    // be renewed, are located at their lines in audits.toml.
+    let _enter = TEST_RUNTIME.enter();
+
+    let dir = tempfile::tempdir().unwrap();
+    let mock = MockMetadata::simple();
+    let mut metadata = mock.metadata();
+    mock_lockfile(&mut metadata, dir.path(), |_| None);
+
+    let (config, mut audits, imports) = builtin_files_full_audited(&metadata);
+    audits
+        .audits
+        .get_mut("third-party1")
+        .unwrap()
+        .push(violation("*".parse().unwrap(), SAFE_TO_DEPLOY));
+    let mut expired = wildcard_audit(1, SAFE_TO_DEPLOY);
+    expired.start = mock_months_ago(6).date_naive().into();
+    expired.end = mock_weeks_ago(1).date_naive().into();
+    audits
+        .wildcard_audits
+        .insert("third-party2".to_owned(), vec![expired.clone()]);
+    // Opting out of renewal doesn't stop the audit from having expired.
+    expired.renew = Some(false);
+    audits
+        .wildcard_audits
+        .insert("transitive-third-party1".to_owned(), vec![expired]);
+
+    let config = crate::serialization::to_formatted_toml(&config, None)
+        .unwrap()
+        .to_string();
+    let audits = crate::serialization::to_formatted_toml(&audits, None)
+        .unwrap()
+        .to_string();
+    let imports = crate::serialization::to_formatted_toml(&imports, None)
+        .unwrap()
+        .to_string();
+    let store = Store::mock_acquire(&config, &audits, &imports, mock_today(), false).unwrap();
+
+    insta::assert_snapshot!(get_sarif(&metadata, &store, dir.path()));
+}
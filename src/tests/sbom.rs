@@ -0,0 +1,162 @@
+use sha2::{Digest, Sha256};
+
+use super::*;
+
+/// A store where third-party1 is audited by an import, third-party2 is
+/// exempted, and transitive-third-party1 is unvetted.
+fn mock_store(metadata: &Metadata) -> Store {
+    let (mut config, mut audits, mut imports) = builtin_files_full_audited(metadata);
+    let mut audit = audits.audits.remove("third-party1").unwrap().pop().unwrap();
+    audit.who = vec!["Alice <alice@example.com>".to_owned().into()];
+    imports.audits.insert(
+        FOREIGN.to_owned(),
+        AuditsFile {
+            audits: [("third-party1".to_owned(), vec![audit])]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        },
+    );
+    config.imports.insert(
+        FOREIGN.to_owned(),
+        crate::format::RemoteImport {
+            url: vec![FOREIGN_URL.to_owned()],
+            ..Default::default()
+        },
+    );
+    audits.audits.remove("third-party2");
+    config.exemptions.insert(
+        "third-party2".to_owned(),
+        vec![exemptions(ver(DEFAULT_VER), SAFE_TO_DEPLOY)],
+    );
+    audits.audits.remove("transitive-third-party1");
+    Store::mock(config, audits, imports)
+}
+
+fn get_sbom(format: crate::cli::SbomFormat) -> String {
+    let dir = tempfile::tempdir().unwrap();
+    let mock = MockMetadata::simple();
+    let mut metadata = mock.metadata();
+    // Name the workspace consistently, as the SPDX document is named after it.
+    let workspace = dir.path().join("workspace");
+    std::fs::create_dir(&workspace).unwrap();
+    mock_lockfile(&mut metadata, &workspace, |package| {
+        Some(format!("{:x}", Sha256::digest(package.name.as_bytes())))
+    });
+    let store = mock_store(&metadata);
+
+    let report = crate::resolver::resolve(&metadata, None, &store, mock_today());
+    let json = match format {
+        crate::cli::SbomFormat::Cyclonedx => serde_json::to_string_pretty(&crate::sbom::cyclonedx(
+            &metadata,
+            &report,
+            &store,
+            mock_now(),
+        )),
+        crate::cli::SbomFormat::Spdx => {
+            serde_json::to_string_pretty(&crate::sbom::spdx(&metadata, &report, &store, mock_now()))
+        }
+    }
+    .unwrap();
+    json.replace(env!("CARGO_PKG_VERSION"), "[VERSION]")
+}
+
+#[test]
+fn sbom_cyclonedx() {
+    let _enter = TEST_RUNTIME.enter();
+    insta::assert_snapshot!(get_sbom(crate::cli::SbomFormat::Cyclonedx));
+}
+
+#[test]
+fn sbom_spdx() {
+    let _enter = TEST_RUNTIME.enter();
+    insta::assert_snapshot!(get_sbom(crate::cli::SbomFormat::Spdx));
+}
+
+#[test]
+fn sbom_package_sources() {
+    // Packages from alternative registries and git repositories are identified
+    // by where they came from, and path dependencies have no purl.
+    let _enter = TEST_RUNTIME.enter();
+    const INTERNAL_INDEX: &str = "sparse+https://cargo.internal.example.com/index/";
+    const GIT_VERSION: &str = "10.0.0@git:00112233445566778899aabbccddeeff00112233";
+    let metadata = MockMetadata::new(vec![
+        MockPackage {
+            name: "root",
+            is_workspace: true,
+            is_first_party: true,
+            deps: vec![
+                dep("internal-crate"),
+                dep("third-party"),
+                MockDependency {
+                    name: "git-crate",
+                    version: GIT_VERSION.parse().unwrap(),
+                },
+            ],
+            ..Default::default()
+        },
+        MockPackage {
+            name: "internal-crate",
+            registry: Some(INTERNAL_INDEX),
+            ..Default::default()
+        },
+        MockPackage {
+            name: "third-party",
+            ..Default::default()
+        },
+        MockPackage {
+            name: "git-crate",
+            version: GIT_VERSION.parse().unwrap(),
+            ..Default::default()
+        },
+    ])
+    .metadata();
+
+    let (mut config, audits, imports) = builtin_files_inited(&metadata);
+    config.registries.insert(
+        "internal".to_owned(),
+        CargoRegistryEntry {
+            index: INTERNAL_INDEX.to_owned(),
+            download: "https://cargo.internal.example.com/api/v1/crates".to_owned(),
+        },
+    );
+    let store = Store::mock(config, audits, imports);
+    let report = crate::resolver::resolve(&metadata, None, &store, mock_today());
+
+    let bom = crate::sbom::cyclonedx(&metadata, &report, &store, mock_now());
+    let purls: SortedMap<_, _> = bom
+        .components
+        .iter()
+        .map(|component| (component.name.as_str(), component.purl.as_deref()))
+        .collect();
+    assert_eq!(purls["root"], None);
+    assert_eq!(purls["third-party"], Some("pkg:cargo/third-party@10.0.0"));
+    assert_eq!(
+        purls["internal-crate"],
+        Some("pkg:cargo/internal-crate@10.0.0?repository_url=https%3A%2F%2Fcargo.internal.example.com%2Findex%2F")
+    );
+    assert_eq!(
+        purls["git-crate"],
+        Some("pkg:cargo/git-crate@10.0.0?vcs_url=git%2Bhttps%3A%2F%2Fgithub.com%2Fowner%2Fgit-crate%4000112233445566778899aabbccddeeff00112233")
+    );
+
+    let document = crate::sbom::spdx(&metadata, &report, &store, mock_now());
+    let locations: SortedMap<_, _> = document
+        .packages
+        .iter()
+        .map(|package| (package.name.as_str(), package.download_location.as_str()))
+        .collect();
+    assert_eq!(locations["root"], "NOASSERTION");
+    assert_eq!(
+        locations["third-party"],
+        "https://crates.io/api/v1/crates/third-party/10.0.0/download"
+    );
+    assert_eq!(
+        locations["internal-crate"],
+        "https://cargo.internal.example.com/api/v1/crates/internal-crate/10.0.0/download"
+    );
+    assert_eq!(
+        locations["git-crate"],
+        "git+https://github.com/owner/git-crate@00112233445566778899aabbccddeeff00112233"
+    );
+}
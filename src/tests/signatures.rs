@@ -0,0 +1,186 @@
+use insta::assert_snapshot;
+use ssh_key::{private::Ed25519Keypair, PrivateKey};
+
+use super::*;
+use crate::errors::{SignatureError, StoreAcquireError};
+use crate::signing::{self, ImportKey};
+
+const SIGNED_URL: &str = "https://peercompany.co.uk/supply-chain/audits.toml";
+const SSH_SIGNATURE_URL: &str = "https://peercompany.co.uk/supply-chain/audits.toml.sig";
+
+fn test_key(seed: u8) -> PrivateKey {
+    Ed25519Keypair::from_seed(&[seed; 32]).into()
+}
+
+fn public_key(key: &PrivateKey) -> String {
+    key.public_key().to_openssh().unwrap()
+}
+
+fn sign(key: &PrivateKey, data: &str) -> String {
+    key.sign(
+        signing::SSH_SIGNATURE_NAMESPACE,
+        ssh_key::HashAlg::Sha512,
+        data.as_bytes(),
+    )
+    .unwrap()
+    .to_pem(ssh_key::LineEnding::LF)
+    .unwrap()
+}
+
+fn foreign_audits() -> String {
+    let audits = AuditsFile {
+        criteria: SortedMap::new(),
+        wildcard_audits: SortedMap::new(),
+        audits: [(
+            "third-party2".to_owned(),
+            vec![full_audit(ver(DEFAULT_VER), SAFE_TO_DEPLOY)],
+        )]
+        .into_iter()
+        .collect(),
+        trusted: SortedMap::new(),
+    };
+    crate::serialization::to_formatted_toml(audits, None)
+        .unwrap()
+        .to_string()
+}
+
+/// Try to acquire a store which imports `audits` signed with `signature` from
+/// a peer which declares `public_key`.
+fn acquire_signed(
+    public_key: &str,
+    audits: &str,
+    signature: Option<&str>,
+) -> Result<Store, StoreAcquireError> {
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+    let (mut config, mut audits_file, imports) = builtin_files_full_audited(&metadata);
+    audits_file.audits.remove("third-party2");
+
+    config.imports.insert(
+        FOREIGN.to_owned(),
+        crate::format::RemoteImport {
+            url: vec![SIGNED_URL.to_owned()],
+            public_key: Some(public_key.to_owned()),
+            ..Default::default()
+        },
+    );
+
+    let cfg = mock_cfg(&metadata);
+
+    let mut network = Network::new_mock();
+    network.mock_serve(SIGNED_URL, audits);
+    if let Some(signature) = signature {
+        network.mock_serve(SSH_SIGNATURE_URL, signature);
+    }
+
+    Store::mock_online(&cfg, config, audits_file, imports, &network, true)
+}
+
+fn acquire_signed_error(public_key: &str, audits: &str, signature: Option<&str>) -> String {
+    match acquire_signed(public_key, audits, signature) {
+        Ok(_) => panic!("expected the signature check to fail"),
+        Err(error) => format!("{:?}", miette::Report::new(error)),
+    }
+}
+
+#[test]
+fn signed_import() {
+    // (Pass) Audits signed with the import's public key are imported.
+
+    let _enter = TEST_RUNTIME.enter();
+    let key = test_key(1);
+    let audits = foreign_audits();
+
+    let store = acquire_signed(&public_key(&key), &audits, Some(&sign(&key, &audits))).unwrap();
+    assert!(store.imported_audits()[FOREIGN]
+        .audits
+        .contains_key("third-party2"));
+}
+
+#[test]
+fn signed_import_tampered() {
+    // (Fail) Audits which were modified after signing are rejected.
+
+    let _enter = TEST_RUNTIME.enter();
+    let key = test_key(1);
+    let audits = foreign_audits();
+    let signature = sign(&key, &audits);
+    let tampered = audits.replace("safe-to-deploy", "safe-to-run");
+
+    let output = acquire_signed_error(&public_key(&key), &tampered, Some(&signature));
+    assert_snapshot!(output);
+}
+
+#[test]
+fn signed_import_wrong_key() {
+    // (Fail) Audits signed with a different key are rejected.
+
+    let _enter = TEST_RUNTIME.enter();
+    let audits = foreign_audits();
+    let signature = sign(&test_key(2), &audits);
+
+    let output = acquire_signed_error(&public_key(&test_key(1)), &audits, Some(&signature));
+    assert_snapshot!(output);
+}
+
+#[test]
+fn signed_import_missing_signature() {
+    // (Fail) Imports with a public key must be signed.
+
+    let _enter = TEST_RUNTIME.enter();
+    let audits = foreign_audits();
+
+    let output = acquire_signed_error(&public_key(&test_key(1)), &audits, None);
+    assert_snapshot!(output);
+}
+
+#[test]
+fn signed_import_invalid_public_key() {
+    // (Fail) Unparseable public keys are reported.
+
+    let _enter = TEST_RUNTIME.enter();
+    let audits = foreign_audits();
+
+    let output = acquire_signed_error("ssh-ed25519 garbage", &audits, None);
+    assert_snapshot!(output);
+}
+
+#[test]
+fn minisign_signature() {
+    // Minisign keys and signatures are verified, and expected next to the
+    // audits with a `.minisig` extension.
+
+    let key = ImportKey::parse(
+        "untrusted comment: minisign public key 37DDC2F7C2CEB9CA\n\
+         RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3",
+    )
+    .unwrap();
+    let signature = "untrusted comment: signature from minisign secret key\n\
+        RUQf6LRCGA9i559r3g7V1qNyJDApGip8MfqcadIgT9CuhV3EMhHoN1mGTkUidF/z7SrlQgXdy8ofjb7bNJJylDOocrCo8KLzZwo=\n\
+        trusted comment: timestamp:1633700835\tfile:test\tprehashed\n\
+        wLMDjy9FLAuxZ3q4NlEvkgtyhrr0gtTu6KC4KBJdITbbOeAi1zBIYo0v4iTgt8jJpIidRJnp94ABQkJAgAooBQ==\n";
+
+    assert_eq!(key.signature_extension(), "minisig");
+    key.verify(b"test", signature).unwrap();
+    assert!(matches!(
+        key.verify(b"tampered", signature),
+        Err(SignatureError::Mismatch)
+    ));
+}
+
+#[test]
+fn aggregate_signature_verifies() {
+    // (Pass) Signatures produced by `cargo vet aggregate --sign-with` can be
+    // verified by an import with the matching public key.
+
+    let key = test_key(3);
+    let key_file = tempfile::NamedTempFile::new().unwrap();
+    key.write_openssh_file(key_file.path(), ssh_key::LineEnding::LF)
+        .unwrap();
+
+    let audits = foreign_audits();
+    let signature = signing::sign_with_ssh_key(key_file.path(), audits.as_bytes()).unwrap();
+
+    let import_key = ImportKey::parse(&public_key(&key)).unwrap();
+    import_key.verify(audits.as_bytes(), &signature).unwrap();
+}
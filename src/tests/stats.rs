@@ -0,0 +1,138 @@
+use super::*;
+
+fn get_stats(metadata: &Metadata, store: &Store) -> (String, String) {
+    let cfg = mock_cfg(metadata);
+    let report = crate::resolver::resolve(metadata, None, store, mock_today());
+    let stats = crate::stats::compute_stats(&cfg, &report, store, None).unwrap();
+
+    let output = BasicTestOutput::new();
+    crate::stats::print_human(&output.clone().as_dyn(), &stats).unwrap();
+    (
+        output.to_string(),
+        serde_json::to_string_pretty(&stats).unwrap(),
+    )
+}
+
+#[test]
+fn stats_full_audited() {
+    // Every package is vetted fully by local audits.
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+
+    let (config, audits, imports) = builtin_files_full_audited(&metadata);
+    let store = Store::mock(config, audits, imports);
+
+    let (human, _) = get_stats(&metadata, &store);
+    insta::assert_snapshot!(human);
+}
+
+#[test]
+fn stats_mixed() {
+    // third-party1 is vetted fully by an import, third-party2 is vetted
+    // partially by a local delta audit from an exempted version, and
+    // transitive-third-party1 is unvetted.
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+
+    let (mut config, mut audits, mut imports) = builtin_files_full_audited(&metadata);
+    let audit = audits.audits.remove("third-party1").unwrap().pop().unwrap();
+    imports.audits.insert(
+        FOREIGN.to_owned(),
+        AuditsFile {
+            audits: [("third-party1".to_owned(), vec![audit])]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        },
+    );
+    config.imports.insert(
+        FOREIGN.to_owned(),
+        crate::format::RemoteImport {
+            url: vec![FOREIGN_URL.to_owned()],
+            ..Default::default()
+        },
+    );
+    audits.audits.insert(
+        "third-party2".to_owned(),
+        vec![delta_audit(ver(5), ver(DEFAULT_VER), SAFE_TO_DEPLOY)],
+    );
+    config.exemptions.insert(
+        "third-party2".to_owned(),
+        vec![exemptions(ver(5), SAFE_TO_DEPLOY)],
+    );
+    audits.audits.remove("transitive-third-party1");
+    let store = Store::mock(config, audits, imports);
+
+    let (human, json) = get_stats(&metadata, &store);
+    insta::assert_snapshot!("stats_mixed_human", human);
+    insta::assert_snapshot!("stats_mixed_json", json);
+}
+
+#[test]
+fn stats_match_report() {
+    // The packages are counted in the same buckets as in the report, including
+    // when some of them fail to vet.
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+
+    let (mut config, mut audits, imports) = builtin_files_inited(&metadata);
+    config.exemptions.remove("third-party1");
+    config.exemptions.remove("transitive-third-party1");
+    audits.audits.insert(
+        "third-party1".to_owned(),
+        vec![full_audit(ver(DEFAULT_VER), SAFE_TO_DEPLOY)],
+    );
+    let store = Store::mock(config, audits, imports);
+
+    let cfg = mock_cfg(&metadata);
+    let report = crate::resolver::resolve(&metadata, None, &store, mock_today());
+    let stats = crate::stats::compute_stats(&cfg, &report, &store, None).unwrap();
+
+    let crate::resolver::Conclusion::FailForVet(fail) = &report.conclusion else {
+        panic!("expected vetting to fail");
+    };
+    let coverage = &stats.coverage;
+    assert_eq!(
+        coverage.vetted_fully.packages,
+        fail.vetted.vetted_fully.len() as u64
+    );
+    assert_eq!(
+        coverage.vetted_partially.packages,
+        fail.vetted.vetted_partially.len() as u64
+    );
+    assert_eq!(
+        coverage.vetted_with_exemptions.packages,
+        fail.vetted.vetted_with_exemptions.len() as u64
+    );
+    assert_eq!(coverage.unvetted.packages, fail.failures.len() as u64);
+    assert_eq!(coverage.vetted_fully.packages, 1);
+    assert_eq!(coverage.vetted_with_exemptions.packages, 1);
+    assert_eq!(coverage.unvetted.packages, 1);
+}
+
+#[test]
+fn stats_rejects_report_formats() {
+    // Only `check` and `suggest` can print SARIF, JUnit or Markdown reports.
+    let metadata = MockMetadata::simple().metadata();
+    let cfg = mock_cfg_args(
+        &metadata,
+        ["cargo", "vet", "stats", "--output-format", "sarif"],
+    );
+    let error = crate::commands::PlainOutputFormat::of(&cfg).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "--output-format=sarif is only supported by `cargo vet check`"
+    );
+
+    let cfg = mock_cfg_args(
+        &metadata,
+        ["cargo", "vet", "stats", "--output-format", "json"],
+    );
+    assert_eq!(
+        crate::commands::PlainOutputFormat::of(&cfg).unwrap(),
+        crate::commands::PlainOutputFormat::Json
+    );
+}
@@ -0,0 +1,83 @@
+use super::*;
+
+fn get_store_diff(old: &Store, new: &Store) -> String {
+    let diff = crate::store_diff::diff_stores(old, new);
+    let output = BasicTestOutput::new();
+    crate::store_diff::print_human(&output.clone().as_dyn(), &diff).unwrap();
+    output.to_string()
+}
+
+#[test]
+fn store_diff_unchanged() {
+    // Comparing a store against itself reports no changes.
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+
+    let (config, audits, imports) = builtin_files_full_audited(&metadata);
+    let store = Store::mock(config, audits, imports);
+
+    insta::assert_snapshot!(get_store_diff(&store, &store));
+}
+
+#[test]
+fn store_diff_changes() {
+    // Each kind of change to the store is listed in its own section, with
+    // imported audits marked with the import they came from.
+    let mock = MockMetadata::simple();
+    let metadata = mock.metadata();
+
+    let (config, audits, imports) = builtin_files_full_audited(&metadata);
+    let old = Store::mock(config.clone(), audits.clone(), imports.clone());
+
+    let (mut config, mut audits, mut imports) = (config, audits, imports);
+    audits.audits.remove("transitive-third-party1");
+    audits
+        .audits
+        .get_mut("third-party1")
+        .unwrap()
+        .push(delta_audit(ver(5), ver(DEFAULT_VER), SAFE_TO_RUN));
+    audits.criteria.insert(
+        "fuzzed".to_owned(),
+        criteria_implies("fuzzed", [SAFE_TO_RUN]),
+    );
+    audits.trusted.insert(
+        "third-party2".to_owned(),
+        vec![trusted_entry(1, SAFE_TO_DEPLOY)],
+    );
+    config.exemptions.insert(
+        "transitive-third-party1".to_owned(),
+        vec![exemptions(ver(DEFAULT_VER), SAFE_TO_DEPLOY)],
+    );
+    config
+        .policy
+        .insert("first-party".to_owned(), self_policy([SAFE_TO_RUN]));
+    config.imports.insert(
+        FOREIGN.to_owned(),
+        crate::format::RemoteImport {
+            url: vec![FOREIGN_URL.to_owned()],
+            ..Default::default()
+        },
+    );
+    imports.audits.insert(
+        FOREIGN.to_owned(),
+        AuditsFile {
+            criteria: SortedMap::new(),
+            wildcard_audits: [(
+                "third-party2".to_owned(),
+                vec![wildcard_audit(2, SAFE_TO_DEPLOY)],
+            )]
+            .into_iter()
+            .collect(),
+            audits: [(
+                "transitive-third-party1".to_owned(),
+                vec![full_audit(ver(DEFAULT_VER), SAFE_TO_DEPLOY)],
+            )]
+            .into_iter()
+            .collect(),
+            trusted: SortedMap::new(),
+        },
+    );
+    let new = Store::mock(config, audits, imports);
+
+    insta::assert_snapshot!(get_store_diff(&old, &new));
+}
@@ -305,6 +305,21 @@ unknown-field = "invalid"
     insta::assert_snapshot!(acquire_errors);
 }
 
+#[test]
+fn test_expiring_violation() {
+    let audits = r#"
+# cargo-vet audits file
+
+[[audits.zzz]]
+criteria = "safe-to-deploy"
+violation = "*"
+expires = "2023-06-01"
+"#;
+
+    let acquire_errors = get_valid_store(EMPTY_CONFIG, audits, EMPTY_IMPORTS);
+    insta::assert_snapshot!(acquire_errors);
+}
+
 #[test]
 fn test_distant_future_end_date() {
     // NOTE: `get_valid_store` pretends that "today" is 2023-01-01, so this will
@@ -439,3 +454,72 @@ description = "fuzzed"
     let acquire_errors = get_valid_store(config, EMPTY_AUDITS, imports);
     insta::assert_snapshot!(acquire_errors);
 }
+
+#[test]
+fn test_config_targets() {
+    let config = r#"
+# cargo-vet config file
+
+targets = ["aarch64-unknown-linux-gnu", "x86_64-unknown-linux-gnu"]
+
+[cargo-vet]
+version = "1.0"
+
+[policy.clap]
+criteria = "safe-to-deploy"
+dev-criteria = "safe-to-run"
+
+[policy.targets]
+criteria = "safe-to-run"
+"#;
+
+    let acquire_errors = get_valid_store(config, EMPTY_AUDITS, EMPTY_IMPORTS);
+    assert_eq!(acquire_errors, "");
+
+    let store = crate::Store::mock_acquire(
+        config,
+        EMPTY_AUDITS,
+        EMPTY_IMPORTS,
+        chrono::NaiveDate::from_ymd_opt(2023, 1, 1).unwrap(),
+        true,
+    )
+    .unwrap();
+    assert_eq!(
+        store.config.targets,
+        ["aarch64-unknown-linux-gnu", "x86_64-unknown-linux-gnu"]
+    );
+    assert!(store.config.policy.package.contains_key("clap"));
+    // A crate named `targets` can have its policy set like any other crate.
+    assert!(store.config.policy.package.contains_key("targets"));
+}
+
+#[test]
+fn test_unknown_registry() {
+    let config = r#"
+# cargo-vet config file
+
+[cargo-vet]
+version = "1.0"
+
+[registries.internal]
+index = "sparse+https://cargo.internal.example.com/index/"
+download = "https://cargo.internal.example.com/api/v1/crates"
+"#;
+
+    let audits = r#"
+# cargo-vet audits file
+
+[[audits.known]]
+criteria = "safe-to-deploy"
+version = "1.0.0"
+registry = "internal"
+
+[[audits.unknown]]
+criteria = "safe-to-deploy"
+version = "1.0.0"
+registry = "external"
+"#;
+
+    let acquire_errors = get_valid_store(config, audits, EMPTY_IMPORTS);
+    insta::assert_snapshot!(acquire_errors);
+}
@@ -0,0 +1,247 @@
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+use super::*;
+use crate::errors::VendorError;
+use crate::storage::VendoredSources;
+
+/// The files in each mock crate.
+fn package_files(package: &str) -> [(&'static str, String); 2] {
+    [
+        (
+            "Cargo.toml",
+            format!("[package]\nname = \"{package}\"\nversion = \"10.0.0\"\n"),
+        ),
+        ("src/lib.rs", "pub fn hello() {}\n".to_owned()),
+    ]
+}
+
+/// Build the `.crate` file for a mock crate, returning it along with its
+/// checksum.
+fn crate_archive(package: &str) -> (Vec<u8>, String) {
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        Vec::new(),
+        flate2::Compression::default(),
+    ));
+    for (path, contents) in package_files(package) {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(
+                &mut header,
+                format!("{package}-10.0.0/{path}"),
+                contents.as_bytes(),
+            )
+            .unwrap();
+    }
+    let archive = builder.into_inner().unwrap().finish().unwrap();
+    let checksum = format!("{:x}", Sha256::digest(&archive));
+    (archive, checksum)
+}
+
+/// Vendor a crate into `dir` like `cargo vendor` would, returning its path.
+fn vendor_package(dir: &Path, package: &str, package_checksum: Option<&str>) -> PathBuf {
+    let package_dir = dir.join(package);
+    std::fs::create_dir_all(package_dir.join("src")).unwrap();
+    let mut checksums = serde_json::Map::new();
+    for (path, contents) in &package_files(package) {
+        std::fs::write(package_dir.join(path), contents).unwrap();
+        checksums.insert(
+            (*path).to_owned(),
+            json!(format!("{:x}", Sha256::digest(contents))),
+        );
+    }
+    std::fs::write(
+        package_dir.join(".cargo-checksum.json"),
+        json!({ "files": checksums, "package": package_checksum }).to_string(),
+    )
+    .unwrap();
+    package_dir
+}
+
+/// Set up a workspace with a `Cargo.lock`, vendored copies of the third-party
+/// crates in `MockMetadata::simple()`, and their `.crate` files in a cache.
+fn vendored_workspace(dir: &Path) -> (Metadata, VendoredSources) {
+    let mock = MockMetadata::simple();
+    let mut metadata = mock.metadata();
+
+    let vendor_dir = dir.join("vendor");
+    let archive_dir = dir.join("cache").join("cache");
+    std::fs::create_dir_all(&archive_dir).unwrap();
+    let mut checksums = SortedMap::new();
+    for package in ["third-party1", "third-party2", "transitive-third-party1"] {
+        let (archive, checksum) = crate_archive(package);
+        std::fs::write(archive_dir.join(format!("{package}-10.0.0.crate")), archive).unwrap();
+        vendor_package(&vendor_dir, package, Some(&checksum));
+        checksums.insert(package, checksum);
+    }
+    mock_lockfile(&mut metadata, dir, |package| {
+        checksums.get(package.name.as_str()).cloned()
+    });
+
+    (metadata, VendoredSources::new(vendor_dir))
+}
+
+/// Verify the vendored sources in a workspace set up by `vendored_workspace`,
+/// without the network.
+fn verify(
+    metadata: &Metadata,
+    vendored_sources: &VendoredSources,
+    dir: &Path,
+) -> Result<(), crate::errors::VendorErrors> {
+    let mut cfg = mock_cfg(metadata);
+    cfg._rest.cache_dir = dir.join("cache");
+    cfg._rest.mock_cache = false;
+    let cache = crate::storage::Cache::acquire(&cfg).unwrap();
+    TEST_RUNTIME.block_on(vendored_sources.verify(metadata, None, &cache))
+}
+
+#[test]
+fn vendored_sources_unmodified() {
+    // Unmodified vendored crates matching Cargo.lock are accepted.
+
+    let dir = tempfile::tempdir().unwrap();
+    let (metadata, vendored_sources) = vendored_workspace(dir.path());
+
+    verify(&metadata, &vendored_sources, dir.path()).unwrap();
+    assert_eq!(
+        vendored_sources.package_dir("third-party1", &ver(DEFAULT_VER).semver),
+        Some(dir.path().join("vendor").join("third-party1"))
+    );
+    assert_eq!(
+        vendored_sources.package_dir("third-party1", &ver(5).semver),
+        None
+    );
+}
+
+#[test]
+fn vendored_sources_modified() {
+    // (Fail) Vendored crates which have been edited, have had files added or
+    // removed, or are missing entirely are all reported.
+
+    let dir = tempfile::tempdir().unwrap();
+    let (metadata, vendored_sources) = vendored_workspace(dir.path());
+
+    let vendor_dir = dir.path().join("vendor");
+    std::fs::write(
+        vendor_dir.join("third-party1/src/lib.rs"),
+        "pub fn hello() { evil() }\n",
+    )
+    .unwrap();
+    std::fs::write(vendor_dir.join("third-party2/build.rs"), "fn main() {}\n").unwrap();
+    std::fs::remove_dir_all(vendor_dir.join("transitive-third-party1")).unwrap();
+
+    let errors = verify(&metadata, &vendored_sources, dir.path())
+        .unwrap_err()
+        .errors;
+    assert_eq!(errors.len(), 3, "{errors:?}");
+    assert!(errors.iter().any(|error| matches!(
+        error,
+        VendorError::ModifiedFile { package, path, .. }
+            if package == "third-party1" && path == "src/lib.rs"
+    )));
+    assert!(errors.iter().any(|error| matches!(
+        error,
+        VendorError::AddedFile { package, path, .. }
+            if package == "third-party2" && path == "build.rs"
+    )));
+    assert!(errors.iter().any(|error| matches!(
+        error,
+        VendorError::MissingPackage { package, .. } if package == "transitive-third-party1"
+    )));
+}
+
+#[test]
+fn vendored_sources_lock_checksum() {
+    // (Fail) Vendored crates must have been vendored from the `.crate` file
+    // recorded in Cargo.lock, even if their own checksums are consistent.
+
+    let dir = tempfile::tempdir().unwrap();
+    let (metadata, vendored_sources) = vendored_workspace(dir.path());
+
+    let vendor_dir = dir.path().join("vendor");
+    std::fs::remove_dir_all(vendor_dir.join("third-party2")).unwrap();
+    vendor_package(
+        &vendor_dir,
+        "third-party2",
+        Some("0000000000000000000000000000000000000000000000000000000000000000"),
+    );
+
+    let errors = verify(&metadata, &vendored_sources, dir.path())
+        .unwrap_err()
+        .errors;
+    assert!(
+        matches!(
+            &errors[..],
+            [VendorError::PackageChecksum { package, .. }] if package == "third-party2"
+        ),
+        "{errors:?}"
+    );
+}
+
+/// Edit a file in a vendored crate, updating its `.cargo-checksum.json` to
+/// match, and setting the recorded `.crate` checksum to `package_checksum`.
+fn tamper_consistently(package_dir: &Path, path: &str, package_checksum: Value) {
+    let contents = "pub fn hello() { evil() }\n";
+    std::fs::write(package_dir.join(path), contents).unwrap();
+    let checksums_path = package_dir.join(".cargo-checksum.json");
+    let mut checksums: Value =
+        serde_json::from_str(&std::fs::read_to_string(&checksums_path).unwrap()).unwrap();
+    checksums["files"][path] = json!(format!("{:x}", Sha256::digest(contents)));
+    checksums["package"] = package_checksum;
+    std::fs::write(&checksums_path, checksums.to_string()).unwrap();
+}
+
+#[test]
+fn vendored_sources_modified_with_checksums() {
+    // (Fail) Vendored files are compared against the `.crate` file from
+    // Cargo.lock, so editing `.cargo-checksum.json` to match isn't enough.
+
+    let dir = tempfile::tempdir().unwrap();
+    let (metadata, vendored_sources) = vendored_workspace(dir.path());
+
+    let package_dir = dir.path().join("vendor").join("third-party1");
+    let checksums: Value = serde_json::from_str(
+        &std::fs::read_to_string(package_dir.join(".cargo-checksum.json")).unwrap(),
+    )
+    .unwrap();
+    tamper_consistently(&package_dir, "src/lib.rs", checksums["package"].clone());
+
+    let errors = verify(&metadata, &vendored_sources, dir.path())
+        .unwrap_err()
+        .errors;
+    assert!(
+        matches!(
+            &errors[..],
+            [VendorError::ModifiedFile { package, path, .. }]
+                if package == "third-party1" && path == "src/lib.rs"
+        ),
+        "{errors:?}"
+    );
+}
+
+#[test]
+fn vendored_sources_null_package_checksum() {
+    // (Fail) A vendored crate can't avoid being compared against Cargo.lock by
+    // omitting the checksum of the `.crate` file it was vendored from.
+
+    let dir = tempfile::tempdir().unwrap();
+    let (metadata, vendored_sources) = vendored_workspace(dir.path());
+
+    let package_dir = dir.path().join("vendor").join("third-party1");
+    tamper_consistently(&package_dir, "src/lib.rs", Value::Null);
+
+    let errors = verify(&metadata, &vendored_sources, dir.path())
+        .unwrap_err()
+        .errors;
+    assert!(
+        matches!(
+            &errors[..],
+            [VendorError::MissingPackageChecksum { package, .. }] if package == "third-party1"
+        ),
+        "{errors:?}"
+    );
+}
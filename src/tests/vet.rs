@@ -2634,3 +2634,296 @@ fn builtin_simple_registry_suggestions() {
         Some(&network)
     );
 }
+
+#[test]
+fn mock_simple_targets_json() {
+    // (Pass) The JSON report records the targets the graph was filtered to.
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+
+    let metadata = mock.metadata();
+    let (config, audits, imports) = files_inited(&metadata);
+
+    let store = Store::mock(config, audits, imports);
+    let report = crate::resolver::resolve(&metadata, None, &store, mock_today());
+
+    let mut cfg = mock_cfg(&metadata);
+    cfg.targets = vec![
+        "x86_64-unknown-linux-gnu".to_owned(),
+        "aarch64-unknown-linux-gnu".to_owned(),
+    ];
+
+    let output = BasicTestOutput::new();
+    report
+        .print_json(&output.clone().as_dyn(), &cfg, None)
+        .unwrap();
+    insta::assert_snapshot!(output.to_string());
+}
+
+#[test]
+fn mock_simple_expired_audit() {
+    // (Fail) Audits and exemptions which have expired are ignored
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+
+    let metadata = mock.metadata();
+    let (mut config, mut audits, imports) = files_full_audited(&metadata);
+
+    let expired = mock_today() - chrono::Duration::days(1);
+    audits
+        .audits
+        .get_mut("transitive-third-party1")
+        .unwrap()
+        .iter_mut()
+        .for_each(|audit| audit.expires = Some(expired));
+    audits.audits.get_mut("third-party2").unwrap().clear();
+    config.exemptions.insert(
+        "third-party2".to_owned(),
+        vec![ExemptedDependency {
+            expires: Some(mock_today()),
+            ..exemptions(ver(DEFAULT_VER), DEFAULT_CRIT)
+        }],
+    );
+
+    let store = Store::mock(config, audits, imports);
+
+    assert_report_snapshot!("mock-simple-expired-audit", metadata, store);
+}
+
+#[test]
+fn mock_simple_unexpired_audit() {
+    // (Pass) Audits which expire in the future are still used
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::simple();
+
+    let metadata = mock.metadata();
+    let (config, mut audits, imports) = files_full_audited(&metadata);
+
+    let expires = mock_today() + chrono::Duration::days(1);
+    audits
+        .audits
+        .get_mut("transitive-third-party1")
+        .unwrap()
+        .iter_mut()
+        .for_each(|audit| audit.expires = Some(expires));
+
+    let store = Store::mock(config, audits, imports);
+
+    assert_report_snapshot!("mock-simple-unexpired-audit", metadata, store);
+}
+
+fn min_auditors_store(who: &[&str]) -> (Metadata, Store) {
+    let mock = MockMetadata::simple();
+
+    let metadata = mock.metadata();
+    let (mut config, mut audits, imports) = files_full_audited(&metadata);
+
+    config.policy.insert(
+        "third-party1".to_owned(),
+        PackagePolicyEntry::Unversioned(PolicyEntry {
+            min_auditors: Some(2),
+            ..default_policy()
+        }),
+    );
+    audits.audits.insert(
+        "third-party1".to_owned(),
+        who.iter()
+            .map(|who| AuditEntry {
+                who: vec![who.to_string().into()],
+                ..full_audit(ver(DEFAULT_VER), DEFAULT_CRIT)
+            })
+            .collect(),
+    );
+
+    let store = Store::mock(config, audits, imports);
+    (metadata, store)
+}
+
+#[test]
+fn mock_simple_min_auditors_missing() {
+    // (Fail) Only one auditor has reviewed a crate which requires two
+
+    let _enter = TEST_RUNTIME.enter();
+    let (metadata, store) = min_auditors_store(&["alice"]);
+
+    assert_report_snapshot!("mock-simple-min-auditors-missing", metadata, store);
+}
+
+#[test]
+fn mock_simple_min_auditors_same_auditor() {
+    // (Fail) The same auditor reviewing a crate twice isn't independent
+
+    let _enter = TEST_RUNTIME.enter();
+    let (metadata, store) = min_auditors_store(&["alice", "alice"]);
+
+    assert_report_snapshot!("mock-simple-min-auditors-same-auditor", metadata, store);
+}
+
+#[test]
+fn mock_simple_min_auditors_cosigned() {
+    // (Fail) A single audit co-signed by two auditors only counts once
+
+    let _enter = TEST_RUNTIME.enter();
+    let (metadata, mut store) = min_auditors_store(&["alice"]);
+    store.audits.audits.get_mut("third-party1").unwrap()[0]
+        .who
+        .push("bob".to_owned().into());
+
+    assert_report_snapshot!("mock-simple-min-auditors-cosigned", metadata, store);
+}
+
+#[test]
+fn mock_simple_min_auditors_cosigned_and_separate() {
+    // (Pass) A co-signed audit and a separate audit by one of its co-signers
+    // count as two independent auditors
+
+    let _enter = TEST_RUNTIME.enter();
+    let (metadata, mut store) = min_auditors_store(&["alice", "alice"]);
+    store.audits.audits.get_mut("third-party1").unwrap()[0]
+        .who
+        .push("bob".to_owned().into());
+
+    assert_report_snapshot!(
+        "mock-simple-min-auditors-cosigned-and-separate",
+        metadata,
+        store
+    );
+}
+
+#[test]
+fn mock_simple_min_auditors_satisfied() {
+    // (Pass) Two independent auditors have reviewed the crate
+
+    let _enter = TEST_RUNTIME.enter();
+    let (metadata, store) = min_auditors_store(&["alice", "bob"]);
+
+    assert_report_snapshot!("mock-simple-min-auditors-satisfied", metadata, store);
+}
+
+#[test]
+fn mock_simple_min_auditors_prune() {
+    // (Pass) Pruning keeps every audit counted towards min-auditors
+
+    let _enter = TEST_RUNTIME.enter();
+    let (metadata, mut store) = min_auditors_store(&["alice", "bob", "carol"]);
+    for audit in store.audits.audits.get_mut("third-party1").unwrap() {
+        audit.importable = false;
+    }
+
+    crate::resolver::update_store(&mock_cfg(&metadata), &mut store, |_| {
+        crate::resolver::UpdateMode {
+            search_mode: crate::resolver::SearchMode::PreferFreshImports,
+            prune_exemptions: true,
+            prune_non_importable_audits: true,
+            prune_imports: true,
+        }
+    });
+
+    assert_eq!(store.audits.audits["third-party1"].len(), 3);
+    assert_report_snapshot!("mock-simple-min-auditors-prune", metadata, store);
+}
+
+fn build_criteria_store(build_criteria: Option<CriteriaStr>) -> (Metadata, Store) {
+    let mock = MockMetadata::simple_deps();
+
+    let metadata = mock.metadata();
+    let (mut config, mut audits, imports) = builtin_files_full_audited(&metadata);
+
+    config.policy.insert(
+        "root".to_owned(),
+        PackagePolicyEntry::Unversioned(PolicyEntry {
+            build_criteria: build_criteria.map(|c| vec![c.to_owned().into()]),
+            ..default_policy()
+        }),
+    );
+    for name in ["build", "proc-macro", "build-proc-macro"] {
+        audits.audits.insert(
+            name.to_owned(),
+            vec![full_audit(ver(DEFAULT_VER), SAFE_TO_RUN)],
+        );
+    }
+
+    let store = Store::mock(config, audits, imports);
+    (metadata, store)
+}
+
+#[test]
+fn builtin_simple_deps_build_criteria() {
+    // (Pass) build-deps and proc-macros only need to satisfy build-criteria
+
+    let _enter = TEST_RUNTIME.enter();
+    let (metadata, store) = build_criteria_store(Some(SAFE_TO_RUN));
+
+    assert_report_snapshot!("builtin-simple-deps-build-criteria", metadata, store);
+}
+
+#[test]
+fn builtin_simple_deps_no_build_criteria() {
+    // (Fail) Without build-criteria, build-deps and proc-macros need `criteria`
+
+    let _enter = TEST_RUNTIME.enter();
+    let (metadata, store) = build_criteria_store(None);
+
+    assert_report_snapshot!("builtin-simple-deps-no-build-criteria", metadata, store);
+}
+
+#[test]
+fn builtin_build_criteria_transitive() {
+    // (Pass) build-criteria is inherited by the build-time dependencies of
+    // third-party crates, and by the dependencies of build-time dependencies.
+
+    let _enter = TEST_RUNTIME.enter();
+    let mock = MockMetadata::new(vec![
+        MockPackage {
+            name: "root",
+            is_workspace: true,
+            is_first_party: true,
+            deps: vec![dep("normal")],
+            ..Default::default()
+        },
+        MockPackage {
+            name: "normal",
+            deps: vec![dep("proc-macro")],
+            build_deps: vec![dep("build")],
+            ..Default::default()
+        },
+        MockPackage {
+            name: "proc-macro",
+            targets: vec!["proc-macro"],
+            deps: vec![dep("proc-macro-dep")],
+            ..Default::default()
+        },
+        MockPackage {
+            name: "proc-macro-dep",
+            ..Default::default()
+        },
+        MockPackage {
+            name: "build",
+            ..Default::default()
+        },
+    ]);
+
+    let metadata = mock.metadata();
+    let (mut config, mut audits, imports) = builtin_files_full_audited(&metadata);
+
+    config.policy.insert(
+        "root".to_owned(),
+        PackagePolicyEntry::Unversioned(PolicyEntry {
+            build_criteria: Some(vec![SAFE_TO_RUN.to_owned().into()]),
+            ..default_policy()
+        }),
+    );
+    for name in ["proc-macro", "proc-macro-dep", "build"] {
+        audits.audits.insert(
+            name.to_owned(),
+            vec![full_audit(ver(DEFAULT_VER), SAFE_TO_RUN)],
+        );
+    }
+
+    let store = Store::mock(config, audits, imports);
+
+    assert_report_snapshot!("builtin-build-criteria-transitive", metadata, store);
+}
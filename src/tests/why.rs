@@ -0,0 +1,158 @@
+use crate::commands::PlainOutputFormat;
+use crate::resolver::DepGraph;
+
+use super::*;
+
+fn get_why_output(
+    metadata: &Metadata,
+    store: &Store,
+    package: PackageStr<'_>,
+    criteria: CriteriaStr<'_>,
+    output_format: PlainOutputFormat,
+) -> String {
+    let graph = DepGraph::new(metadata, None, Some(&store.config));
+    let output = BasicTestOutput::new();
+    crate::commands::do_cmd_why(
+        &output.clone().as_dyn(),
+        &graph,
+        store,
+        package,
+        None,
+        criteria,
+        output_format,
+    )
+    .unwrap();
+    output.to_string()
+}
+
+#[test]
+fn why_root_default() {
+    let mock = MockMetadata::dev_detection();
+    let metadata = mock.metadata();
+    let (config, audits, imports) = builtin_files_full_audited(&metadata);
+    let store = Store::mock(config, audits, imports);
+
+    let output = get_why_output(
+        &metadata,
+        &store,
+        "normal",
+        SAFE_TO_DEPLOY,
+        PlainOutputFormat::Human,
+    );
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn why_default_dev_criteria() {
+    let mock = MockMetadata::dev_detection();
+    let metadata = mock.metadata();
+    let (config, audits, imports) = builtin_files_full_audited(&metadata);
+    let store = Store::mock(config, audits, imports);
+
+    let output = get_why_output(
+        &metadata,
+        &store,
+        "simple-dev-indirect",
+        SAFE_TO_RUN,
+        PlainOutputFormat::Human,
+    );
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn why_not_required() {
+    let mock = MockMetadata::dev_detection();
+    let metadata = mock.metadata();
+    let (config, audits, imports) = builtin_files_full_audited(&metadata);
+    let store = Store::mock(config, audits, imports);
+
+    let human = get_why_output(
+        &metadata,
+        &store,
+        "simple-dev-indirect",
+        SAFE_TO_DEPLOY,
+        PlainOutputFormat::Human,
+    );
+    insta::assert_snapshot!(human);
+    let json = get_why_output(
+        &metadata,
+        &store,
+        "simple-dev-indirect",
+        SAFE_TO_DEPLOY,
+        PlainOutputFormat::Json,
+    );
+    insta::assert_snapshot!(json);
+}
+
+#[test]
+fn why_criteria_policy() {
+    let mock = MockMetadata::dev_detection();
+    let metadata = mock.metadata();
+    let (mut config, audits, imports) = builtin_files_full_audited(&metadata);
+    config
+        .policy
+        .insert("root".to_owned(), self_policy([SAFE_TO_RUN]));
+    let store = Store::mock(config, audits, imports);
+
+    let output = get_why_output(
+        &metadata,
+        &store,
+        "normal",
+        SAFE_TO_RUN,
+        PlainOutputFormat::Human,
+    );
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn why_dependency_criteria() {
+    let mock = MockMetadata::dev_detection();
+    let metadata = mock.metadata();
+    let (mut config, audits, imports) = builtin_files_full_audited(&metadata);
+    config.policy.insert(
+        "root".to_owned(),
+        dep_policy([("simple-dev", [SAFE_TO_DEPLOY])]),
+    );
+    let store = Store::mock(config, audits, imports);
+
+    let output = get_why_output(
+        &metadata,
+        &store,
+        "simple-dev-indirect",
+        SAFE_TO_DEPLOY,
+        PlainOutputFormat::Human,
+    );
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn why_build_criteria() {
+    let mock = MockMetadata::simple_deps();
+    let metadata = mock.metadata();
+    let (mut config, audits, imports) = builtin_files_full_audited(&metadata);
+    config.policy.insert(
+        "root".to_owned(),
+        PackagePolicyEntry::Unversioned(PolicyEntry {
+            build_criteria: Some(vec![SAFE_TO_RUN.to_owned().into()]),
+            ..default_policy()
+        }),
+    );
+    let store = Store::mock(config, audits, imports);
+
+    let human = get_why_output(
+        &metadata,
+        &store,
+        "build-proc-macro",
+        SAFE_TO_RUN,
+        PlainOutputFormat::Human,
+    );
+    insta::assert_snapshot!(human);
+    let json = get_why_output(
+        &metadata,
+        &store,
+        "build-proc-macro",
+        SAFE_TO_RUN,
+        PlainOutputFormat::Json,
+    );
+    insta::assert_snapshot!(json);
+}